@@ -12,7 +12,7 @@ use criterion::{black_box, criterion_group, BenchmarkId, Criterion};
 use grepika::bench_utils::BenchmarkStats;
 use grepika::db::Database;
 use grepika::services::{Indexer, SearchService, TrigramIndex};
-use grepika::tools::{SearchInput, SearchMode};
+use grepika::tools::{RegionFilter, SearchInput, SearchMode};
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
@@ -91,6 +91,9 @@ fn bench_library_search(c: &mut Criterion) {
                     query: query.to_string(),
                     limit: 20,
                     mode: SearchMode::Combined,
+                    aggregate: None,
+                    max_per_directory: None,
+                    filter: RegionFilter::All,
                 };
                 black_box(grepika::tools::execute_search(&search, input))
             })
@@ -104,6 +107,9 @@ fn bench_library_search(c: &mut Criterion) {
                 query: "SearchService".to_string(),
                 limit: 20,
                 mode: SearchMode::Fts,
+                aggregate: None,
+                max_per_directory: None,
+                filter: RegionFilter::All,
             };
             black_box(grepika::tools::execute_search(&search, input))
         })
@@ -115,6 +121,9 @@ fn bench_library_search(c: &mut Criterion) {
                 query: "SearchService".to_string(),
                 limit: 20,
                 mode: SearchMode::Grep,
+                aggregate: None,
+                max_per_directory: None,
+                filter: RegionFilter::All,
             };
             black_box(grepika::tools::execute_search(&search, input))
         })
@@ -107,8 +107,7 @@ fn measure_grepika_search(service: &SearchService, query: &str, limit: usize) ->
                 .map(|s| MatchSnippetOutput {
                     line: s.line_number,
                     text: s.line_content.clone(),
-                    highlight_start: s.match_start,
-                    highlight_end: s.match_end,
+                    highlights: s.spans.clone(),
                 })
                 .collect();
 
@@ -116,6 +115,7 @@ fn measure_grepika_search(service: &SearchService, query: &str, limit: usize) ->
                 path: relative_path,
                 score: r.score.as_f64(),
                 sources,
+                match_count: r.match_count,
                 snippets,
             }
         })
@@ -125,6 +125,8 @@ fn measure_grepika_search(service: &SearchService, query: &str, limit: usize) ->
     let output = SearchOutput {
         results: items,
         has_more: false,
+        aggregates: Vec::new(),
+        collapsed: Vec::new(),
         hint: None,
     };
 
@@ -237,8 +239,7 @@ fn measure_output_formats(
                 .map(|s| MatchSnippetOutput {
                     line: s.line_number,
                     text: s.line_content.clone(),
-                    highlight_start: s.match_start,
-                    highlight_end: s.match_end,
+                    highlights: s.spans.clone(),
                 })
                 .collect();
 
@@ -246,6 +247,7 @@ fn measure_output_formats(
                 path: relative_path,
                 score: r.score.as_f64(),
                 sources,
+                match_count: r.match_count,
                 snippets,
             }
         })
@@ -254,6 +256,8 @@ fn measure_output_formats(
     let search_output = SearchOutput {
         results: items,
         has_more: false,
+        aggregates: Vec::new(),
+        collapsed: Vec::new(),
         hint: None,
     };
 
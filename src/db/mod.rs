@@ -48,6 +48,48 @@ pub struct FileData {
     pub hash: u64,
 }
 
+/// A symbol row for the `symbols` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolData {
+    /// Symbol name
+    pub name: String,
+    /// Symbol kind (fn, struct, class, impl, ...)
+    pub kind: String,
+    /// Start line (1-indexed)
+    pub line: usize,
+    /// End line (1-indexed), if detectable
+    pub end_line: Option<usize>,
+    /// Name of the enclosing symbol (e.g. the type of an `impl` block)
+    pub parent: Option<String>,
+    /// Types it extends or implements, without generics or qualifiers
+    pub supertypes: Vec<String>,
+}
+
+/// An import row for the `imports` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportData {
+    /// Line of the import statement (1-indexed)
+    pub line: usize,
+    /// Imported module path as written (`crate::db`, `.models`, `./util`)
+    pub spec: String,
+}
+
+/// Reads a [`SymbolData`] from the `name, kind, line, end_line, parent,
+/// supertypes` columns starting at `offset`.
+fn symbol_from_row(row: &rusqlite::Row<'_>, offset: usize) -> rusqlite::Result<SymbolData> {
+    Ok(SymbolData {
+        name: row.get(offset)?,
+        kind: row.get(offset + 1)?,
+        line: row.get::<_, i64>(offset + 2)? as usize,
+        end_line: row.get::<_, Option<i64>>(offset + 3)?.map(|l| l as usize),
+        parent: row.get(offset + 4)?,
+        supertypes: row
+            .get::<_, Option<String>>(offset + 5)?
+            .map(|s| s.split(',').map(String::from).collect())
+            .unwrap_or_default(),
+    })
+}
+
 /// Executes a `query_row` and maps `QueryReturnedNoRows` to `Ok(None)`.
 fn query_row_optional<T, P, F>(
     conn: &rusqlite::Connection,
@@ -66,6 +108,18 @@ where
     }
 }
 
+/// Escapes `%`, `_` and `\` for use in a `LIKE ... ESCAPE '\'` pattern.
+pub fn escape_like(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
 /// Runs a closure inside a `BEGIN IMMEDIATE` / `COMMIT` transaction.
 /// Rolls back on error to release the write lock.
 fn with_transaction<T>(
@@ -235,6 +289,68 @@ impl Database {
         Ok(results)
     }
 
+    /// Returns indexed FTS tokens whose length is within `min_len..=max_len`.
+    ///
+    /// Tokens are as stored by the tokenizer: lowercased and porter-stemmed.
+    pub fn vocab_terms(&self, min_len: usize, max_len: usize) -> DbResult<Vec<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn
+            .prepare_cached("SELECT term FROM files_vocab WHERE length(term) BETWEEN ?1 AND ?2")?;
+        let terms = stmt
+            .query_map(rusqlite::params![min_len as i64, max_len as i64], |row| {
+                row.get::<_, String>(0)
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(terms)
+    }
+
+    /// Returns up to `limit` files containing the exact FTS token `term`.
+    pub fn files_with_term(&self, term: &str, limit: usize) -> DbResult<Vec<FileId>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare_cached(
+            "SELECT DISTINCT doc FROM files_vocab_instance WHERE term = ?1 LIMIT ?2",
+        )?;
+        let ids = stmt
+            .query_map(rusqlite::params![term, limit as i64], |row| {
+                Ok(FileId::new(row.get::<_, u32>(0)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+
+    /// Returns the content of each file with FTS5 `highlight()` markers.
+    ///
+    /// Tokens matching `query` are wrapped in `open`/`close`. Files that do
+    /// not match are omitted. One lookup per file: callers pass only the
+    /// handful of top results that need snippets.
+    pub fn fts_highlight(
+        &self,
+        query: &str,
+        file_ids: &[FileId],
+        open: &str,
+        close: &str,
+    ) -> DbResult<HashMap<FileId, String>> {
+        let conn = self.conn()?;
+        let mut results = HashMap::with_capacity(file_ids.len());
+        for &file_id in file_ids {
+            let highlighted: Option<String> = query_row_optional(
+                &conn,
+                r"
+                SELECT highlight(files_fts, 2, ?3, ?4)
+                FROM files_fts
+                WHERE files_fts MATCH ?1 AND rowid = ?2
+                ",
+                rusqlite::params![query, file_id.as_u32(), open, close],
+                |row| row.get(0),
+            )?;
+            if let Some(text) = highlighted {
+                results.insert(file_id, text);
+            }
+        }
+
+        Ok(results)
+    }
+
     /// Upserts a file into the database.
     ///
     /// Uses `RETURNING file_id` to get the ID in a single statement,
@@ -417,6 +533,11 @@ impl Database {
     /// Gets all file_id → path mappings for cache population.
     pub fn get_all_file_paths(&self) -> DbResult<Vec<(FileId, String)>> {
         let conn = self.conn()?;
+        Self::get_all_file_paths_on(&conn)
+    }
+
+    /// Gets all file_id → path mappings using a caller-provided connection.
+    pub fn get_all_file_paths_on(conn: &rusqlite::Connection) -> DbResult<Vec<(FileId, String)>> {
         let mut stmt = conn.prepare_cached("SELECT file_id, path FROM files")?;
         let results = stmt
             .query_map([], |row| {
@@ -519,6 +640,282 @@ impl Database {
         })
     }
 
+    /// Replaces the stored symbols of each file in one transaction.
+    ///
+    /// Existing rows for every listed file are deleted first, so files
+    /// that no longer declare anything end up with no rows.
+    pub fn replace_symbols_batch_on(
+        conn: &rusqlite::Connection,
+        files: &[(FileId, Vec<SymbolData>)],
+    ) -> DbResult<()> {
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        with_transaction(conn, || {
+            let mut delete = conn.prepare_cached("DELETE FROM symbols WHERE file_id = ?1")?;
+            let mut insert = conn.prepare_cached(
+                "INSERT INTO symbols (file_id, name, kind, line, end_line, parent, supertypes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+
+            for (file_id, symbols) in files {
+                delete.execute([file_id.as_u32()])?;
+                for sym in symbols {
+                    insert.execute(rusqlite::params![
+                        file_id.as_u32(),
+                        &sym.name,
+                        &sym.kind,
+                        sym.line as i64,
+                        sym.end_line.map(|l| l as i64),
+                        &sym.parent,
+                        (!sym.supertypes.is_empty()).then(|| sym.supertypes.join(",")),
+                    ])?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Replaces the stored symbols of one file.
+    pub fn replace_symbols(&self, file_id: FileId, symbols: Vec<SymbolData>) -> DbResult<()> {
+        let conn = self.conn()?;
+        Self::replace_symbols_batch_on(&conn, &[(file_id, symbols)])
+    }
+
+    /// Replaces the stored imports of each file in one transaction.
+    ///
+    /// New rows start unresolved; [`Self::set_import_targets_on`] fills in
+    /// their targets once every file of the run is stored.
+    pub fn replace_imports_batch_on(
+        conn: &rusqlite::Connection,
+        files: &[(FileId, Vec<ImportData>)],
+    ) -> DbResult<()> {
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        with_transaction(conn, || {
+            let mut delete = conn.prepare_cached("DELETE FROM imports WHERE file_id = ?1")?;
+            let mut insert = conn
+                .prepare_cached("INSERT INTO imports (file_id, line, spec) VALUES (?1, ?2, ?3)")?;
+
+            for (file_id, imports) in files {
+                delete.execute([file_id.as_u32()])?;
+                for import in imports {
+                    insert.execute(rusqlite::params![
+                        file_id.as_u32(),
+                        import.line as i64,
+                        &import.spec,
+                    ])?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Replaces the stored imports of one file.
+    pub fn replace_imports(&self, file_id: FileId, imports: Vec<ImportData>) -> DbResult<()> {
+        let conn = self.conn()?;
+        Self::replace_imports_batch_on(&conn, &[(file_id, imports)])
+    }
+
+    /// Gets the imports without a target as `(file_id, importer path, import)`.
+    ///
+    /// These are new rows, rows whose target file was deleted, and imports
+    /// of packages outside the workspace.
+    pub fn pending_imports_on(
+        conn: &rusqlite::Connection,
+    ) -> DbResult<Vec<(FileId, String, ImportData)>> {
+        let mut stmt = conn.prepare_cached(
+            "SELECT DISTINCT i.file_id, f.path, i.line, i.spec
+             FROM imports i JOIN files f ON f.file_id = i.file_id
+             WHERE i.target_id IS NULL",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    FileId::new(row.get::<_, u32>(0)?),
+                    row.get(1)?,
+                    ImportData {
+                        line: row.get::<_, i64>(2)? as usize,
+                        spec: row.get(3)?,
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Records the files each import resolves to, in one transaction.
+    ///
+    /// An import may resolve to several files (a Go package is a
+    /// directory), so its rows are replaced by one row per target.
+    pub fn set_import_targets_on(
+        conn: &rusqlite::Connection,
+        resolved: &[(FileId, ImportData, Vec<FileId>)],
+    ) -> DbResult<()> {
+        if resolved.is_empty() {
+            return Ok(());
+        }
+
+        with_transaction(conn, || {
+            let mut delete = conn.prepare_cached(
+                "DELETE FROM imports WHERE file_id = ?1 AND line = ?2 AND spec = ?3",
+            )?;
+            let mut insert = conn.prepare_cached(
+                "INSERT INTO imports (file_id, line, spec, target_id) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+
+            for (file_id, import, targets) in resolved {
+                let line = import.line as i64;
+                delete.execute(rusqlite::params![file_id.as_u32(), line, &import.spec])?;
+                for target in targets {
+                    insert.execute(rusqlite::params![
+                        file_id.as_u32(),
+                        line,
+                        &import.spec,
+                        target.as_u32(),
+                    ])?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Gets a file's imports as `(import, target path)`, in line order.
+    /// The target is `None` for imports outside the workspace.
+    pub fn get_file_imports(&self, file_id: FileId) -> DbResult<Vec<(ImportData, Option<String>)>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare_cached(
+            "SELECT DISTINCT i.line, i.spec, t.path
+             FROM imports i LEFT JOIN files t ON t.file_id = i.target_id
+             WHERE i.file_id = ?1
+             ORDER BY i.line, t.path",
+        )?;
+        let rows = stmt
+            .query_map([file_id.as_u32()], |row| {
+                Ok((
+                    ImportData {
+                        line: row.get::<_, i64>(0)? as usize,
+                        spec: row.get(1)?,
+                    },
+                    row.get(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Gets the files importing `file_id` as `(importer path, line)`, in
+    /// path and line order.
+    pub fn get_importers(&self, file_id: FileId) -> DbResult<Vec<(String, usize)>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare_cached(
+            "SELECT DISTINCT f.path, i.line
+             FROM imports i JOIN files f ON f.file_id = i.file_id
+             WHERE i.target_id = ?1
+             ORDER BY f.path, i.line",
+        )?;
+        let rows = stmt
+            .query_map([file_id.as_u32()], |row| {
+                Ok((row.get(0)?, row.get::<_, i64>(1)? as usize))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Gets the stored symbols of a file, in line order.
+    pub fn get_file_symbols(&self, file_id: FileId) -> DbResult<Vec<SymbolData>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare_cached(
+            "SELECT name, kind, line, end_line, parent, supertypes FROM symbols
+             WHERE file_id = ?1 ORDER BY line, symbol_id",
+        )?;
+        let rows = stmt
+            .query_map([file_id.as_u32()], |row| symbol_from_row(row, 0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Finds symbols whose name matches a `LIKE` pattern (ASCII
+    /// case-insensitive), shortest names first.
+    ///
+    /// `kind` keeps one symbol kind; `path_like` is a `LIKE` pattern over
+    /// the stored (absolute) file path. Returns `(path, symbol)` pairs.
+    pub fn find_symbols(
+        &self,
+        name_like: &str,
+        kind: Option<&str>,
+        path_like: Option<&str>,
+        limit: usize,
+    ) -> DbResult<Vec<(String, SymbolData)>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare_cached(
+            r"
+            SELECT f.path, s.name, s.kind, s.line, s.end_line, s.parent, s.supertypes
+            FROM symbols s JOIN files f ON f.file_id = s.file_id
+            WHERE s.name LIKE ?1 ESCAPE '\'
+              AND (?2 IS NULL OR s.kind = ?2)
+              AND (?3 IS NULL OR f.path LIKE ?3 ESCAPE '\')
+            ORDER BY length(s.name), s.name, f.path, s.line
+            LIMIT ?4
+            ",
+        )?;
+        let rows = stmt
+            .query_map(
+                rusqlite::params![name_like, kind, path_like, limit as i64],
+                |row| Ok((row.get::<_, String>(0)?, symbol_from_row(row, 1)?)),
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Finds symbols declaring `name` among their supertypes (exact,
+    /// case-sensitive), in path and line order. Returns `(path, symbol)`
+    /// pairs.
+    pub fn find_subtypes(&self, name: &str, limit: usize) -> DbResult<Vec<(String, SymbolData)>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare_cached(
+            r"
+            SELECT f.path, s.name, s.kind, s.line, s.end_line, s.parent, s.supertypes
+            FROM symbols s JOIN files f ON f.file_id = s.file_id
+            WHERE s.supertypes IS NOT NULL
+              AND instr(',' || s.supertypes || ',', ',' || ?1 || ',') > 0
+            ORDER BY f.path, s.line
+            LIMIT ?2
+            ",
+        )?;
+        let rows = stmt
+            .query_map(rusqlite::params![name, limit as i64], |row| {
+                Ok((row.get::<_, String>(0)?, symbol_from_row(row, 1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Gets distinct symbol names with a length in `[min_len, max_len]`,
+    /// for fuzzy name matching.
+    pub fn symbol_names(&self, min_len: usize, max_len: usize) -> DbResult<Vec<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare_cached(
+            "SELECT DISTINCT name FROM symbols WHERE length(name) BETWEEN ?1 AND ?2",
+        )?;
+        let names = stmt
+            .query_map(rusqlite::params![min_len as i64, max_len as i64], |row| {
+                row.get(0)
+            })?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(names)
+    }
+
+    /// Gets total symbol count.
+    pub fn symbol_count(&self) -> DbResult<u64> {
+        let conn = self.conn()?;
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM symbols", [], |row| row.get(0))?;
+        Ok(count as u64)
+    }
+
     /// Gets file path by ID (without loading content).
     pub fn get_file_path(&self, file_id: FileId) -> DbResult<Option<String>> {
         let conn = self.conn()?;
@@ -711,6 +1108,142 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_replace_symbols_and_cascade_on_delete() {
+        let db = Database::in_memory().unwrap();
+        let id = db
+            .upsert_file("src/lib.rs", "pub struct A;\nfn b() {}", 0x1)
+            .unwrap();
+        let sym = |name: &str, line| SymbolData {
+            name: name.to_string(),
+            kind: "fn".to_string(),
+            line,
+            end_line: None,
+            parent: None,
+            supertypes: Vec::new(),
+        };
+
+        db.replace_symbols(id, vec![sym("old", 1)]).unwrap();
+        db.replace_symbols(id, vec![sym("b", 2), sym("a", 1)])
+            .unwrap();
+        let names: Vec<String> = db
+            .get_file_symbols(id)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["a", "b"]);
+
+        db.delete_file("src/lib.rs").unwrap();
+        assert_eq!(db.symbol_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_import_targets_resolve_and_clear_on_delete() {
+        let db = Database::in_memory().unwrap();
+        let main = db
+            .upsert_file("src/main.rs", "mod db;\nuse serde;", 0x1)
+            .unwrap();
+        let target = db.upsert_file("src/db.rs", "", 0x2).unwrap();
+        let import = |line, spec: &str| ImportData {
+            line,
+            spec: spec.to_string(),
+        };
+        db.replace_imports(main, vec![import(1, "self::db"), import(2, "serde")])
+            .unwrap();
+
+        {
+            let conn = db.conn().unwrap();
+            assert_eq!(Database::pending_imports_on(&conn).unwrap().len(), 2);
+            Database::set_import_targets_on(&conn, &[(main, import(1, "self::db"), vec![target])])
+                .unwrap();
+        }
+
+        let imports = db.get_file_imports(main).unwrap();
+        assert_eq!(
+            imports,
+            vec![
+                (import(1, "self::db"), Some("src/db.rs".to_string())),
+                (import(2, "serde"), None),
+            ]
+        );
+        assert_eq!(
+            db.get_importers(target).unwrap(),
+            vec![("src/main.rs".to_string(), 1)]
+        );
+
+        // Deleting the target leaves the import pending again
+        db.delete_file("src/db.rs").unwrap();
+        let conn = db.conn().unwrap();
+        assert_eq!(Database::pending_imports_on(&conn).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_find_symbols_by_pattern() {
+        let db = Database::in_memory().unwrap();
+        let a = db.upsert_file("/r/src/config.rs", "", 0x1).unwrap();
+        let b = db.upsert_file("/r/tests/config_test.rs", "", 0x2).unwrap();
+        let sym = |name: &str, kind: &str| SymbolData {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            line: 1,
+            end_line: None,
+            parent: None,
+            supertypes: Vec::new(),
+        };
+        db.replace_symbols(a, vec![sym("Config", "struct"), sym("config_path", "fn")])
+            .unwrap();
+        db.replace_symbols(b, vec![sym("Config_", "struct")])
+            .unwrap();
+
+        let names = |rows: Vec<(String, SymbolData)>| -> Vec<String> {
+            rows.into_iter().map(|(_, s)| s.name).collect()
+        };
+        // `_` is escaped, so it only matches itself
+        assert_eq!(
+            names(db.find_symbols("config\\_%", None, None, 10).unwrap()),
+            vec!["Config_", "config_path"]
+        );
+        assert_eq!(
+            names(
+                db.find_symbols("config%", Some("struct"), Some("/r/src/%"), 10)
+                    .unwrap()
+            ),
+            vec!["Config"]
+        );
+        assert_eq!(db.symbol_names(6, 6).unwrap(), vec!["Config"]);
+        assert_eq!(escape_like("a_b%"), "a\\_b\\%");
+    }
+
+    #[test]
+    fn test_find_subtypes_matches_whole_names() {
+        let db = Database::in_memory().unwrap();
+        let id = db.upsert_file("/r/models.py", "", 0x1).unwrap();
+        let class = |name: &str, supertypes: &[&str]| SymbolData {
+            name: name.to_string(),
+            kind: "class".to_string(),
+            line: 1,
+            end_line: None,
+            parent: None,
+            supertypes: supertypes.iter().map(|s| s.to_string()).collect(),
+        };
+        db.replace_symbols(
+            id,
+            vec![
+                class("Base", &[]),
+                class("User", &["Base", "Mixin"]),
+                class("Admin", &["BaseUser"]),
+            ],
+        )
+        .unwrap();
+
+        let found = db.find_subtypes("Base", 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1.name, "User");
+        assert_eq!(found[0].1.supertypes, vec!["Base", "Mixin"]);
+        assert!(db.find_subtypes("base", 10).unwrap().is_empty());
+    }
+
     #[test]
     fn test_delete_nonexistent_file() {
         let db = Database::in_memory().unwrap();
@@ -6,7 +6,10 @@ use rusqlite::Connection;
 /// Current schema version for migrations.
 /// v2: Changed hash from TEXT (SHA256 hex) to INTEGER (xxHash u64)
 /// v3: Replaced 3-byte trigram keys with u64 sparse n-gram keys
-pub const SCHEMA_VERSION: u32 = 3;
+/// v4: Added symbols table populated at index time
+/// v5: Added imports table (file dependency graph)
+/// v6: Added symbols.supertypes (type hierarchy)
+pub const SCHEMA_VERSION: u32 = 6;
 
 /// Initializes the database schema.
 ///
@@ -27,11 +30,15 @@ pub fn init_schema(conn: &Connection) -> DbResult<()> {
         .ok();
 
     match existing_version {
-        Some(v) if v >= SCHEMA_VERSION => return Ok(()), // Already up to date
+        Some(v) if v >= SCHEMA_VERSION => return create_vocab_tables(conn), // Already up to date
         Some(_) => {
             // Old version - drop everything and recreate
             conn.execute_batch(
                 r"
+                DROP TABLE IF EXISTS files_vocab;
+                DROP TABLE IF EXISTS files_vocab_instance;
+                DROP TABLE IF EXISTS imports;
+                DROP TABLE IF EXISTS symbols;
                 DROP TABLE IF EXISTS files;
                 DROP TABLE IF EXISTS files_fts;
                 DROP TABLE IF EXISTS trigrams;
@@ -98,6 +105,43 @@ pub fn init_schema(conn: &Connection) -> DbResult<()> {
             file_ids BLOB NOT NULL
         ) WITHOUT ROWID;
 
+        -- Symbol index: declarations extracted at index time from
+        -- tree-sitter syntax trees (line heuristics for files without
+        -- a grammar)
+        -- Rows go away with their file via ON DELETE CASCADE.
+        -- name is NOCASE so the name index serves case-insensitive
+        -- equality and LIKE 'prefix%' lookups.
+        -- supertypes lists the extended/implemented type names,
+        -- comma-separated, NULL when there are none.
+        CREATE TABLE IF NOT EXISTS symbols (
+            symbol_id INTEGER PRIMARY KEY,
+            file_id INTEGER NOT NULL REFERENCES files(file_id) ON DELETE CASCADE,
+            name TEXT NOT NULL COLLATE NOCASE,
+            kind TEXT NOT NULL,
+            line INTEGER NOT NULL,
+            end_line INTEGER,
+            parent TEXT,
+            supertypes TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_symbols_name ON symbols(name);
+        CREATE INDEX IF NOT EXISTS idx_symbols_file ON symbols(file_id);
+
+        -- Import statements extracted at index time (file dependency graph)
+        -- spec is the imported module path as written; target_id is the
+        -- workspace file it resolves to, NULL while unresolved or for
+        -- packages outside the workspace.
+        CREATE TABLE IF NOT EXISTS imports (
+            import_id INTEGER PRIMARY KEY,
+            file_id INTEGER NOT NULL REFERENCES files(file_id) ON DELETE CASCADE,
+            line INTEGER NOT NULL,
+            spec TEXT NOT NULL,
+            target_id INTEGER REFERENCES files(file_id) ON DELETE SET NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_imports_file ON imports(file_id);
+        CREATE INDEX IF NOT EXISTS idx_imports_target ON imports(target_id);
+
         -- Schema version tracking
         CREATE TABLE IF NOT EXISTS schema_info (
             key TEXT PRIMARY KEY,
@@ -105,10 +149,29 @@ pub fn init_schema(conn: &Connection) -> DbResult<()> {
         ) WITHOUT ROWID;
 
         INSERT OR REPLACE INTO schema_info (key, value)
-        VALUES ('version', '3');
+        VALUES ('version', '6');
         "#,
     )?;
 
+    create_vocab_tables(conn)
+}
+
+/// Creates read-only `fts5vocab` views over `files_fts`.
+///
+/// They store nothing (rows are computed from the FTS index on demand),
+/// so they are created outside the versioned schema and added to existing
+/// databases without a rebuild.
+/// - `files_vocab`: one row per distinct token (fuzzy "did you mean")
+/// - `files_vocab_instance`: one row per token occurrence (token -> files)
+fn create_vocab_tables(conn: &Connection) -> DbResult<()> {
+    conn.execute_batch(
+        r"
+        CREATE VIRTUAL TABLE IF NOT EXISTS files_vocab
+            USING fts5vocab(files_fts, 'row');
+        CREATE VIRTUAL TABLE IF NOT EXISTS files_vocab_instance
+            USING fts5vocab(files_fts, 'instance');
+        ",
+    )?;
     Ok(())
 }
 
@@ -135,6 +198,9 @@ mod tests {
         assert!(tables.contains(&"files".to_string()));
         assert!(tables.contains(&"trigrams".to_string()));
         assert!(tables.contains(&"files_fts".to_string()));
+        assert!(tables.contains(&"files_vocab".to_string()));
+        assert!(tables.contains(&"symbols".to_string()));
+        assert!(tables.contains(&"imports".to_string()));
     }
 
     /// 6e: Verify SCHEMA_VERSION constant matches the value written to SQL.
@@ -4,8 +4,10 @@
 //! When `color` is true, ANSI escape codes are emitted via `owo_colors`.
 
 use crate::tools::{
-    ContextOutput, DiffOutput, GetOutput, IndexOutput, OutlineOutput, RefsOutput, SearchOutput,
-    StatsOutput, TocOutput,
+    CallEdge, CallsOutput, ContextOutput, DefinitionOutput, DepEdge, DepsOutput, DiffOutput,
+    GetOutput, HierarchyOutput, ImplementationsOutput, IndexOutput, OutlineNode, OutlineOutput,
+    Override, Reference, RefsOutput, SearchOutput, StatsOutput, Symbol, SymbolBodyOutput,
+    SymbolsOutput, TestsForOutput, TocOutput, TypeEdge,
 };
 use owo_colors::OwoColorize;
 use std::io::{self, Write};
@@ -20,16 +22,42 @@ fn expand_sources(compact: &str) -> String {
             'f' => Some("fts"),
             'g' => Some("grep"),
             't' => Some("trigram"),
+            'e' => Some("fuzzy"),
             _ => None,
         })
         .collect::<Vec<_>>()
         .join("+")
 }
 
+/// Renders `text` with each `[start, end)` byte span in bold red.
+///
+/// Spans that overlap, run backwards or split a UTF-8 char are skipped.
+fn highlight_spans(text: &str, spans: &[(usize, usize)]) -> String {
+    let mut out = String::with_capacity(text.len() + spans.len() * 16);
+    let mut pos = 0;
+    for &(start, end) in spans {
+        if start < pos
+            || end <= start
+            || !text.is_char_boundary(start)
+            || !text.is_char_boundary(end)
+        {
+            continue;
+        }
+        out.push_str(&text[pos..start]);
+        out.push_str(&(&text[start..end]).red().bold().to_string());
+        pos = end;
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
 pub fn fmt_search(w: &mut impl Write, out: &SearchOutput, color: bool) -> io::Result<()> {
     for item in &out.results {
-        // Path + score + sources
-        let sources = expand_sources(&item.sources);
+        // Path + score + sources (+ match count)
+        let mut sources = expand_sources(&item.sources);
+        if item.match_count > 0 {
+            sources = format!("{sources} · {} matches", item.match_count);
+        }
         if color {
             writeln!(
                 w,
@@ -45,13 +73,61 @@ pub fn fmt_search(w: &mut impl Write, out: &SearchOutput, color: bool) -> io::Re
         // Snippets
         for s in &item.snippets {
             if color {
-                writeln!(w, "  {}{}", format_args!("{:>5}│ ", s.line).green(), s.text)?;
+                writeln!(
+                    w,
+                    "  {}{}",
+                    format_args!("{:>5}│ ", s.line).green(),
+                    highlight_spans(&s.text, &s.highlights)
+                )?;
             } else {
                 writeln!(w, "  {:>5}│ {}", s.line, s.text)?;
             }
         }
     }
 
+    // Aggregate buckets: matches, files, key
+    for bucket in &out.aggregates {
+        let files = if bucket.files == 1 { "file" } else { "files" };
+        if color {
+            writeln!(
+                w,
+                "{:>7}  {}  {}",
+                bucket.matches.to_string().green(),
+                format_args!("{:>4} {files}", bucket.files).dimmed(),
+                bucket.key.bold()
+            )?;
+        } else {
+            writeln!(
+                w,
+                "{:>7}  {:>4} {files}  {}",
+                bucket.matches, bucket.files, bucket.key
+            )?;
+        }
+    }
+
+    // Areas held back by diversification
+    if !out.collapsed.is_empty() {
+        let areas = out
+            .collapsed
+            .iter()
+            .map(|c| format!("{} (+{})", c.directory, c.hidden))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if color {
+            writeln!(w, "{}", format_args!("collapsed: {areas}").dimmed())?;
+        } else {
+            writeln!(w, "collapsed: {areas}")?;
+        }
+    }
+
+    if let Some(hint) = &out.hint {
+        if color {
+            writeln!(w, "{}", hint.dimmed())?;
+        } else {
+            writeln!(w, "{hint}")?;
+        }
+    }
+
     if out.has_more {
         if color {
             writeln!(w, "{}", "... more results available".dimmed())?;
@@ -66,6 +142,12 @@ pub fn fmt_search(w: &mut impl Write, out: &SearchOutput, color: bool) -> io::Re
 // ── refs ────────────────────────────────────────────────────────────────────
 
 pub fn fmt_refs(w: &mut impl Write, out: &RefsOutput, color: bool) -> io::Result<()> {
+    if !out.groups.is_empty() {
+        return fmt_refs_groups(w, out, color);
+    }
+    if !out.files.is_empty() {
+        return fmt_refs_summary(w, out, color);
+    }
     let mut current_path = "";
 
     for r in &out.references {
@@ -88,59 +170,696 @@ pub fn fmt_refs(w: &mut impl Write, out: &RefsOutput, color: bool) -> io::Result
                 "definition" => format!("{:<12}", r.ref_type).green().to_string(),
                 "import" => format!("{:<12}", r.ref_type).blue().to_string(),
                 "type_usage" => format!("{:<12}", r.ref_type).yellow().to_string(),
+                "trait_impl" => format!("{:<12}", r.ref_type).magenta().to_string(),
+                "comment" => format!("{:<12}", r.ref_type).dimmed().to_string(),
                 _ => format!("{:<12}", r.ref_type),
             };
+            let suffix = ref_suffix(r).dimmed().to_string();
             writeln!(
                 w,
-                "  {} {}{}",
+                "  {} {}{}{}",
                 ref_type_colored,
                 format_args!("{:>5}│ ", r.line).dimmed(),
-                r.content
+                r.content,
+                suffix
+            )?;
+        } else {
+            let suffix = ref_suffix(r);
+            writeln!(
+                w,
+                "  {:<12} {:>5}│ {}{}",
+                r.ref_type, r.line, r.content, suffix
+            )?;
+        }
+    }
+
+    write_refs_footer(w, out, "references", color)
+}
+
+fn fmt_refs_summary(w: &mut impl Write, out: &RefsOutput, color: bool) -> io::Result<()> {
+    for d in &out.definitions {
+        let site = format!("{}:{}", d.path, d.line);
+        if color {
+            writeln!(w, "{} {}", site.green(), d.content)?;
+        } else {
+            writeln!(w, "{site} {}", d.content)?;
+        }
+    }
+    if !out.definitions.is_empty() {
+        writeln!(w)?;
+    }
+
+    let width = out.files.iter().map(|f| f.path.len()).max().unwrap_or(0);
+    for file in &out.files {
+        let mut counts: Vec<(&String, &usize)> = file.counts.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        let counts = counts
+            .iter()
+            .map(|(kind, n)| format!("{kind} {n}"))
+            .collect::<Vec<_>>()
+            .join(" · ");
+        if color {
+            writeln!(
+                w,
+                "{} {:>5}  {}",
+                format!("{:<width$}", file.path).bold(),
+                file.total,
+                counts.dimmed()
             )?;
         } else {
-            writeln!(w, "  {:<12} {:>5}│ {}", r.ref_type, r.line, r.content)?;
+            writeln!(w, "{:<width$} {:>5}  {counts}", file.path, file.total)?;
         }
     }
+    write_refs_footer(w, out, "files", color)
+}
 
+fn fmt_refs_groups(w: &mut impl Write, out: &RefsOutput, color: bool) -> io::Result<()> {
+    for (i, group) in out.groups.iter().enumerate() {
+        if i > 0 {
+            writeln!(w)?;
+        }
+        let header = format!("== {} ({})", group.symbol, group.refs.total);
+        if color {
+            writeln!(w, "{}", header.cyan().bold())?;
+        } else {
+            writeln!(w, "{header}")?;
+        }
+        if group.refs.total == 0 {
+            writeln!(w, "No references found")?;
+            continue;
+        }
+        fmt_refs(w, &group.refs, color)?;
+    }
     Ok(())
 }
 
-// ── outline ─────────────────────────────────────────────────────────────────
+/// The hint and, when cut at the limit, a note on what was left out.
+fn write_refs_footer(
+    w: &mut impl Write,
+    out: &RefsOutput,
+    unit: &str,
+    color: bool,
+) -> io::Result<()> {
+    let mut notes: Vec<String> = out.hint.iter().cloned().collect();
+    if out.has_more {
+        notes.push(format!("... more {unit} available (raise --limit)"));
+    }
+    for note in notes {
+        if color {
+            writeln!(w, "{}", note.dimmed())?;
+        } else {
+            writeln!(w, "{note}")?;
+        }
+    }
+    Ok(())
+}
 
-pub fn fmt_outline(w: &mut impl Write, out: &OutlineOutput, color: bool) -> io::Result<()> {
+/// Trailing notes for a reference: string-literal matches and the
+/// enclosing symbol.
+fn ref_suffix(r: &Reference) -> String {
+    let mut suffix = String::new();
+    if r.region == "string" {
+        suffix.push_str("  [string]");
+    }
+    if let Some(via @ ("receiver_type" | "unique_name")) = r.via.as_deref() {
+        suffix.push_str(&format!("  [via {via}]"));
+    }
+    if let Some(name) = &r.enclosing {
+        suffix.push_str(&format!("  (in {name})"));
+    }
+    suffix
+}
+
+// ── tests_for ───────────────────────────────────────────────────────────────
+
+pub fn fmt_tests_for(w: &mut impl Write, out: &TestsForOutput, color: bool) -> io::Result<()> {
+    if let Some(target) = &out.target {
+        if color {
+            writeln!(w, "{}", target.bold())?;
+        } else {
+            writeln!(w, "{target}")?;
+        }
+    }
+    for test in &out.tests {
+        let reasons = test.reasons.join(", ");
+        if color {
+            writeln!(
+                w,
+                "  {} {}  {}",
+                format_args!("{}:{}", test.path, test.line).dimmed(),
+                test.name.bold(),
+                reasons.dimmed()
+            )?;
+        } else {
+            writeln!(w, "  {}:{} {}  {reasons}", test.path, test.line, test.name)?;
+        }
+    }
+
+    if let Some(hint) = &out.hint {
+        if color {
+            writeln!(w, "{}", hint.dimmed())?;
+        } else {
+            writeln!(w, "{hint}")?;
+        }
+    }
+
+    if out.has_more {
+        if color {
+            writeln!(w, "{}", "... more tests available (raise --limit)".dimmed())?;
+        } else {
+            writeln!(w, "... more tests available (raise --limit)")?;
+        }
+    }
+
+    Ok(())
+}
+
+// ── symbols ─────────────────────────────────────────────────────────────────
+
+pub fn fmt_symbols(w: &mut impl Write, out: &SymbolsOutput, color: bool) -> io::Result<()> {
+    for line in &out.symbols {
+        // `path:line kind name`
+        let mut parts = line.splitn(3, ' ');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(location), Some(kind), Some(name)) if color => {
+                writeln!(
+                    w,
+                    "{} {:<6} {}",
+                    location.dimmed(),
+                    kind.blue(),
+                    name.bold()
+                )?;
+            }
+            (Some(location), Some(kind), Some(name)) => {
+                writeln!(w, "{location} {kind:<6} {name}")?;
+            }
+            _ => writeln!(w, "{line}")?,
+        }
+    }
+
+    if let Some(hint) = &out.hint {
+        if color {
+            writeln!(w, "{}", hint.dimmed())?;
+        } else {
+            writeln!(w, "{hint}")?;
+        }
+    }
+
+    if out.has_more {
+        if color {
+            writeln!(w, "{}", "... more symbols available".dimmed())?;
+        } else {
+            writeln!(w, "... more symbols available")?;
+        }
+    }
+
+    Ok(())
+}
+
+// ── definition ──────────────────────────────────────────────────────────────
+
+pub fn fmt_definition(w: &mut impl Write, out: &DefinitionOutput, color: bool) -> io::Result<()> {
+    for d in &out.definitions {
+        let end_info = d.end_line.map(|e| format!("-{e}")).unwrap_or_default();
+        if color {
+            writeln!(
+                w,
+                "{}{} {} {}",
+                format_args!("{}:{}", d.path, d.line).bold(),
+                end_info.dimmed(),
+                d.kind.blue(),
+                d.name.bold()
+            )?;
+            writeln!(w, "  {}", d.signature.dimmed())?;
+        } else {
+            writeln!(w, "{}:{}{} {} {}", d.path, d.line, end_info, d.kind, d.name)?;
+            writeln!(w, "  {}", d.signature)?;
+        }
+    }
+
+    if let Some(hint) = &out.hint {
+        if color {
+            writeln!(w, "{}", hint.dimmed())?;
+        } else {
+            writeln!(w, "{hint}")?;
+        }
+    }
+
+    Ok(())
+}
+
+// ── calls ───────────────────────────────────────────────────────────────────
+
+pub fn fmt_calls(w: &mut impl Write, out: &CallsOutput, color: bool) -> io::Result<()> {
+    if let Some(function) = &out.function {
+        if color {
+            writeln!(w, "{}", function.bold())?;
+        } else {
+            writeln!(w, "{function}")?;
+        }
+        for (label, edges) in [("callers", &out.callers), ("callees", &out.callees)] {
+            if color {
+                writeln!(w, "{} ({})", label.blue(), edges.len())?;
+            } else {
+                writeln!(w, "{label} ({})", edges.len())?;
+            }
+            for edge in edges {
+                write_call_edge(w, edge, color)?;
+            }
+        }
+    }
+
+    if let Some(hint) = &out.hint {
+        if color {
+            writeln!(w, "{}", hint.dimmed())?;
+        } else {
+            writeln!(w, "{hint}")?;
+        }
+    }
+
+    if out.has_more {
+        if color {
+            writeln!(w, "{}", "... more calls available (raise --limit)".dimmed())?;
+        } else {
+            writeln!(w, "... more calls available (raise --limit)")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes `path:line name  @call,lines`, indented by depth, with the
+/// function it was reached through for transitive hops.
+fn write_call_edge(w: &mut impl Write, edge: &CallEdge, color: bool) -> io::Result<()> {
+    let indent = "  ".repeat(edge.depth);
+    let lines = edge
+        .call_lines
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let via = edge
+        .via
+        .as_ref()
+        .map(|v| format!(" (via {v})"))
+        .unwrap_or_default();
     if color {
-        writeln!(w, "{} ({})", out.path.bold(), out.file_type.dimmed())?;
+        writeln!(
+            w,
+            "{indent}{} {}  {}{}",
+            format_args!("{}:{}", edge.path, edge.line).dimmed(),
+            edge.name.bold(),
+            format_args!("@{lines}").dimmed(),
+            via.dimmed()
+        )
     } else {
-        writeln!(w, "{} ({})", out.path, out.file_type)?;
+        writeln!(
+            w,
+            "{indent}{}:{} {}  @{lines}{via}",
+            edge.path, edge.line, edge.name
+        )
     }
+}
 
-    for sym in &out.symbols {
-        let indent = "  ".repeat(sym.level);
-        let end_info = sym.end_line.map(|e| format!("-{e}")).unwrap_or_default();
-
-        if color {
-            let kind_colored = match sym.kind.as_str() {
-                "fn" => format!("{:<6}", "fn").blue().to_string(),
-                "struct" => format!("{:<6}", "struct").green().to_string(),
-                "enum" => format!("{:<6}", "enum").yellow().to_string(),
-                "impl" => format!("{:<6}", "impl").cyan().to_string(),
-                "trait" => format!("{:<6}", "trait").magenta().to_string(),
-                "class" => format!("{:<6}", "class").green().to_string(),
-                "mod" => format!("{:<6}", "mod").cyan().to_string(),
-                "iface" => format!("{:<6}", "iface").magenta().to_string(),
-                other => format!("{other:<6}"),
-            };
+// ── deps ────────────────────────────────────────────────────────────────────
+
+pub fn fmt_deps(w: &mut impl Write, out: &DepsOutput, color: bool) -> io::Result<()> {
+    if color {
+        writeln!(w, "{}", out.path.bold())?;
+    } else {
+        writeln!(w, "{}", out.path)?;
+    }
+    for (label, edges) in [("imports", &out.imports), ("imported by", &out.imported_by)] {
+        if color {
+            writeln!(w, "{} ({})", label.blue(), edges.len())?;
+        } else {
+            writeln!(w, "{label} ({})", edges.len())?;
+        }
+        for edge in edges {
+            write_dep_edge(w, edge, color)?;
+        }
+    }
+    if !out.external.is_empty() {
+        let external = out.external.join(", ");
+        if color {
+            writeln!(w, "{} {}", "external".blue(), external.dimmed())?;
+        } else {
+            writeln!(w, "external {external}")?;
+        }
+    }
+
+    if let Some(hint) = &out.hint {
+        if color {
+            writeln!(w, "{}", hint.dimmed())?;
+        } else {
+            writeln!(w, "{hint}")?;
+        }
+    }
+
+    if out.has_more {
+        if color {
+            writeln!(w, "{}", "... more files available (raise --limit)".dimmed())?;
+        } else {
+            writeln!(w, "... more files available (raise --limit)")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes `path  @import,lines`, indented by depth, with the file it was
+/// reached through for transitive hops.
+fn write_dep_edge(w: &mut impl Write, edge: &DepEdge, color: bool) -> io::Result<()> {
+    let indent = "  ".repeat(edge.depth);
+    let lines = edge
+        .lines
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let via = edge
+        .via
+        .as_ref()
+        .map(|v| format!(" (via {v})"))
+        .unwrap_or_default();
+    if color {
+        writeln!(
+            w,
+            "{indent}{}  {}{}",
+            edge.path.bold(),
+            format_args!("@{lines}").dimmed(),
+            via.dimmed()
+        )
+    } else {
+        writeln!(w, "{indent}{}  @{lines}{via}", edge.path)
+    }
+}
+
+// ── implementations ─────────────────────────────────────────────────────────
+
+pub fn fmt_implementations(
+    w: &mut impl Write,
+    out: &ImplementationsOutput,
+    color: bool,
+) -> io::Result<()> {
+    if let Some(definition) = &out.definition {
+        if color {
+            writeln!(w, "{}", definition.bold())?;
+        } else {
+            writeln!(w, "{definition}")?;
+        }
+    }
+    for imp in &out.implementors {
+        if color {
             writeln!(
                 w,
-                "  {indent}{kind_colored} {:<20} :{}{}",
-                sym.name, sym.line, end_info
+                "  {} {} {}  {}",
+                format_args!("{}:{}", imp.path, imp.line).dimmed(),
+                imp.kind.blue(),
+                imp.name.bold(),
+                imp.signature.dimmed()
             )?;
         } else {
             writeln!(
                 w,
-                "  {indent}{:<6} {:<20} :{}{}",
-                sym.kind, sym.name, sym.line, end_info
+                "  {}:{} {} {}  {}",
+                imp.path, imp.line, imp.kind, imp.name, imp.signature
+            )?;
+        }
+    }
+
+    if let Some(hint) = &out.hint {
+        if color {
+            writeln!(w, "{}", hint.dimmed())?;
+        } else {
+            writeln!(w, "{hint}")?;
+        }
+    }
+
+    if out.has_more {
+        if color {
+            writeln!(
+                w,
+                "{}",
+                "... more implementors available (raise --limit)".dimmed()
             )?;
+        } else {
+            writeln!(w, "... more implementors available (raise --limit)")?;
+        }
+    }
+
+    Ok(())
+}
+
+// ── hierarchy ───────────────────────────────────────────────────────────────
+
+pub fn fmt_hierarchy(w: &mut impl Write, out: &HierarchyOutput, color: bool) -> io::Result<()> {
+    if let Some(class) = &out.class {
+        if color {
+            writeln!(w, "{}", class.bold())?;
+        } else {
+            writeln!(w, "{class}")?;
+        }
+        write_overrides(w, &out.overrides, "  ", color)?;
+        for (label, edges) in [
+            ("ancestors", &out.ancestors),
+            ("descendants", &out.descendants),
+        ] {
+            if color {
+                writeln!(w, "{} ({})", label.blue(), edges.len())?;
+            } else {
+                writeln!(w, "{label} ({})", edges.len())?;
+            }
+            for edge in edges {
+                write_type_edge(w, edge, color)?;
+            }
+        }
+        if !out.external.is_empty() {
+            let external = out.external.join(", ");
+            if color {
+                writeln!(w, "{} {}", "external".blue(), external.dimmed())?;
+            } else {
+                writeln!(w, "external {external}")?;
+            }
+        }
+    }
+
+    if let Some(hint) = &out.hint {
+        if color {
+            writeln!(w, "{}", hint.dimmed())?;
+        } else {
+            writeln!(w, "{hint}")?;
+        }
+    }
+
+    if out.has_more {
+        if color {
+            writeln!(w, "{}", "... more types available (raise --limit)".dimmed())?;
+        } else {
+            writeln!(w, "... more types available (raise --limit)")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes `path:line kind name`, indented by depth, with the type it was
+/// reached through and its overriding methods below it.
+fn write_type_edge(w: &mut impl Write, edge: &TypeEdge, color: bool) -> io::Result<()> {
+    let indent = "  ".repeat(edge.depth);
+    let via = edge
+        .via
+        .as_ref()
+        .map(|v| format!(" (via {v})"))
+        .unwrap_or_default();
+    if color {
+        writeln!(
+            w,
+            "{indent}{} {} {}{}",
+            format_args!("{}:{}", edge.path, edge.line).dimmed(),
+            edge.kind.blue(),
+            edge.name.bold(),
+            via.dimmed()
+        )?;
+    } else {
+        writeln!(
+            w,
+            "{indent}{}:{} {} {}{via}",
+            edge.path, edge.line, edge.kind, edge.name
+        )?;
+    }
+    write_overrides(w, &edge.overrides, &format!("{indent}  "), color)
+}
+
+/// Writes one `method :line → path:line Type.method` line per override.
+fn write_overrides(
+    w: &mut impl Write,
+    overrides: &[Override],
+    indent: &str,
+    color: bool,
+) -> io::Result<()> {
+    for o in overrides {
+        if color {
+            writeln!(
+                w,
+                "{indent}{} {} {}",
+                o.method,
+                format_args!(":{}", o.line).dimmed(),
+                format_args!("→ {}", o.overrides).dimmed()
+            )?;
+        } else {
+            writeln!(w, "{indent}{} :{} → {}", o.method, o.line, o.overrides)?;
+        }
+    }
+    Ok(())
+}
+
+// ── outline ─────────────────────────────────────────────────────────────────
+
+pub fn fmt_outline(w: &mut impl Write, out: &OutlineOutput, color: bool) -> io::Result<()> {
+    if color {
+        writeln!(w, "{} ({})", out.path.bold(), out.file_type.dimmed())?;
+    } else {
+        writeln!(w, "{} ({})", out.path, out.file_type)?;
+    }
+
+    if let Some(text) = &out.text {
+        write!(w, "{text}")?;
+    }
+    for sym in &out.symbols {
+        let indent = "  ".repeat(sym.level);
+        write_outline_symbol(w, sym, &indent, &format!("{indent}       "), color)?;
+    }
+    for node in &out.tree {
+        write_outline_node(w, node, "", None, color)?;
+    }
+
+    for file in &out.files {
+        writeln!(w)?;
+        fmt_outline(w, file, color)?;
+    }
+    if let Some(hint) = &out.hint {
+        if color {
+            writeln!(w, "\n{}", hint.dimmed())?;
+        } else {
+            writeln!(w, "\n{hint}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes `node` and its children with tree connectors; `last` is `None`
+/// for top-level nodes.
+fn write_outline_node(
+    w: &mut impl Write,
+    node: &OutlineNode,
+    prefix: &str,
+    last: Option<bool>,
+    color: bool,
+) -> io::Result<()> {
+    let (connector, continuation) = match last {
+        None => ("", ""),
+        Some(true) => ("└─ ", "   "),
+        Some(false) => ("├─ ", "│  "),
+    };
+    let child_prefix = format!("{prefix}{continuation}");
+    let doc_rail = if node.children.is_empty() {
+        "   "
+    } else {
+        "│  "
+    };
+    write_outline_symbol(
+        w,
+        &node.symbol,
+        &format!("{prefix}{connector}"),
+        &format!("{child_prefix}{doc_rail}"),
+        color,
+    )?;
+    for (i, child) in node.children.iter().enumerate() {
+        let is_last = i + 1 == node.children.len();
+        write_outline_node(w, child, &child_prefix, Some(is_last), color)?;
+    }
+    Ok(())
+}
+
+/// Writes one outline line (`kind name :start-end`) after `lead`, and the
+/// doc line, if any, after `doc_lead`.
+fn write_outline_symbol(
+    w: &mut impl Write,
+    sym: &Symbol,
+    lead: &str,
+    doc_lead: &str,
+    color: bool,
+) -> io::Result<()> {
+    let end_info = sym.end_line.map(|e| format!("-{e}")).unwrap_or_default();
+    // Verbose outlines show the declaration itself in place of the name
+    let label = sym.signature.as_deref().unwrap_or(&sym.name);
+
+    if color {
+        let kind_colored = match sym.kind.as_str() {
+            "fn" => format!("{:<6}", "fn").blue().to_string(),
+            "struct" => format!("{:<6}", "struct").green().to_string(),
+            "enum" => format!("{:<6}", "enum").yellow().to_string(),
+            "impl" => format!("{:<6}", "impl").cyan().to_string(),
+            "trait" => format!("{:<6}", "trait").magenta().to_string(),
+            "class" => format!("{:<6}", "class").green().to_string(),
+            "mod" => format!("{:<6}", "mod").cyan().to_string(),
+            "iface" => format!("{:<6}", "iface").magenta().to_string(),
+            other => format!("{other:<6}"),
+        };
+        writeln!(
+            w,
+            "  {lead}{kind_colored} {label:<20} :{}{}",
+            sym.line, end_info
+        )?;
+    } else {
+        writeln!(
+            w,
+            "  {lead}{:<6} {label:<20} :{}{}",
+            sym.kind, sym.line, end_info
+        )?;
+    }
+
+    if let Some(doc) = &sym.doc {
+        let doc_line = format!("  {doc_lead}{doc}");
+        if color {
+            writeln!(w, "{}", doc_line.dimmed())?;
+        } else {
+            writeln!(w, "{doc_line}")?;
+        }
+    }
+    Ok(())
+}
+
+// ── symbol_body ─────────────────────────────────────────────────────────────
+
+pub fn fmt_symbol_body(w: &mut impl Write, out: &SymbolBodyOutput, color: bool) -> io::Result<()> {
+    let header = format!(
+        "{}:{}-{} {} {}",
+        out.path, out.start_line, out.end_line, out.kind, out.name
+    );
+    if color {
+        writeln!(w, "{}", header.bold())?;
+    } else {
+        writeln!(w, "{header}")?;
+    }
+
+    // Strip content boundary markers, print raw content
+    for line in out.content.lines() {
+        if line.starts_with("--- BEGIN FILE CONTENT:") || line.starts_with("--- END FILE CONTENT:")
+        {
+            continue;
+        }
+        writeln!(w, "{line}")?;
+    }
+
+    if !out.other_matches.is_empty() {
+        let others = format!("also: {}", out.other_matches.join(", "));
+        if color {
+            writeln!(w, "{}", others.dimmed())?;
+        } else {
+            writeln!(w, "{others}")?;
         }
     }
 
@@ -62,9 +62,21 @@ enum Commands {
         #[arg(short, long, default_value = "20")]
         limit: usize,
 
-        /// Search mode: combined, fts, or grep
+        /// Search mode: combined, fts, grep, phrase, or fuzzy
         #[arg(short, long, default_value = "combined")]
         mode: String,
+
+        /// Count matches per group instead of listing files: directory, extension, or package
+        #[arg(short, long)]
+        aggregate: Option<String>,
+
+        /// Diversify results: keep at most N files per directory
+        #[arg(long, value_name = "N")]
+        max_per_dir: Option<usize>,
+
+        /// Keep matches in: all, code_only, comments_only, or strings_only
+        #[arg(short, long, default_value = "all")]
+        filter: String,
     },
 
     /// Index the codebase
@@ -99,6 +111,35 @@ enum Commands {
     Outline {
         /// File path
         path: String,
+
+        /// Detail per symbol: names, signatures, or full (adds doc lines)
+        #[arg(short, long, default_value = "names")]
+        verbosity: String,
+
+        /// JSON shape (with --json): flat, tree, or text
+        #[arg(short, long, default_value = "flat")]
+        format: String,
+
+        /// Maximum files for a directory or glob
+        #[arg(short, long, default_value = "30")]
+        limit: usize,
+
+        /// Include nested symbols for a directory or glob
+        #[arg(long)]
+        nested: bool,
+    },
+
+    /// Print one symbol's source (doc comments through end of body)
+    SymbolBody {
+        /// File path
+        path: String,
+
+        /// Symbol name, optionally qualified (Type::method)
+        symbol: String,
+
+        /// Omit doc comments and attributes
+        #[arg(long)]
+        no_docs: bool,
     },
 
     /// Show directory tree
@@ -127,12 +168,124 @@ enum Commands {
 
     /// Find all references to a symbol
     Refs {
-        /// Symbol/identifier to find
-        symbol: String,
+        /// Symbol/identifier to find; qualify as Type::method or module.func,
+        /// or use .method( for member calls only. Several are grouped per symbol
+        #[arg(required = true)]
+        symbols: Vec<String>,
 
         /// Maximum references
         #[arg(short, long, default_value = "50")]
         limit: usize,
+
+        /// Keep references in: all, code_only, comments_only, or strings_only
+        #[arg(short, long, default_value = "all")]
+        filter: String,
+
+        /// Per-file counts by reference type and definition sites
+        #[arg(short, long)]
+        summary: bool,
+
+        /// Only list references in this file
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+
+    /// Find symbol declarations by name
+    Symbols {
+        /// Symbol name or fragment
+        query: String,
+
+        /// Name matching: prefix, exact, contains, or fuzzy
+        #[arg(short, long, default_value = "prefix")]
+        mode: String,
+
+        /// Only this kind (fn, struct, enum, trait, class, impl, mod, iface, const, static, type)
+        #[arg(short, long)]
+        kind: Option<String>,
+
+        /// Only files under this path prefix
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Maximum symbols
+        #[arg(short, long, default_value = "30")]
+        limit: usize,
+    },
+
+    /// Find where a symbol is defined
+    Definition {
+        /// Symbol name, optionally qualified (Type::method)
+        symbol: String,
+
+        /// Only this kind
+        #[arg(short, long)]
+        kind: Option<String>,
+
+        /// Maximum definitions
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// Show a function's callers and callees
+    Calls {
+        /// Function name, optionally qualified (Type::method)
+        symbol: String,
+
+        /// Levels of transitive expansion
+        #[arg(short, long, default_value = "1")]
+        depth: usize,
+
+        /// Maximum callers and callees (each)
+        #[arg(short, long, default_value = "30")]
+        limit: usize,
+    },
+
+    /// Show a file's imports and importers
+    Deps {
+        /// File path
+        path: String,
+
+        /// Levels of transitive expansion
+        #[arg(short, long, default_value = "1")]
+        depth: usize,
+
+        /// Maximum files per direction
+        #[arg(short, long, default_value = "50")]
+        limit: usize,
+    },
+
+    /// List types implementing a trait or interface, or extending a class
+    Implementations {
+        /// Trait, interface or base class name
+        name: String,
+
+        /// Maximum implementors
+        #[arg(short, long, default_value = "50")]
+        limit: usize,
+    },
+
+    /// Show a class's supertypes, subtypes and overridden methods
+    Hierarchy {
+        /// Class or interface name
+        name: String,
+
+        /// Levels to walk up and down
+        #[arg(short, long, default_value = "3")]
+        depth: usize,
+
+        /// Maximum types per direction
+        #[arg(short, long, default_value = "50")]
+        limit: usize,
+    },
+
+    /// Find tests likely to cover a symbol or file
+    TestsFor {
+        /// Symbol name or file path
+        target: String,
+
+        /// Maximum test functions
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
     },
 
     /// Show differences between two files
@@ -297,13 +450,33 @@ async fn run_cli(
     }
 
     match cmd {
-        Commands::Search { query, limit, mode } => {
+        Commands::Search {
+            query,
+            limit,
+            mode,
+            aggregate,
+            max_per_dir,
+            filter,
+        } => {
             let mode: grepika::tools::SearchMode =
                 mode.parse().map_err(|e: String| anyhow::anyhow!(e))?;
-            let input = grepika::tools::SearchInput { query, limit, mode };
+            let filter: grepika::tools::RegionFilter =
+                filter.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            let aggregate = aggregate
+                .map(|a| a.parse::<grepika::tools::AggregateBy>())
+                .transpose()
+                .map_err(|e: String| anyhow::anyhow!(e))?;
+            let input = grepika::tools::SearchInput {
+                query,
+                limit,
+                mode,
+                aggregate,
+                max_per_directory: max_per_dir,
+                filter,
+            };
             let result =
                 grepika::tools::execute_search(&search, input).map_err(|e| anyhow::anyhow!(e))?;
-            let empty = result.results.is_empty();
+            let empty = result.results.is_empty() && result.aggregates.is_empty();
             if json {
                 output_json!(result);
             } else {
@@ -362,8 +535,28 @@ async fn run_cli(
             }
         }
 
-        Commands::Outline { path } => {
-            let input = grepika::tools::OutlineInput { path };
+        Commands::Outline {
+            path,
+            verbosity,
+            format,
+            limit,
+            nested,
+        } => {
+            let verbosity: grepika::tools::OutlineVerbosity =
+                verbosity.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            let mut format: grepika::tools::OutlineFormat =
+                format.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            if !json {
+                // The terminal rendering draws the containment tree
+                format = grepika::tools::OutlineFormat::Tree;
+            }
+            let input = grepika::tools::OutlineInput {
+                path,
+                verbosity,
+                format,
+                limit,
+                nested,
+            };
             let result =
                 grepika::tools::execute_outline(&search, input).map_err(|e| anyhow::anyhow!(e))?;
             if json {
@@ -373,6 +566,25 @@ async fn run_cli(
             }
         }
 
+        Commands::SymbolBody {
+            path,
+            symbol,
+            no_docs,
+        } => {
+            let input = grepika::tools::SymbolBodyInput {
+                path,
+                symbol,
+                include_docs: !no_docs,
+            };
+            let result = grepika::tools::execute_symbol_body(&search, input)
+                .map_err(|e| anyhow::anyhow!(e))?;
+            if json {
+                output_json!(result);
+            } else {
+                fmt::fmt_symbol_body(&mut out, &result, use_color)?;
+            }
+        }
+
         Commands::Toc { path, depth } => {
             let input = grepika::tools::TocInput { path, depth };
             let result =
@@ -403,11 +615,28 @@ async fn run_cli(
             }
         }
 
-        Commands::Refs { symbol, limit } => {
-            let input = grepika::tools::RefsInput { symbol, limit };
+        Commands::Refs {
+            mut symbols,
+            limit,
+            filter,
+            summary,
+            path,
+        } => {
+            let filter: grepika::tools::RegionFilter =
+                filter.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            // One symbol keeps the flat output; more are grouped
+            let symbol = symbols.remove(0);
+            let input = grepika::tools::RefsInput {
+                symbol,
+                symbols,
+                limit,
+                filter,
+                summary,
+                path,
+            };
             let result =
                 grepika::tools::execute_refs(&search, input).map_err(|e| anyhow::anyhow!(e))?;
-            let empty = result.references.is_empty();
+            let empty = result.total == 0;
             if json {
                 output_json!(result);
             } else {
@@ -418,6 +647,137 @@ async fn run_cli(
             }
         }
 
+        Commands::Symbols {
+            query,
+            mode,
+            kind,
+            path,
+            limit,
+        } => {
+            let mode: grepika::tools::SymbolMatch =
+                mode.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            let input = grepika::tools::SymbolsInput {
+                query,
+                mode,
+                kind,
+                path,
+                limit,
+            };
+            let result =
+                grepika::tools::execute_symbols(&search, input).map_err(|e| anyhow::anyhow!(e))?;
+            let empty = result.symbols.is_empty();
+            if json {
+                output_json!(result);
+            } else {
+                fmt::fmt_symbols(&mut out, &result, use_color)?;
+            }
+            if empty {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Definition {
+            symbol,
+            kind,
+            limit,
+        } => {
+            let input = grepika::tools::DefinitionInput {
+                symbol,
+                kind,
+                limit,
+            };
+            let result = grepika::tools::execute_definition(&search, input)
+                .map_err(|e| anyhow::anyhow!(e))?;
+            let empty = result.definitions.is_empty();
+            if json {
+                output_json!(result);
+            } else {
+                fmt::fmt_definition(&mut out, &result, use_color)?;
+            }
+            if empty {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Calls {
+            symbol,
+            depth,
+            limit,
+        } => {
+            let input = grepika::tools::CallsInput {
+                symbol,
+                depth,
+                limit,
+            };
+            let result =
+                grepika::tools::execute_calls(&search, input).map_err(|e| anyhow::anyhow!(e))?;
+            let empty = result.function.is_none();
+            if json {
+                output_json!(result);
+            } else {
+                fmt::fmt_calls(&mut out, &result, use_color)?;
+            }
+            if empty {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Deps { path, depth, limit } => {
+            let input = grepika::tools::DepsInput { path, depth, limit };
+            let result =
+                grepika::tools::execute_deps(&search, input).map_err(|e| anyhow::anyhow!(e))?;
+            if json {
+                output_json!(result);
+            } else {
+                fmt::fmt_deps(&mut out, &result, use_color)?;
+            }
+        }
+
+        Commands::Implementations { name, limit } => {
+            let input = grepika::tools::ImplementationsInput { name, limit };
+            let result = grepika::tools::execute_implementations(&search, input)
+                .map_err(|e| anyhow::anyhow!(e))?;
+            let empty = result.implementors.is_empty();
+            if json {
+                output_json!(result);
+            } else {
+                fmt::fmt_implementations(&mut out, &result, use_color)?;
+            }
+            if empty {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Hierarchy { name, depth, limit } => {
+            let input = grepika::tools::HierarchyInput { name, depth, limit };
+            let result = grepika::tools::execute_hierarchy(&search, input)
+                .map_err(|e| anyhow::anyhow!(e))?;
+            let empty = result.class.is_none();
+            if json {
+                output_json!(result);
+            } else {
+                fmt::fmt_hierarchy(&mut out, &result, use_color)?;
+            }
+            if empty {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::TestsFor { target, limit } => {
+            let input = grepika::tools::TestsForInput { target, limit };
+            let result = grepika::tools::execute_tests_for(&search, input)
+                .map_err(|e| anyhow::anyhow!(e))?;
+            let empty = result.tests.is_empty();
+            if json {
+                output_json!(result);
+            } else {
+                fmt::fmt_tests_for(&mut out, &result, use_color)?;
+            }
+            if empty {
+                std::process::exit(1);
+            }
+        }
+
         Commands::Diff {
             file1,
             file2,
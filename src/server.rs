@@ -218,12 +218,23 @@ pub struct AddWorkspaceParams {
 
 #[derive(Deserialize, JsonSchema)]
 pub struct SearchParams {
-    /// Search query — regex patterns (e.g., "fn\\s+main") or natural language (e.g., "error handling")
+    /// Search query — regex patterns (e.g., "fn\\s+main"), natural language (e.g., "error handling"),
+    /// quoted phrases (e.g., "\"error handling\" retry") or proximity (e.g., "NEAR(cache evict, 5)")
     pub query: String,
     /// Maximum results to return (default: 20, max: 200). Start with 10-20 for exploration.
     pub limit: Option<usize>,
-    /// Search mode: combined (default, best quality), fts (natural language), grep (exact regex)
+    /// Search mode: combined (default, best quality), fts (natural language), grep (exact regex),
+    /// phrase (exact word sequence), fuzzy (typo-tolerant identifier lookup)
     pub mode: Option<tools::SearchMode>,
+    /// Instead of a file list, return match counts grouped by "directory", "extension" or
+    /// "package" (top-level directory). Cheap way to answer "where is this used the most".
+    pub aggregate: Option<tools::AggregateBy>,
+    /// Spread results across the codebase: keep at most this many files per directory
+    /// (e.g., 3). Directories that hit the cap are listed in `collapsed`.
+    pub max_per_directory: Option<usize>,
+    /// Keep only matches in code_only, comments_only or strings_only regions (default: all).
+    /// Needs line matches, so not available with mode=fts or fuzzy.
+    pub filter: Option<tools::RegionFilter>,
 }
 
 #[derive(Deserialize, JsonSchema)]
@@ -240,6 +251,26 @@ pub struct GetParams {
 pub struct OutlineParams {
     /// File path relative to workspace root (e.g., "src/server.rs")
     pub path: String,
+    /// Detail per symbol: "names" (default), "signatures" (adds signature, visibility, parent),
+    /// or "full" (also the first doc-comment line)
+    pub verbosity: Option<tools::OutlineVerbosity>,
+    /// Result shape: "flat" (default, symbols with a nesting level), "tree" (nested children),
+    /// or "text" (compact indented lines)
+    pub format: Option<tools::OutlineFormat>,
+    /// Maximum files when path is a directory or glob (default: 30)
+    pub limit: Option<usize>,
+    /// Include methods and inner types for a directory or glob (default: false, top level only)
+    pub nested: Option<bool>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SymbolBodyParams {
+    /// File path relative to workspace root (e.g., "src/services/search.rs")
+    pub path: String,
+    /// Symbol name, optionally qualified (e.g., "merge_results", "SearchService::merge_results")
+    pub symbol: String,
+    /// Include doc comments and attributes above the item (default: true)
+    pub include_docs: Option<bool>,
 }
 
 #[derive(Deserialize, JsonSchema)]
@@ -268,10 +299,93 @@ pub struct StatsParams {
 
 #[derive(Deserialize, JsonSchema)]
 pub struct RefsParams {
-    /// Symbol name to find (exact identifier, e.g., "SearchService" not "search service")
-    pub symbol: String,
+    /// Symbol name to find (exact identifier, e.g., "SearchService" not "search service").
+    /// Qualify it to keep one type's or module's uses: "Database::open", "module.func",
+    /// "obj.method". A leading "." keeps member accesses, a trailing "(" keeps calls.
+    pub symbol: Option<String>,
+    /// Several symbols to look up in one pass (e.g., a struct's public methods), instead of
+    /// or besides symbol. Results come back grouped per symbol, limit applying to each.
+    pub symbols: Option<Vec<String>>,
     /// Maximum references to return (default: 50, max: 500)
     pub limit: Option<usize>,
+    /// Keep only references in code_only, comments_only or strings_only regions
+    /// (default: all). Use code_only when assessing a rename.
+    pub filter: Option<tools::RegionFilter>,
+    /// Return per-file counts by reference type plus the definition sites instead of every
+    /// reference (default: false). limit then caps the number of files.
+    pub summary: Option<bool>,
+    /// Only list references in this file, relative to root (e.g., after a summary)
+    pub path: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SymbolsParams {
+    /// Symbol name or fragment (e.g., "Config", "merge_")
+    pub query: String,
+    /// Name matching: prefix (default), exact, contains, or fuzzy (tolerates typos)
+    pub mode: Option<tools::SymbolMatch>,
+    /// Only this kind: fn, struct, enum, trait, class, impl, mod, iface, const, static, type, macro
+    pub kind: Option<String>,
+    /// Only files under this path prefix relative to workspace root (e.g., "src/services")
+    pub path: Option<String>,
+    /// Maximum symbols to return (default: 30, max: 500)
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct DefinitionParams {
+    /// Symbol name, optionally qualified (e.g., "merge_results", "SearchService::merge_results")
+    pub symbol: String,
+    /// Only definitions of this kind (e.g., "fn", "struct", "const")
+    pub kind: Option<String>,
+    /// Maximum definitions to return (default: 10, max: 100)
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CallsParams {
+    /// Function name, optionally qualified (e.g., "authenticate", "Config::load")
+    pub symbol: String,
+    /// Levels of transitive expansion (default: 1 = direct only, max: 5)
+    pub depth: Option<usize>,
+    /// Maximum callers and callees to return, each (default: 30, max: 200)
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct DepsParams {
+    /// File path relative to workspace root (e.g., "src/db/mod.rs")
+    pub path: String,
+    /// Levels of transitive expansion (default: 1 = direct only, max: 5)
+    pub depth: Option<usize>,
+    /// Maximum files to return per direction (default: 50, max: 500)
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ImplementationsParams {
+    /// Trait, interface or base class name (e.g., "Display", "Repository")
+    pub name: String,
+    /// Maximum implementors to return (default: 50, max: 500)
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct HierarchyParams {
+    /// Class or interface name (e.g., "BaseModel", "Repository")
+    pub name: String,
+    /// Levels to walk up and down (default: 3, max: 10)
+    pub depth: Option<usize>,
+    /// Maximum types to return per direction (default: 50, max: 500)
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct TestsForParams {
+    /// Symbol name or file path relative to workspace root (e.g., "authenticate", "src/auth.rs")
+    pub target: String,
+    /// Maximum test functions to return (default: 20, max: 200)
+    pub limit: Option<usize>,
 }
 
 #[derive(Deserialize, JsonSchema)]
@@ -459,7 +573,12 @@ impl GrepikaServer {
     #[tool(
         description = "Search for code patterns across the indexed codebase. Returns ranked results \
         with file paths, relevance scores (0-1), and matching line snippets.\n\n\
-        Modes: combined (default, best quality), grep (exact regex), fts (natural language).\n\
+        Modes: combined (default, best quality), grep (exact regex), fts (natural language), \
+        phrase (exact word sequence), fuzzy (misspelled identifier → \"did you mean\" candidates). \
+        Quoted \"phrases\" and NEAR(a b, n) work in combined and fts. Empty results suggest near-miss names.\n\
+        Set aggregate=directory|extension|package to get match counts per group instead of files, \
+        or max_per_directory=N to spread results across directories. \
+        filter=code_only|comments_only|strings_only keeps matches in those regions only.\n\
         Requires 'index' to be built first.\n\n\
         For tracking a specific symbol's usages, prefer 'refs' instead. \
         To read matched files, follow up with 'get' or 'context'.",
@@ -480,6 +599,9 @@ impl GrepikaServer {
             query: params.query,
             limit: params.limit.unwrap_or(20).min(200),
             mode: params.mode.unwrap_or_default(),
+            aggregate: params.aggregate,
+            max_per_directory: params.max_per_directory,
+            filter: params.filter.unwrap_or_default(),
         };
         let search = Arc::clone(&ws.search);
         spawn_tool(move || tools::execute_search(&search, input)).await
@@ -515,9 +637,18 @@ impl GrepikaServer {
 
     #[tool(
         description = "Extract file structure (functions, classes, structs, traits, enums, impls) \
-        without reading full content. Returns symbols with name, kind, line, and end_line.\n\n\
+        without reading full content. Returns symbols with name, kind, line, end_line, and \
+        nesting level (methods sit under their impl/class).\n\n\
         Use before 'get' to understand a file's shape and find specific sections to read. \
-        Supports: Rust, Python, JavaScript/TypeScript, Go. Does not require indexing.",
+        verbosity='signatures' or 'full' adds signatures, visibility, parents and doc lines, \
+        often enough to understand an API without 'get'. format='tree' nests methods under \
+        their impl/class; format='text' is the most compact.\n\n\
+        Pass a directory (\"src/services\") or glob (\"src/**/*.rs\") to outline many files \
+        in one call: top-level symbols per file, test modules omitted, size-capped. \
+        Supports: Rust, Python, JavaScript/TypeScript, Go, Java, C/C++, C#, Ruby, PHP, Swift \
+        (parsed with tree-sitter) and Kotlin. For Markdown, TOML, YAML and JSON it lists \
+        headings (kind h1-h6), tables, and keys. \
+        Does not require indexing.",
         annotations(
             title = "File Outline",
             read_only_hint = true,
@@ -531,11 +662,45 @@ impl GrepikaServer {
         Parameters(params): Parameters<OutlineParams>,
     ) -> Result<CallToolResult, rmcp::ErrorData> {
         let ws = require_workspace!(self);
-        let input = tools::OutlineInput { path: params.path };
+        let input = tools::OutlineInput {
+            path: params.path,
+            verbosity: params.verbosity.unwrap_or_default(),
+            format: params.format.unwrap_or_default(),
+            limit: params.limit.unwrap_or(30),
+            nested: params.nested.unwrap_or(false),
+        };
         let search = Arc::clone(&ws.search);
         spawn_tool(move || tools::execute_outline(&search, input)).await
     }
 
+    #[tool(
+        description = "Get one symbol's full source by name: a function, method, struct, class, etc. \
+        from its doc comments and attributes through the end of its body.\n\n\
+        Use instead of 'outline' + 'get' with copied line numbers. Qualify methods \
+        (Type::method) when a name appears in several impls or classes; other same-named \
+        matches are listed. Does not require indexing.",
+        annotations(
+            title = "Symbol Body",
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn symbol_body(
+        &self,
+        Parameters(params): Parameters<SymbolBodyParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let ws = require_workspace!(self);
+        let input = tools::SymbolBodyInput {
+            path: params.path,
+            symbol: params.symbol,
+            include_docs: params.include_docs.unwrap_or(true),
+        };
+        let search = Arc::clone(&ws.search);
+        spawn_tool(move || tools::execute_symbol_body(&search, input)).await
+    }
+
     #[tool(
         description = "Get directory tree structure (like 'tree' command). Respects .gitignore. \
         Returns indented tree text, total_files, and total_dirs counts.\n\n\
@@ -617,8 +782,16 @@ impl GrepikaServer {
 
     #[tool(
         description = "Find all references to a symbol/identifier using word-boundary matching. \
-        Returns references classified as definition, import, type_usage, or usage, \
-        with file path, line number, and trimmed context.\n\n\
+        Returns references classified as definition, import, type_usage, call, field_access, \
+        trait_impl, comment or usage, with file path, line number, trimmed context, the region \
+        (code, comment or string) and, for indexed files, the enclosing symbol. \
+        filter=code_only drops mentions in comments, docs and strings. Files are ranked with \
+        definitions first; for widely used symbols, start with summary=true (per-file counts \
+        and definition sites), then pass path to list one file's references. A qualified \
+        symbol (Database::open, module.func) keeps only uses tied to that type or module: \
+        written through it, via self inside it, or on receivers declared with its type; \
+        each match reports how it was tied (via). For impact analysis across related \
+        symbols, pass symbols=[...] to search them in one pass, grouped per symbol.\n\n\
         Use to trace where a function/class/type is defined, imported, and called. \
         Combine with 'outline' on caller files to understand call hierarchy. \
         Does not require indexing (uses grep backend).",
@@ -636,13 +809,221 @@ impl GrepikaServer {
     ) -> Result<CallToolResult, rmcp::ErrorData> {
         let ws = require_workspace!(self);
         let input = tools::RefsInput {
-            symbol: params.symbol,
+            symbol: params.symbol.unwrap_or_default(),
+            symbols: params.symbols.unwrap_or_default(),
             limit: params.limit.unwrap_or(50).min(500),
+            filter: params.filter.unwrap_or_default(),
+            summary: params.summary.unwrap_or(false),
+            path: params.path,
         };
         let search = Arc::clone(&ws.search);
         spawn_tool(move || tools::execute_refs(&search, input)).await
     }
 
+    #[tool(
+        description = "Find where symbols are declared, by name, across the whole workspace. \
+        Returns compact 'path:line kind name' lines (methods as Type::method), ranked by \
+        match quality, then top-level types before functions before members.\n\n\
+        Modes: prefix (default), exact, contains, fuzzy (tolerates typos). \
+        Filter with kind (fn, struct, enum, trait, class, impl, mod, iface) and path prefix. \
+        Use instead of 'search' when you know (part of) a name; follow up with 'get' or 'outline'. \
+        Requires 'index' to be built first.",
+        annotations(
+            title = "Workspace Symbols",
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn symbols(
+        &self,
+        Parameters(params): Parameters<SymbolsParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let ws = require_workspace!(self);
+        let input = tools::SymbolsInput {
+            query: params.query,
+            mode: params.mode.unwrap_or_default(),
+            kind: params.kind,
+            path: params.path,
+            limit: params.limit.unwrap_or(30).min(500),
+        };
+        let search = Arc::clone(&ws.search);
+        spawn_tool(move || tools::execute_symbols(&search, input)).await
+    }
+
+    #[tool(
+        description = "Go to definition: resolve a symbol name to where it is declared. \
+        Returns each definition's path, line, end_line (body range), kind, and signature line, \
+        so you can 'get' exactly those lines next.\n\n\
+        Accepts qualified names (Type::method, Class.method) to disambiguate. Covers \
+        pub(crate)/async/unsafe fns, consts, statics and type aliases. \
+        Use instead of 'refs' when you only need the declaration. Requires 'index' to be built first.",
+        annotations(
+            title = "Go to Definition",
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn definition(
+        &self,
+        Parameters(params): Parameters<DefinitionParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let ws = require_workspace!(self);
+        let input = tools::DefinitionInput {
+            symbol: params.symbol,
+            kind: params.kind,
+            limit: params.limit.unwrap_or(10).min(100),
+        };
+        let search = Arc::clone(&ws.search);
+        spawn_tool(move || tools::execute_definition(&search, input)).await
+    }
+
+    #[tool(
+        description = "Call hierarchy for a function: its callers (enclosing functions of each \
+        usage) and callees (workspace functions invoked in its body), with declaration lines \
+        and call-site lines.\n\n\
+        Set depth > 1 to expand transitively (each function is listed once, so cycles stop). \
+        Accepts qualified names (Type::method) to disambiguate; calls into libraries are omitted. \
+        Use instead of chaining 'refs' and 'outline' to trace a call chain. \
+        Requires 'index' to be built first.",
+        annotations(
+            title = "Call Hierarchy",
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn calls(
+        &self,
+        Parameters(params): Parameters<CallsParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let ws = require_workspace!(self);
+        let input = tools::CallsInput {
+            symbol: params.symbol,
+            depth: params.depth.unwrap_or(1),
+            limit: params.limit.unwrap_or(30).min(200),
+        };
+        let search = Arc::clone(&ws.search);
+        spawn_tool(move || tools::execute_calls(&search, input)).await
+    }
+
+    #[tool(
+        description = "File dependency graph: the workspace files a file imports and the files \
+        importing it, with the import lines, plus the external packages it uses.\n\n\
+        Built from Rust use/mod, Python import/from, JS/TS import/require and Go imports, \
+        resolved to workspace files at index time. Set depth > 1 for transitive dependencies. \
+        Use to gauge a module's blast radius before changing it. Requires 'index' to be built first.",
+        annotations(
+            title = "File Dependencies",
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn deps(
+        &self,
+        Parameters(params): Parameters<DepsParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let ws = require_workspace!(self);
+        let input = tools::DepsInput {
+            path: params.path,
+            depth: params.depth.unwrap_or(1),
+            limit: params.limit.unwrap_or(50).min(500),
+        };
+        let search = Arc::clone(&ws.search);
+        spawn_tool(move || tools::execute_deps(&search, input)).await
+    }
+
+    #[tool(
+        description = "Find the types implementing a trait or interface, or extending a class: \
+        Rust `impl Trait for Type`, `class Y implements X` / `extends X` (TS/JS/Java/PHP), \
+        Python `class Y(X)`, and `:` base lists (C#/Kotlin/Swift/C++). Lists each implementor \
+        with file, line and declaration header.\n\n\
+        Unlike refs, skips bounds, imports and `dyn Trait` uses. Requires 'index' to be built first.",
+        annotations(
+            title = "Find Implementations",
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn implementations(
+        &self,
+        Parameters(params): Parameters<ImplementationsParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let ws = require_workspace!(self);
+        let input = tools::ImplementationsInput {
+            name: params.name,
+            limit: params.limit.unwrap_or(50).min(500),
+        };
+        let search = Arc::clone(&ws.search);
+        spawn_tool(move || tools::execute_implementations(&search, input)).await
+    }
+
+    #[tool(
+        description = "Type hierarchy of a class or interface: the supertypes it extends or \
+        implements (ancestors) and the types extending it (descendants), each with file and \
+        line, plus which methods override an inherited one and where that one is defined.\n\n\
+        Built from extends/implements clauses and base-class lists (Python, TS/JS, Java, C#, \
+        Kotlin, Swift, C++) extracted at index time. Supertypes outside the workspace are listed \
+        as external. Requires 'index' to be built first.",
+        annotations(
+            title = "Type Hierarchy",
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn hierarchy(
+        &self,
+        Parameters(params): Parameters<HierarchyParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let ws = require_workspace!(self);
+        let input = tools::HierarchyInput {
+            name: params.name,
+            depth: params.depth.unwrap_or(3),
+            limit: params.limit.unwrap_or(50).min(500),
+        };
+        let search = Arc::clone(&ws.search);
+        spawn_tool(move || tools::execute_hierarchy(&search, input)).await
+    }
+
+    #[tool(
+        description = "Find the tests likely to cover a symbol or file, ranked. Combines test \
+        bodies referencing the target (or, for a file, its top-level declarations), in-file test \
+        modules like `#[cfg(test)] mod tests`, naming conventions (`tests/foo.rs`, `test_foo.py`, \
+        `foo_test.go`, `foo.test.ts`, `FooTest.java`) and test files importing the file.\n\n\
+        Returns test functions (or it/test descriptions for JS/TS) with file, line and the \
+        reasons they were picked. Use before changing code to know what to run. \
+        Requires 'index' to be built first.",
+        annotations(
+            title = "Find Tests",
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn tests_for(
+        &self,
+        Parameters(params): Parameters<TestsForParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let ws = require_workspace!(self);
+        let input = tools::TestsForInput {
+            target: params.target,
+            limit: params.limit.unwrap_or(20).min(200),
+        };
+        let search = Arc::clone(&ws.search);
+        spawn_tool(move || tools::execute_tests_for(&search, input)).await
+    }
+
     #[tool(
         description = "Build or update the search index. Incremental by default (skips unchanged files); \
         set force=true for full rebuild. Reports files processed and timing.\n\n\
@@ -785,17 +1166,30 @@ impl ServerHandler for GrepikaServer {
              {setup}\n\n\
              TOOL SELECTION:\n\
              - Finding code patterns/keywords → search (needs index)\n\
+             - Finding a declaration by (partial) name → symbols (needs index)\n\
+             - Jumping to a known symbol's definition → definition, then get (needs index)\n\
              - Tracking where a symbol is used → refs (no index needed)\n\
+             - Who calls a function / what it calls → calls (needs index)\n\
+             - What a file imports / who imports it → deps (needs index)\n\
+             - Types implementing a trait/interface → implementations (needs index)\n\
+             - Superclasses, subclasses and overrides → hierarchy (needs index)\n\
+             - Which tests cover a symbol or file → tests_for (needs index)\n\
              - Understanding file structure → outline (no index needed)\n\
              - Reading specific code → get or context (no index needed)\n\
+             - Reading one function/type by name → symbol_body (no index needed)\n\
              - Project layout → toc (no index needed)\n\n\
              COMMON PATTERNS:\n\
              - Explore: toc → search → outline → get\n\
              - Trace symbol: refs → context on callers → outline on key files\n\
+             - Trace call chain: calls with depth=2-3 → symbol_body on interesting hops\n\
              - Investigate: search for error → context on matches → refs on functions\n\
-             - Understand file: outline first, then get specific sections\n\n\
+             - Understand file: outline first, then symbol_body or get specific sections\n\
+             - Understand module: outline on the directory (one call, top-level symbols per file)\n\
+             - Before changing a file: deps for importers → calls on the functions you touch\n\
+             - After changing code: tests_for on the changed symbol → run those tests\n\n\
              TIPS:\n\
-             - Use mode=grep for regex, mode=fts for natural language\n\
+             - Use mode=grep for regex, mode=fts for natural language, mode=phrase for exact wording\n\
+             - Unsure of a name's spelling? mode=fuzzy lists the closest identifiers\n\
              - Run 'index' periodically to pick up changes\n\
              - Use 'stats' to check index health\n\
              - Prefer grepika tools over built-in grep/glob for code search\n\n\
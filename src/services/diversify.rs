@@ -0,0 +1,233 @@
+//! Result diversification across directories.
+//!
+//! Broad queries often rank a single directory (e.g. `tests/`) above
+//! everything else. This MMR-style re-selection (Maximal Marginal
+//! Relevance) trades a little score for spread: each pick maximizes
+//! `λ·score − (1−λ)·similarity` to what was already picked, where
+//! similarity is the share of directory components two paths have in
+//! common. A hard per-directory cap bounds the worst case, and the
+//! directories that hit it are reported so the caller knows what was
+//! collapsed.
+
+use super::search::SearchResult;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Relevance vs. novelty trade-off. 1.0 = pure score order.
+const MMR_LAMBDA: f64 = 0.7;
+
+/// A diversified selection of search results.
+#[derive(Debug)]
+pub struct Diversified {
+    /// Selected results, in pick order
+    pub picked: Vec<SearchResult>,
+    /// Per collapsed directory (relative to the root), how many results
+    /// the cap held back, most first
+    pub collapsed: Vec<(PathBuf, usize)>,
+    /// Whether another result could have been picked under the cap
+    pub has_more: bool,
+}
+
+/// Re-selects up to `limit` results, at most `max_per_directory` from
+/// any one directory.
+///
+/// `results` must be sorted by score descending. Collapsed directories
+/// are those at the cap after `limit` picks; results left over elsewhere
+/// only count towards `has_more`.
+pub fn diversify(
+    results: Vec<SearchResult>,
+    root: &Path,
+    limit: usize,
+    max_per_directory: usize,
+) -> Diversified {
+    let max_per_directory = max_per_directory.max(1);
+    let dirs: Vec<PathBuf> = results
+        .iter()
+        .map(|r| {
+            r.path
+                .strip_prefix(root)
+                .unwrap_or(&r.path)
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut remaining: Vec<usize> = (0..results.len()).collect();
+    let mut selected: Vec<usize> = Vec::with_capacity(limit.min(results.len()));
+    let mut per_dir: HashMap<&Path, usize> = HashMap::new();
+
+    while selected.len() < limit {
+        let mut best: Option<(usize, f64)> = None;
+        for (pos, &i) in remaining.iter().enumerate() {
+            if per_dir.get(dirs[i].as_path()).copied().unwrap_or(0) >= max_per_directory {
+                continue;
+            }
+            let redundancy = selected
+                .iter()
+                .map(|&j| directory_similarity(&dirs[i], &dirs[j]))
+                .fold(0.0, f64::max);
+            let mmr = MMR_LAMBDA * results[i].score.as_f64() - (1.0 - MMR_LAMBDA) * redundancy;
+            // Strict `>` keeps the earlier (higher-scored) candidate on ties
+            if best.is_none_or(|(_, b)| mmr > b) {
+                best = Some((pos, mmr));
+            }
+        }
+
+        let Some((pos, _)) = best else { break };
+        let i = remaining.remove(pos);
+        *per_dir.entry(dirs[i].as_path()).or_insert(0) += 1;
+        selected.push(i);
+    }
+
+    // Leftovers in capped directories were collapsed; the rest just didn't fit
+    let mut collapsed: HashMap<&Path, usize> = HashMap::new();
+    let mut has_more = false;
+    for &i in &remaining {
+        if per_dir.get(dirs[i].as_path()).copied().unwrap_or(0) >= max_per_directory {
+            *collapsed.entry(dirs[i].as_path()).or_insert(0) += 1;
+        } else {
+            has_more = true;
+        }
+    }
+    let mut collapsed: Vec<(PathBuf, usize)> = collapsed
+        .into_iter()
+        .map(|(dir, n)| (dir.to_path_buf(), n))
+        .collect();
+    collapsed.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut slots: Vec<Option<SearchResult>> = results.into_iter().map(Some).collect();
+    let picked = selected
+        .into_iter()
+        .filter_map(|i| slots[i].take())
+        .collect();
+
+    Diversified {
+        picked,
+        collapsed,
+        has_more,
+    }
+}
+
+/// Fraction of directory components shared from the root: 1.0 for the
+/// same directory, 0.0 for disjoint top-level trees.
+fn directory_similarity(a: &Path, b: &Path) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let depth = a.components().count().max(b.components().count());
+    if depth == 0 {
+        return 1.0;
+    }
+    let shared = a
+        .components()
+        .zip(b.components())
+        .take_while(|(x, y)| x == y)
+        .count();
+    shared as f64 / depth as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::SearchSources;
+    use crate::types::{FileId, Score};
+
+    fn hit(path: &str, score: f64) -> SearchResult {
+        SearchResult {
+            file_id: FileId::new(0),
+            path: PathBuf::from("/repo").join(path),
+            score: Score::new(score),
+            sources: SearchSources::default(),
+            snippets: Vec::new(),
+            match_count: 0,
+        }
+    }
+
+    fn paths(results: &[SearchResult]) -> Vec<String> {
+        results
+            .iter()
+            .map(|r| {
+                r.path
+                    .strip_prefix("/repo")
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_directory_similarity() {
+        let sim = |a: &str, b: &str| directory_similarity(Path::new(a), Path::new(b));
+        assert_eq!(sim("src/a", "src/a"), 1.0);
+        assert_eq!(sim("src/a", "src/b"), 0.5);
+        assert_eq!(sim("src", "tests"), 0.0);
+    }
+
+    #[test]
+    fn test_cap_limits_directory_and_reports_collapsed() {
+        let results = vec![
+            hit("tests/a.rs", 0.9),
+            hit("tests/b.rs", 0.88),
+            hit("tests/c.rs", 0.86),
+            hit("tests/d.rs", 0.84),
+            hit("src/lib.rs", 0.5),
+        ];
+
+        let out = diversify(results, Path::new("/repo"), 10, 2);
+
+        assert_eq!(out.picked.len(), 3);
+        assert_eq!(paths(&out.picked)[0], "tests/a.rs");
+        assert!(paths(&out.picked).contains(&"src/lib.rs".to_string()));
+        assert_eq!(out.collapsed, vec![(PathBuf::from("tests"), 2)]);
+        assert!(!out.has_more);
+    }
+
+    #[test]
+    fn test_mmr_interleaves_directories() {
+        let results = vec![
+            hit("tests/a.rs", 0.9),
+            hit("tests/b.rs", 0.85),
+            hit("src/lib.rs", 0.8),
+        ];
+
+        let out = diversify(results, Path::new("/repo"), 3, 10);
+
+        // Novelty lifts src/lib.rs above the second tests/ hit
+        assert_eq!(
+            paths(&out.picked),
+            vec!["tests/a.rs", "src/lib.rs", "tests/b.rs"]
+        );
+        assert!(out.collapsed.is_empty());
+    }
+
+    #[test]
+    fn test_limit_is_not_reported_as_collapse() {
+        let results = vec![hit("a/x.rs", 0.9), hit("b/y.rs", 0.8), hit("c/z.rs", 0.7)];
+
+        let out = diversify(results, Path::new("/repo"), 2, 1);
+
+        assert_eq!(out.picked.len(), 2);
+        assert!(out.collapsed.is_empty());
+        assert!(out.has_more);
+    }
+
+    #[test]
+    fn test_collapsed_counts_only_directories_full_at_limit() {
+        // A third pick would fill tests/ to the cap; with a limit of two
+        // the leftover tests/ file merely did not fit
+        let results = vec![
+            hit("tests/a.rs", 0.9),
+            hit("src/lib.rs", 0.8),
+            hit("tests/b.rs", 0.7),
+            hit("tests/c.rs", 0.6),
+        ];
+
+        let out = diversify(results, Path::new("/repo"), 2, 2);
+
+        assert_eq!(paths(&out.picked), vec!["tests/a.rs", "src/lib.rs"]);
+        assert!(out.collapsed.is_empty());
+        assert!(out.has_more);
+    }
+}
@@ -3,11 +3,19 @@
 //! Wraps the database's FTS5 capabilities with a cleaner interface
 //! and query preprocessing.
 
+use super::phrase::{self, ParsedQuery};
+use super::search::MatchSnippet;
 use crate::db::Database;
 use crate::error::DbResult;
 use crate::types::{FileId, Score};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Control characters used as `highlight()` markers; they never occur in
+/// indexed source text, so stripping them recovers the original line.
+const HIGHLIGHT_OPEN: char = '\u{2}';
+const HIGHLIGHT_CLOSE: char = '\u{3}';
+
 /// Fixed reference for BM25 normalization.
 ///
 /// BM25 scores are negative (more negative = better match).
@@ -64,7 +72,10 @@ impl FtsService {
     /// Returns `DbError` if the database query fails.
     pub fn search_phrase(&self, phrase: &str, limit: usize) -> DbResult<Vec<(FileId, Score)>> {
         // Wrap in quotes for exact phrase matching
-        let escaped = phrase.replace('"', "");
+        let escaped = strip_non_bareword(phrase);
+        if escaped.is_empty() {
+            return Ok(Vec::new());
+        }
         let fts_query = format!("\"{escaped}\"");
         self.db.fts_search(&fts_query, limit).map(|results| {
             results
@@ -74,6 +85,43 @@ impl FtsService {
         })
     }
 
+    /// Returns, per file, the number of lines with a token matching `query`
+    /// and the first `max_lines` of them, with every matched token as a
+    /// highlight span.
+    ///
+    /// Uses SQLite's `highlight()` on the content column (rather than
+    /// `snippet()`, which loses line numbers), so FTS-only results get the
+    /// same line-anchored snippets as grep results — including stemmed
+    /// matches grep can't see.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the database query fails.
+    pub fn snippets(
+        &self,
+        query: &str,
+        file_ids: &[FileId],
+        max_lines: usize,
+    ) -> DbResult<HashMap<FileId, (usize, Vec<MatchSnippet>)>> {
+        let fts_query = preprocess_query(query);
+        if fts_query.is_empty() || file_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let highlighted = self.db.fts_highlight(
+            &fts_query,
+            file_ids,
+            &HIGHLIGHT_OPEN.to_string(),
+            &HIGHLIGHT_CLOSE.to_string(),
+        )?;
+
+        Ok(highlighted
+            .into_iter()
+            .map(|(id, text)| (id, highlighted_lines(&text, max_lines)))
+            .filter(|(_, (count, _))| *count > 0)
+            .collect())
+    }
+
     /// Searches by filename only.
     ///
     /// # Errors
@@ -94,16 +142,20 @@ impl FtsService {
 /// Preprocesses a query for FTS5.
 ///
 /// Improved preprocessing (Q4):
-/// - Preserves `"..."` for phrase matching
+/// - Preserves `"..."` for phrase matching, including quoted segments
+///   inside larger queries
+/// - Preserves `NEAR(a b, n)` proximity groups
 /// - Preserves `column:` prefix for column-qualified searches
 /// - Only adds `*` suffix for words >= 4 chars (short tokens stay exact)
 /// - Strips other FTS5 special characters
 fn preprocess_query(query: &str) -> String {
     let trimmed = query.trim();
 
-    // Preserve phrase queries: "exact phrase"
-    if trimmed.starts_with('"') && trimmed.ends_with('"') && trimmed.len() > 2 {
-        return trimmed.to_string();
+    if phrase::has_syntax(trimmed) {
+        let parsed = phrase::parse(trimmed);
+        if parsed.has_structure() {
+            return render_parsed(&parsed);
+        }
     }
 
     // Check for column-qualified search (e.g., "filename:auth")
@@ -129,6 +181,97 @@ fn preprocess_query(query: &str) -> String {
     preprocess_words(trimmed)
 }
 
+/// Renders a parsed phrase/NEAR query as FTS5 syntax.
+///
+/// Phrase contents are reduced to bareword characters (the tokenizer would
+/// split on punctuation anyway), and NEAR terms get the same prefix rules
+/// as loose words.
+fn render_parsed(parsed: &ParsedQuery) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    for p in &parsed.phrases {
+        let cleaned = strip_non_bareword(p);
+        if !cleaned.is_empty() {
+            parts.push(format!("\"{cleaned}\""));
+        }
+    }
+
+    for group in &parsed.near {
+        let terms = preprocess_words(&group.terms.join(" "));
+        match terms.split_whitespace().count() {
+            0 => {}
+            1 => parts.push(terms),
+            _ => parts.push(format!("NEAR({terms}, {})", group.distance)),
+        }
+    }
+
+    let loose = preprocess_words(&parsed.terms.join(" "));
+    if !loose.is_empty() {
+        parts.push(loose);
+    }
+
+    parts.join(" ")
+}
+
+/// Replaces non-bareword characters with spaces and collapses whitespace.
+fn strip_non_bareword(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| if is_fts5_bareword_char(c) { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Counts lines containing highlight markers and extracts the first
+/// `max_lines` of them, stripping the markers and recording their byte spans.
+fn highlighted_lines(text: &str, max_lines: usize) -> (usize, Vec<MatchSnippet>) {
+    let mut count = 0;
+    let mut snippets = Vec::new();
+
+    for (idx, raw) in text.lines().enumerate() {
+        if !raw.contains(HIGHLIGHT_OPEN) {
+            continue;
+        }
+        count += 1;
+        if snippets.len() >= max_lines {
+            continue;
+        }
+
+        let mut line = String::with_capacity(raw.len());
+        let mut spans = Vec::new();
+        let mut open_at = None;
+        for c in raw.chars() {
+            match c {
+                HIGHLIGHT_OPEN => open_at = Some(line.len()),
+                HIGHLIGHT_CLOSE => {
+                    if let Some(start) = open_at.take() {
+                        spans.push((start, line.len()));
+                    }
+                }
+                _ => line.push(c),
+            }
+        }
+
+        // Match grep snippets: trailing whitespace trimmed, spans clamped
+        let trimmed_len = line.trim_end().len();
+        line.truncate(trimmed_len);
+        spans.retain_mut(|(start, end)| {
+            *end = (*end).min(trimmed_len);
+            *start < *end
+        });
+
+        snippets.push(MatchSnippet {
+            line_number: idx as u64 + 1,
+            line_content: line,
+            spans,
+        });
+    }
+
+    (count, snippets)
+}
+
 /// FTS5 boolean keywords that must be filtered out (case-sensitive, ALL-CAPS only per FTS5 spec).
 const FTS5_KEYWORDS: &[&str] = &["AND", "OR", "NOT", "NEAR"];
 
@@ -225,6 +368,33 @@ mod tests {
         assert_eq!(preprocess_query("test()"), "test*");
     }
 
+    #[test]
+    fn test_preprocess_query_phrase_inside_query() {
+        assert_eq!(
+            preprocess_query("login \"error handling\" retry"),
+            "\"error handling\" login* retry*"
+        );
+        // Punctuation inside phrases is left to the tokenizer's word boundaries
+        assert_eq!(preprocess_query("\"fn main()\""), "\"fn main\"");
+    }
+
+    #[test]
+    fn test_preprocess_query_near() {
+        assert_eq!(
+            preprocess_query("NEAR(cache invalidate, 3)"),
+            "NEAR(cache* invalidate*, 3)"
+        );
+        assert_eq!(
+            preprocess_query("NEAR(\"token\" refresh) auth"),
+            "NEAR(token* refresh*, 10) auth*"
+        );
+    }
+
+    #[test]
+    fn test_preprocess_query_unterminated_quote() {
+        assert_eq!(preprocess_query("\"broken query"), "broken* query*");
+    }
+
     #[test]
     fn test_preprocess_query_column_multi_token() {
         // Column prefix applied to all tokens when special chars split the value
@@ -272,6 +442,66 @@ mod tests {
         assert!(!results.unwrap().is_empty());
     }
 
+    #[test]
+    fn test_fts_search_phrase() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        db.upsert_file("a.rs", "// proper error handling here", 0x1)
+            .unwrap();
+        db.upsert_file("b.rs", "// handling of the error case", 0x2)
+            .unwrap();
+        let fts = FtsService::new(db);
+
+        let results = fts.search_phrase("error handling", 10).unwrap();
+        assert_eq!(results.len(), 1);
+
+        // Same phrase embedded in a larger query
+        let results = fts.search("proper \"error handling\"", 10).unwrap();
+        assert_eq!(results.len(), 1);
+
+        // Both files have the words within 3 tokens of each other
+        let results = fts.search("NEAR(error handling, 3)", 10).unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_fts_search_phrase_empty() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let fts = FtsService::new(db);
+        assert!(fts.search_phrase("!!", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fts_snippets_mark_matched_tokens() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let id = db
+            .upsert_file(
+                "cache.rs",
+                "// module header\nfn evict() {}\n// cache eviction keeps the cache small\n",
+                0x1,
+            )
+            .unwrap();
+        let fts = FtsService::new(db);
+
+        let snippets = fts.snippets("cache", &[id], 3).unwrap();
+        let (count, lines) = &snippets[&id];
+        assert_eq!(*count, 1);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].line_number, 3);
+        assert_eq!(
+            lines[0].line_content,
+            "// cache eviction keeps the cache small"
+        );
+        assert_eq!(lines[0].spans, vec![(3, 8), (28, 33)]);
+    }
+
+    #[test]
+    fn test_fts_snippets_skip_non_matching_files() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let id = db.upsert_file("a.rs", "fn main() {}", 0x1).unwrap();
+        let fts = FtsService::new(db);
+        assert!(fts.snippets("evict", &[id], 3).unwrap().is_empty());
+    }
+
     #[test]
     fn test_fts_search_all_special_chars_returns_empty() {
         let db = Arc::new(Database::in_memory().unwrap());
@@ -0,0 +1,172 @@
+//! Typo-tolerant identifier matching.
+//!
+//! Helpers for "did you mean" lookups: an edit-distance budget scaled to
+//! the identifier's length, a case-insensitive optimal string alignment
+//! distance (Levenshtein plus adjacent transpositions, so `serach` is one
+//! edit from `search`), and an identifier scanner for file content.
+
+/// Returns the largest edit distance tolerated for an identifier of
+/// `len` characters: 1 up to 4 chars, then one more per 4 chars, max 3.
+pub fn max_distance(len: usize) -> usize {
+    (len / 4).clamp(1, 3)
+}
+
+/// Case-insensitive optimal string alignment distance between `a` and `b`.
+///
+/// Returns `None` as soon as the distance must exceed `max`, which keeps
+/// vocabulary scans cheap.
+pub fn distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().flat_map(char::to_lowercase).collect();
+    let b: Vec<char> = b.chars().flat_map(char::to_lowercase).collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    // Three rolling rows: i-2, i-1, i
+    let mut prev2: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr: Vec<usize> = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        let mut row_min = curr[0];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut d = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d = d.min(prev2[j - 2] + 1);
+            }
+            curr[j] = d;
+            row_min = row_min.min(d);
+        }
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut prev2, &mut prev);
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    let d = prev[b.len()];
+    (d <= max).then_some(d)
+}
+
+/// Returns the longest identifier-like token in `query`, if any.
+///
+/// Fuzzy lookup works on one name at a time; surrounding words
+/// (`fn execute_serach`) are ignored.
+pub fn target_identifier(query: &str) -> Option<&str> {
+    identifiers(query)
+        .map(|(_, _, ident)| ident)
+        .max_by_key(|ident| ident.len())
+}
+
+/// Splits an identifier into lowercase words at `_` and camelCase
+/// boundaries, the way the FTS tokenizer sees it
+/// (`validateCredentials` → `validate`, `credentials`).
+pub fn words(ident: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in ident.chars() {
+        if (c == '_' || (c.is_uppercase() && prev_lower)) && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        if c != '_' {
+            current.extend(c.to_lowercase());
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Iterates `(line_number, byte_offset_in_line, identifier)` for every
+/// identifier (`[A-Za-z_][A-Za-z0-9_]*`) in `content`. Line numbers are
+/// 1-indexed.
+pub fn identifiers(content: &str) -> impl Iterator<Item = (u64, usize, &str)> {
+    content.lines().enumerate().flat_map(|(idx, line)| {
+        let bytes = line.as_bytes();
+        let mut pos = 0;
+        std::iter::from_fn(move || {
+            while pos < bytes.len() {
+                let b = bytes[pos];
+                if b.is_ascii_alphabetic() || b == b'_' {
+                    let start = pos;
+                    while pos < bytes.len()
+                        && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_')
+                    {
+                        pos += 1;
+                    }
+                    return Some((idx as u64 + 1, start, &line[start..pos]));
+                }
+                // Skip the rest of a token that starts with a digit (e.g. `0x1f`)
+                if b.is_ascii_digit() {
+                    while pos < bytes.len()
+                        && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_')
+                    {
+                        pos += 1;
+                    }
+                } else {
+                    pos += 1;
+                }
+            }
+            None
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_transposition_is_one_edit() {
+        assert_eq!(distance("execute_serach", "execute_search", 3), Some(1));
+        assert_eq!(distance("SearchSerivce", "SearchService", 3), Some(1));
+    }
+
+    #[test]
+    fn test_distance_is_case_insensitive() {
+        assert_eq!(distance("searchservice", "SearchService", 1), Some(0));
+    }
+
+    #[test]
+    fn test_distance_cutoff() {
+        assert_eq!(distance("config", "context", 1), None);
+        assert_eq!(distance("abc", "abcdef", 2), None);
+    }
+
+    #[test]
+    fn test_max_distance_scales_with_length() {
+        assert_eq!(max_distance(3), 1);
+        assert_eq!(max_distance(8), 2);
+        assert_eq!(max_distance(40), 3);
+    }
+
+    #[test]
+    fn test_identifiers_skip_numbers_and_track_lines() {
+        let found: Vec<_> = identifiers("let x1 = 0x1f;\nfoo_bar()").collect();
+        assert_eq!(found, vec![(1, 0, "let"), (1, 4, "x1"), (2, 0, "foo_bar")]);
+    }
+
+    #[test]
+    fn test_words_split_snake_and_camel_case() {
+        assert_eq!(
+            words("validate_credentials"),
+            vec!["validate", "credentials"]
+        );
+        assert_eq!(words("SearchSerivce"), vec!["search", "serivce"]);
+        assert_eq!(words("HTTPServer"), vec!["httpserver"]);
+    }
+
+    #[test]
+    fn test_target_identifier_picks_longest() {
+        assert_eq!(
+            target_identifier("fn execute_serach("),
+            Some("execute_serach")
+        );
+        assert_eq!(target_identifier("!!"), None);
+    }
+}
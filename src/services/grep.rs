@@ -21,8 +21,23 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
-/// Scored files with their matching line snippets.
-pub type GrepSearchResult = (Vec<(PathBuf, Score)>, HashMap<Arc<Path>, Vec<GrepMatch>>);
+/// Scored files with their per-file match counts and snippets.
+pub type GrepSearchResult = (Vec<(PathBuf, Score)>, HashMap<Arc<Path>, FileMatches>);
+
+/// Grep hits for one file.
+#[derive(Debug, Clone, Default)]
+pub struct FileMatches {
+    /// Matching lines found in the file (bounded by the search's match budget)
+    pub count: usize,
+    /// First matches in the file, for snippets (up to 3)
+    pub matches: Vec<GrepMatch>,
+}
+
+/// Maximum match spans recorded per line.
+///
+/// Keeps pathological lines (minified code, `.` patterns) from blowing up
+/// snippet size; the count of matching lines is unaffected.
+pub const MAX_SPANS_PER_LINE: usize = 8;
 
 /// Match found by grep.
 #[derive(Debug, Clone)]
@@ -30,8 +45,9 @@ pub struct GrepMatch {
     pub path: Arc<Path>,
     pub line_number: u64,
     pub line_content: String,
-    pub match_start: usize,
-    pub match_end: usize,
+    /// Byte ranges `(start, end)` of every match within `line_content`,
+    /// in line order (up to `MAX_SPANS_PER_LINE`)
+    pub spans: Vec<(usize, usize)>,
 }
 
 /// Configuration for grep operations.
@@ -224,15 +240,23 @@ impl GrepService {
                         &*matcher,
                         path,
                         UTF8(|line_number, line| {
-                            if let Ok(Some(m)) = matcher.find(line.as_bytes()) {
-                                file_matches.push(GrepMatch {
-                                    path: Arc::clone(&arc_path),
-                                    line_number,
-                                    line_content: line.trim_end().to_string(),
-                                    match_start: m.start(),
-                                    match_end: m.end(),
-                                });
-                            }
+                            // The sink only sees matching lines; spans are
+                            // clamped to the trimmed content
+                            let content = line.trim_end();
+                            let mut spans = Vec::new();
+                            let _ = matcher.find_iter(line.as_bytes(), |m| {
+                                let end = m.end().min(content.len());
+                                if m.start() < end {
+                                    spans.push((m.start(), end));
+                                }
+                                spans.len() < MAX_SPANS_PER_LINE
+                            });
+                            file_matches.push(GrepMatch {
+                                path: Arc::clone(&arc_path),
+                                line_number,
+                                line_content: content.to_string(),
+                                spans,
+                            });
                             Ok(true)
                         }),
                     )
@@ -283,8 +307,8 @@ impl GrepService {
 
     /// Searches and returns file-level results with scores plus top matches per file.
     ///
-    /// Returns `(scored_files, matches_by_file)` where `matches_by_file` contains
-    /// the top 3 `GrepMatch`es per file for snippet generation.
+    /// Returns `(scored_files, matches_by_file)` where `matches_by_file` holds
+    /// each file's match count and its top 3 `GrepMatch`es for snippets.
     ///
     /// # Errors
     ///
@@ -341,7 +365,7 @@ impl GrepService {
 
         // Split into scored results + file_matches in one pass
         let mut results: Vec<(PathBuf, Score)> = Vec::with_capacity(file_agg.len());
-        let mut file_matches: HashMap<Arc<Path>, Vec<GrepMatch>> =
+        let mut file_matches: HashMap<Arc<Path>, FileMatches> =
             HashMap::with_capacity(file_agg.len().min(limit));
 
         for (path, (count, max_line, snippets)) in file_agg {
@@ -355,7 +379,13 @@ impl GrepService {
 
             // Temporarily store all snippets; trimmed after truncation (1F)
             if !snippets.is_empty() {
-                file_matches.insert(Arc::clone(&path), snippets);
+                file_matches.insert(
+                    Arc::clone(&path),
+                    FileMatches {
+                        count,
+                        matches: snippets,
+                    },
+                );
             }
             results.push((path.to_path_buf(), score));
         }
@@ -417,6 +447,25 @@ mod tests {
         assert_eq!(matches.len(), 1);
     }
 
+    #[test]
+    fn test_grep_records_every_span_on_line() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.rs"), "let ab = ab + ab;\n").unwrap();
+        let service = GrepService::new(dir.path().to_path_buf()).unwrap();
+        let matches = service.search_parallel("ab", 10).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].spans, vec![(4, 6), (9, 11), (14, 16)]);
+    }
+
+    #[test]
+    fn test_grep_caps_spans_per_line() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.txt"), "x ".repeat(50)).unwrap();
+        let service = GrepService::new(dir.path().to_path_buf()).unwrap();
+        let matches = service.search_parallel("x", 10).unwrap();
+        assert_eq!(matches[0].spans.len(), MAX_SPANS_PER_LINE);
+    }
+
     #[test]
     fn test_redos_pattern_rejected() {
         let dir = setup_test_dir();
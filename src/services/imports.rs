@@ -0,0 +1,804 @@
+//! Import extraction and resolution for the file dependency graph.
+//!
+//! At index time each file's import statements are reduced to module specs
+//! (`crate::db::schema`, `.models.user`, `./util`, `example.com/m/pkg`) and
+//! stored unresolved. A [`Resolver`] then maps them onto workspace files
+//! following each language's module layout: Rust `mod`/`use` paths from
+//! the crate root or current module, Python packages, relative JS/TS
+//! specifiers, and Go packages under the module path in `go.mod`. Specs
+//! naming no workspace file (packages, the standard library) stay
+//! unresolved.
+
+use super::symbols::detect_file_type;
+use crate::db::ImportData;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Lines a single multi-line import statement may span.
+const MAX_STATEMENT_LINES: usize = 50;
+
+/// Extensions tried, in order, for an extensionless JS/TS specifier.
+const JS_EXTENSIONS: &[&str] = &[
+    ".ts", ".tsx", ".d.ts", ".js", ".jsx", ".mjs", ".cjs", ".mts", ".cts",
+];
+
+/// Whether imports of `file_type` are extracted.
+pub fn supports(file_type: &str) -> bool {
+    matches!(
+        file_type,
+        "rs" | "py" | "pyi" | "go" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts"
+    )
+}
+
+/// Extracts the import statements of a file as module specs, in line
+/// order. Unsupported file types yield none.
+pub fn extract_imports(content: &str, file_type: &str) -> Vec<ImportData> {
+    let lines: Vec<&str> = content.lines().collect();
+    match file_type {
+        "rs" => rust_imports(&lines),
+        "py" | "pyi" => python_imports(&lines),
+        "go" => go_imports(&lines),
+        ft if supports(ft) => js_imports(&lines),
+        _ => Vec::new(),
+    }
+}
+
+/// Joins the lines of a statement starting at `start` until one contains
+/// `end`, returning the text and the index of the last line.
+fn statement(lines: &[&str], start: usize, end: char) -> (String, usize) {
+    let mut text = String::new();
+    let last = (start + MAX_STATEMENT_LINES).min(lines.len()) - 1;
+    for (i, line) in lines.iter().enumerate().take(last + 1).skip(start) {
+        text.push_str(line.trim());
+        text.push(' ');
+        if line.contains(end) {
+            return (text, i);
+        }
+    }
+    (text, last)
+}
+
+fn rust_imports(lines: &[&str]) -> Vec<ImportData> {
+    static USE: OnceLock<Regex> = OnceLock::new();
+    static MOD: OnceLock<Regex> = OnceLock::new();
+    let use_re = USE.get_or_init(|| {
+        Regex::new(r"^(?:pub(?:\s*\([^)]*\))?\s+)?use\s+").expect("valid use regex")
+    });
+    let mod_re = MOD.get_or_init(|| {
+        Regex::new(r"^(?:pub(?:\s*\([^)]*\))?\s+)?mod\s+([A-Za-z_]\w*)\s*([;{])")
+            .expect("valid mod regex")
+    });
+
+    let mut imports = Vec::new();
+    // Brace depth, and the depths at which enclosing inline `mod x {` opened
+    let mut depth = 0usize;
+    let mut inline_mods: Vec<usize> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+        let code = trimmed.split("//").next().unwrap_or_default();
+        let mod_caps = mod_re.captures(trimmed);
+        if let Some(caps) = &mod_caps {
+            if &caps[2] == ";" && inline_mods.is_empty() {
+                // `mod x;` is a child of the current module, i.e. `self::x`
+                imports.push(ImportData {
+                    line: i + 1,
+                    spec: format!("self::{}", &caps[1]),
+                });
+            } else if &caps[2] == "{" {
+                inline_mods.push(depth);
+            }
+        } else if let Some(m) = use_re.find(trimmed) {
+            let (text, last) = statement(lines, i, ';');
+            let tree = text
+                .trim()
+                .strip_prefix(&trimmed[..m.end()])
+                .unwrap_or(&text)
+                .split(';')
+                .next()
+                .unwrap_or_default();
+            let mut specs = Vec::new();
+            expand_use_tree("", tree, &mut specs);
+            imports.extend(
+                specs
+                    .iter()
+                    .filter_map(|spec| outside_inline_modules(spec, inline_mods.len()))
+                    .map(|spec| ImportData { line: i + 1, spec }),
+            );
+            // Use trees are brace-balanced, so depth is unchanged
+            i = last + 1;
+            continue;
+        }
+
+        for c in code.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if inline_mods.last() == Some(&depth) {
+                        inline_mods.pop();
+                    }
+                }
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    imports
+}
+
+/// Rewrites a path used inside `levels` nested inline modules
+/// (`mod tests { use super::*; }`) relative to the file's own module.
+/// Paths that stay within the file yield `None`.
+fn outside_inline_modules(spec: &str, levels: usize) -> Option<String> {
+    if levels == 0 {
+        return Some(spec.to_string());
+    }
+    if spec == "self" || spec.starts_with("self::") {
+        return None;
+    }
+    let mut rest = spec;
+    let mut supers = 0;
+    while rest == "super" || rest.starts_with("super::") {
+        supers += 1;
+        rest = rest.get("super::".len()..).unwrap_or_default();
+    }
+    match supers {
+        0 => Some(spec.to_string()),
+        n if n <= levels => None,
+        n if rest.is_empty() => Some(vec!["super"; n - levels].join("::")),
+        n => Some(format!("{}{rest}", "super::".repeat(n - levels))),
+    }
+}
+
+/// Expands a Rust use tree (`a::{b, c::{self, D as E}}`) into full paths
+/// (`a::b`, `a::c`, `a::c::D`). Globs keep their parent path.
+fn expand_use_tree(prefix: &str, tree: &str, out: &mut Vec<String>) {
+    let join = |prefix: &str, path: &str| {
+        let path = path.trim().trim_start_matches("::").trim_end_matches("::");
+        match (prefix.is_empty(), path.is_empty()) {
+            (_, true) => prefix.to_string(),
+            (true, false) => path.to_string(),
+            (false, false) => format!("{prefix}::{path}"),
+        }
+    };
+
+    let tree = tree.trim();
+    if tree.is_empty() {
+        return;
+    }
+    if let Some(open) = tree.find('{') {
+        let close = tree.rfind('}').unwrap_or(tree.len());
+        let prefix = join(prefix, &tree[..open]);
+        let inner = tree.get(open + 1..close).unwrap_or_default();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (i, c) in inner.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    expand_use_tree(&prefix, &inner[start..i], out);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        expand_use_tree(&prefix, &inner[start..], out);
+        return;
+    }
+
+    // Drop `as alias`, then `self` / `*` refer to the prefix itself
+    let path = tree.split(" as ").next().unwrap_or_default().trim();
+    let path = path.trim_end_matches('*').trim_end_matches("::");
+    let spec = match path {
+        "" | "self" => prefix.to_string(),
+        _ => join(prefix, path.strip_suffix("::self").unwrap_or(path)),
+    };
+    if !spec.is_empty() && !out.contains(&spec) {
+        out.push(spec);
+    }
+}
+
+fn python_imports(lines: &[&str]) -> Vec<ImportData> {
+    let mut imports = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+        let code = trimmed.split('#').next().unwrap_or_default().trim_end();
+        let line = i + 1;
+
+        if let Some(rest) = code.strip_prefix("import ") {
+            let (text, last) = continued(lines, i, code);
+            let names = text.trim().strip_prefix("import ").unwrap_or(rest);
+            for name in names.split(',') {
+                let module = name.split(" as ").next().unwrap_or_default().trim();
+                if !module.is_empty() {
+                    imports.push(ImportData {
+                        line,
+                        spec: module.to_string(),
+                    });
+                }
+            }
+            i = last;
+        } else if let Some(rest) = code.strip_prefix("from ") {
+            let (text, last) = if code.contains('(') && !code.contains(')') {
+                statement(lines, i, ')')
+            } else {
+                continued(lines, i, code)
+            };
+            let text = text.trim().strip_prefix("from ").unwrap_or(rest);
+            if let Some((module, names)) = text.split_once(" import ") {
+                let module = module.trim();
+                let names = names.trim().trim_start_matches('(');
+                for name in names.split(',') {
+                    let name = name
+                        .split('#')
+                        .next()
+                        .unwrap_or_default()
+                        .split(" as ")
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .trim_end_matches(')')
+                        .trim();
+                    let spec = match name {
+                        "" => continue,
+                        "*" => module.to_string(),
+                        _ if module.chars().all(|c| c == '.') => format!("{module}{name}"),
+                        _ => format!("{module}.{name}"),
+                    };
+                    imports.push(ImportData { line, spec });
+                }
+            }
+            i = last;
+        }
+        i += 1;
+    }
+    imports
+}
+
+/// Joins backslash-continued Python lines starting at `start`.
+fn continued(lines: &[&str], start: usize, first: &str) -> (String, usize) {
+    let mut text = first.trim_end_matches('\\').to_string();
+    let mut last = start;
+    let mut current = first;
+    while current.ends_with('\\') && last + 1 < lines.len() {
+        last += 1;
+        current = lines[last].trim();
+        text.push(' ');
+        text.push_str(current.trim_end_matches('\\'));
+    }
+    (text, last)
+}
+
+fn js_imports(lines: &[&str]) -> Vec<ImportData> {
+    static SPEC: OnceLock<Regex> = OnceLock::new();
+    let re = SPEC.get_or_init(|| {
+        Regex::new(
+            r#"(?:^\s*import\s+|[\s}]from\s+|\brequire\s*\(\s*|\bimport\s*\(\s*)['"]([^'"]+)['"]"#,
+        )
+        .expect("valid import regex")
+    });
+
+    let mut imports = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") || trimmed.starts_with('*') {
+            continue;
+        }
+        for caps in re.captures_iter(line) {
+            imports.push(ImportData {
+                line: i + 1,
+                spec: caps[1].to_string(),
+            });
+        }
+    }
+    imports
+}
+
+fn go_imports(lines: &[&str]) -> Vec<ImportData> {
+    /// The quoted path of an import spec line (`alias "path"`).
+    fn quoted(s: &str) -> Option<&str> {
+        let start = s.find('"')? + 1;
+        let end = start + s[start..].find('"')?;
+        Some(&s[start..end])
+    }
+
+    let mut imports = Vec::new();
+    let mut in_block = false;
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if in_block {
+            if trimmed.starts_with(')') {
+                in_block = false;
+            } else if let Some(path) = quoted(trimmed).filter(|_| !trimmed.starts_with("//")) {
+                imports.push(ImportData {
+                    line: i + 1,
+                    spec: path.to_string(),
+                });
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("import") {
+            let rest = rest.trim_start();
+            if rest.starts_with('(') {
+                in_block = true;
+            } else if let Some(path) = quoted(rest) {
+                imports.push(ImportData {
+                    line: i + 1,
+                    spec: path.to_string(),
+                });
+            }
+        }
+    }
+    imports
+}
+
+/// Maps import specs onto indexed workspace files.
+///
+/// Built once per index run from the full set of indexed paths; the
+/// manifests it consults (`Cargo.toml` package names, `go.mod` module
+/// paths) are read from disk on first use and cached.
+pub struct Resolver {
+    root: PathBuf,
+    paths: HashSet<String>,
+    by_dir: HashMap<PathBuf, Vec<String>>,
+    rust_crates: Option<HashMap<String, PathBuf>>,
+    go_modules: HashMap<PathBuf, Option<(PathBuf, String)>>,
+}
+
+impl Resolver {
+    /// Creates a resolver over the given (absolute) indexed paths.
+    pub fn new(root: &Path, paths: impl IntoIterator<Item = String>) -> Self {
+        let paths: HashSet<String> = paths.into_iter().collect();
+        let mut by_dir: HashMap<PathBuf, Vec<String>> = HashMap::new();
+        for path in &paths {
+            if let Some(dir) = Path::new(path).parent() {
+                by_dir
+                    .entry(dir.to_path_buf())
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+        Self {
+            root: root.to_path_buf(),
+            paths,
+            by_dir,
+            rust_crates: None,
+            go_modules: HashMap::new(),
+        }
+    }
+
+    /// Resolves an import of `importer` to the workspace files it names.
+    pub fn resolve(&mut self, importer: &str, spec: &str) -> Vec<String> {
+        let importer = Path::new(importer);
+        let resolved = match detect_file_type(importer).as_str() {
+            "rs" => self.resolve_rust(importer, spec),
+            "py" | "pyi" => self.resolve_python(importer, spec),
+            "go" => return self.resolve_go(importer, spec),
+            ft if supports(ft) => self.resolve_js(importer, spec),
+            _ => None,
+        };
+        resolved.into_iter().collect()
+    }
+
+    /// The first candidate that is an indexed file.
+    fn first_indexed(&self, candidates: impl IntoIterator<Item = PathBuf>) -> Option<String> {
+        candidates
+            .into_iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .find(|p| self.paths.contains(p))
+    }
+
+    fn resolve_rust(&mut self, importer: &Path, spec: &str) -> Option<String> {
+        let mut segments: Vec<&str> = spec.split("::").filter(|s| !s.is_empty()).collect();
+        let first = *segments.first()?;
+        let (base, is_module_path) = match first {
+            "crate" => {
+                segments.remove(0);
+                (self.rust_crate_root(importer), true)
+            }
+            "self" | "super" => {
+                let mut dir = rust_module_dir(importer);
+                if first == "self" {
+                    segments.remove(0);
+                }
+                while segments.first() == Some(&"super") {
+                    segments.remove(0);
+                    dir = dir.parent()?.to_path_buf();
+                }
+                (dir, true)
+            }
+            name => match self.rust_crate(name) {
+                Some(src) => {
+                    segments.remove(0);
+                    (src, true)
+                }
+                // A module in scope of the current one (`mod x; use x::Y;`)
+                None => (rust_module_dir(importer), false),
+            },
+        };
+
+        // Longest module prefix: the rest are items inside that module
+        for len in (1..=segments.len()).rev() {
+            let path: PathBuf = segments[..len].iter().fold(base.clone(), |p, s| p.join(s));
+            if let Some(found) =
+                self.first_indexed([with_suffix(&path, ".rs"), path.join("mod.rs")])
+            {
+                return Some(found);
+            }
+        }
+        // A missing lowercase module (`crate::session::Session` before
+        // session.rs exists) stays unresolved rather than hitting the base
+        let names_item =
+            segments.len() <= 1 || segments[0].starts_with(|c: char| c.is_ascii_uppercase());
+        if !is_module_path || !names_item {
+            return None;
+        }
+        // An item of the base module itself (`use crate::Config`, `use super::X`)
+        self.first_indexed([
+            base.join("mod.rs"),
+            base.join("lib.rs"),
+            base.join("main.rs"),
+            with_suffix(&base, ".rs"),
+        ])
+    }
+
+    /// The directory `crate::` paths start from: the directory of the
+    /// nearest `lib.rs`/`main.rs`, or the file's own for crate roots.
+    fn rust_crate_root(&self, importer: &Path) -> PathBuf {
+        let dir = importer.parent().unwrap_or(&self.root);
+        if is_rust_crate_root(importer) {
+            return dir.to_path_buf();
+        }
+        dir.ancestors()
+            .take_while(|d| d.starts_with(&self.root))
+            .find(|d| {
+                self.paths
+                    .contains(&d.join("lib.rs").to_string_lossy().to_string())
+                    || self
+                        .paths
+                        .contains(&d.join("main.rs").to_string_lossy().to_string())
+            })
+            .unwrap_or(dir)
+            .to_path_buf()
+    }
+
+    /// The `src` directory of the workspace library crate named `name`
+    /// (as written in paths, `-` replaced by `_`).
+    fn rust_crate(&mut self, name: &str) -> Option<PathBuf> {
+        let crates = self.rust_crates.get_or_insert_with(|| {
+            let mut crates = HashMap::new();
+            for path in &self.paths {
+                let path = Path::new(path);
+                if !path.ends_with("src/lib.rs") {
+                    continue;
+                }
+                let (Some(src), Some(dir)) = (path.parent(), path.parent().and_then(Path::parent))
+                else {
+                    continue;
+                };
+                let name = fs::read_to_string(dir.join("Cargo.toml"))
+                    .ok()
+                    .and_then(|manifest| cargo_package_name(&manifest))
+                    .or_else(|| dir.file_name().map(|n| n.to_string_lossy().to_string()));
+                if let Some(name) = name {
+                    crates.insert(name.replace('-', "_"), src.to_path_buf());
+                }
+            }
+            crates
+        });
+        crates.get(name).cloned()
+    }
+
+    fn resolve_python(&self, importer: &Path, spec: &str) -> Option<String> {
+        let dots = spec.chars().take_while(|&c| c == '.').count();
+        let segments: Vec<&str> = spec[dots..].split('.').filter(|s| !s.is_empty()).collect();
+
+        let bases: Vec<PathBuf> = if dots > 0 {
+            let mut dir = importer.parent()?;
+            for _ in 1..dots {
+                dir = dir.parent()?;
+            }
+            vec![dir.to_path_buf()]
+        } else {
+            // Absolute imports: the workspace root, `src/`, then the
+            // importer's own ancestors (scripts run from their directory)
+            let mut bases = vec![self.root.clone(), self.root.join("src")];
+            bases.extend(
+                importer
+                    .ancestors()
+                    .skip(1)
+                    .take_while(|d| d.starts_with(&self.root))
+                    .map(Path::to_path_buf),
+            );
+            bases
+        };
+
+        let min_len = usize::from(dots == 0);
+        for base in &bases {
+            for len in (min_len..=segments.len()).rev() {
+                let path: PathBuf = segments[..len].iter().fold(base.clone(), |p, s| p.join(s));
+                let mut candidates = vec![path.join("__init__.py")];
+                if len > 0 {
+                    candidates.insert(0, with_suffix(&path, ".py"));
+                    candidates.insert(1, with_suffix(&path, ".pyi"));
+                }
+                if let Some(found) = self.first_indexed(candidates) {
+                    return Some(found);
+                }
+            }
+        }
+        None
+    }
+
+    fn resolve_js(&self, importer: &Path, spec: &str) -> Option<String> {
+        // Bare specifiers are packages (path aliases are not followed)
+        if !(spec.starts_with("./") || spec.starts_with("../") || spec == "." || spec == "..") {
+            return None;
+        }
+        let target = normalize(&importer.parent()?.join(spec));
+        // `./util.js` in TypeScript sources names `./util.ts`
+        let stem = [".js", ".jsx", ".mjs", ".cjs"]
+            .iter()
+            .find_map(|ext| target.to_str()?.strip_suffix(ext))
+            .map(PathBuf::from);
+
+        let mut candidates = vec![target.clone()];
+        for base in stem.iter().chain(std::iter::once(&target)) {
+            candidates.extend(JS_EXTENSIONS.iter().map(|ext| with_suffix(base, ext)));
+        }
+        candidates.extend(
+            JS_EXTENSIONS
+                .iter()
+                .map(|ext| with_suffix(&target.join("index"), ext)),
+        );
+        self.first_indexed(candidates)
+    }
+
+    fn resolve_go(&mut self, importer: &Path, spec: &str) -> Vec<String> {
+        let Some((module_dir, module_path)) = importer.parent().and_then(|d| self.go_module(d))
+        else {
+            return Vec::new();
+        };
+        let rel = if spec == module_path {
+            ""
+        } else {
+            match spec.strip_prefix(&format!("{module_path}/")) {
+                Some(rel) => rel,
+                None => return Vec::new(),
+            }
+        };
+
+        // A Go import names a package: every non-test file of its directory
+        let mut files: Vec<String> = self
+            .by_dir
+            .get(&module_dir.join(rel))
+            .into_iter()
+            .flatten()
+            .filter(|p| p.ends_with(".go") && !p.ends_with("_test.go"))
+            .cloned()
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// The nearest `go.mod` at or above `dir` within the root, as its
+    /// directory and module path.
+    fn go_module(&mut self, dir: &Path) -> Option<(PathBuf, String)> {
+        if let Some(found) = self.go_modules.get(dir) {
+            return found.clone();
+        }
+        let found = dir
+            .ancestors()
+            .take_while(|d| d.starts_with(&self.root))
+            .find_map(|d| {
+                let manifest = fs::read_to_string(d.join("go.mod")).ok()?;
+                let module = manifest.lines().find_map(|l| {
+                    l.trim()
+                        .strip_prefix("module ")
+                        .map(|m| m.trim().trim_matches('"').to_string())
+                })?;
+                Some((d.to_path_buf(), module))
+            });
+        self.go_modules.insert(dir.to_path_buf(), found.clone());
+        found
+    }
+}
+
+/// Whether a Rust file is a crate root: `lib.rs`, `main.rs`, binaries
+/// under `src/bin`, and integration tests, examples and benches.
+fn is_rust_crate_root(path: &Path) -> bool {
+    let stem = path.file_stem().and_then(|s| s.to_str());
+    let dir = path
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|d| d.to_str());
+    matches!(stem, Some("lib" | "main"))
+        || matches!(dir, Some("tests" | "examples" | "benches" | "bin"))
+}
+
+/// The directory holding a Rust module's child modules.
+fn rust_module_dir(path: &Path) -> PathBuf {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    match path.file_stem().and_then(|s| s.to_str()) {
+        Some("mod") => dir.to_path_buf(),
+        _ if is_rust_crate_root(path) => dir.to_path_buf(),
+        Some(stem) => dir.join(stem),
+        None => dir.to_path_buf(),
+    }
+}
+
+/// The `[package]` name in a `Cargo.toml`.
+fn cargo_package_name(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if in_package {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim() == "name" {
+                    return Some(value.trim().trim_matches('"').to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Appends `suffix` to the last path component (`a/b` + `.rs` → `a/b.rs`).
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut s = path.as_os_str().to_os_string();
+    s.push(suffix);
+    PathBuf::from(s)
+}
+
+/// Resolves `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn specs(content: &str, file_type: &str) -> Vec<(usize, String)> {
+        extract_imports(content, file_type)
+            .into_iter()
+            .map(|i| (i.line, i.spec))
+            .collect()
+    }
+
+    fn resolver(files: &[&str]) -> Resolver {
+        Resolver::new(Path::new("/r"), files.iter().map(|f| format!("/r/{f}")))
+    }
+
+    #[test]
+    fn test_rust_use_trees_and_mods() {
+        let content = "mod db;\npub(crate) mod tools;\nuse std::fmt;\n\
+                       use crate::{\n    db::{self, Database},\n    services::*,\n};\n\
+                       mod tests {\n    use super::*;\n    use super::super::x;\n}\n";
+        assert_eq!(
+            specs(content, "rs"),
+            vec![
+                (1, "self::db".to_string()),
+                (2, "self::tools".to_string()),
+                (3, "std::fmt".to_string()),
+                (4, "crate::db".to_string()),
+                (4, "crate::db::Database".to_string()),
+                (4, "crate::services".to_string()),
+                (10, "super::x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_imports() {
+        let content = "import os, app.models as m\nfrom . import util\n\
+                       from ..core.db import (\n    Session,\n    engine,\n)\n";
+        assert_eq!(
+            specs(content, "py"),
+            vec![
+                (1, "os".to_string()),
+                (1, "app.models".to_string()),
+                (2, ".util".to_string()),
+                (3, "..core.db.Session".to_string()),
+                (3, "..core.db.engine".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_js_and_go_imports() {
+        let js = "import a from './a';\nimport {\n  b,\n} from \"../b\";\n\
+                  const c = require('c');\nexport * from './d';\n";
+        assert_eq!(
+            specs(js, "ts"),
+            vec![
+                (1, "./a".to_string()),
+                (4, "../b".to_string()),
+                (5, "c".to_string()),
+                (6, "./d".to_string()),
+            ]
+        );
+        let go = "import \"fmt\"\n\nimport (\n\tlog \"example.com/m/log\"\n\t\"os\"\n)\n";
+        assert_eq!(
+            specs(go, "go"),
+            vec![
+                (1, "fmt".to_string()),
+                (4, "example.com/m/log".to_string()),
+                (5, "os".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_rust_paths() {
+        let mut r = resolver(&[
+            "src/lib.rs",
+            "src/db/mod.rs",
+            "src/db/schema.rs",
+            "src/tools/graph.rs",
+            "src/tools/mod.rs",
+        ]);
+        assert_eq!(
+            r.resolve("/r/src/tools/graph.rs", "crate::db::schema::init"),
+            vec!["/r/src/db/schema.rs"]
+        );
+        assert_eq!(
+            r.resolve("/r/src/tools/graph.rs", "super::mod_item"),
+            vec!["/r/src/tools/mod.rs"]
+        );
+        assert_eq!(
+            r.resolve("/r/src/lib.rs", "self::db"),
+            vec!["/r/src/db/mod.rs"]
+        );
+        assert_eq!(
+            r.resolve("/r/src/db/mod.rs", "crate::Config"),
+            vec!["/r/src/lib.rs"]
+        );
+        assert!(r.resolve("/r/src/lib.rs", "std::fmt").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_python_and_js() {
+        let mut r = resolver(&[
+            "app/__init__.py",
+            "app/models.py",
+            "app/api/views.py",
+            "web/util.ts",
+            "web/components/index.tsx",
+            "web/pages/home.ts",
+        ]);
+        assert_eq!(
+            r.resolve("/r/app/api/views.py", "..models.User"),
+            vec!["/r/app/models.py"]
+        );
+        assert_eq!(
+            r.resolve("/r/app/api/views.py", "app.models"),
+            vec!["/r/app/models.py"]
+        );
+        assert!(r.resolve("/r/app/api/views.py", "os.path").is_empty());
+        assert_eq!(
+            r.resolve("/r/web/pages/home.ts", "../util.js"),
+            vec!["/r/web/util.ts"]
+        );
+        assert_eq!(
+            r.resolve("/r/web/pages/home.ts", "../components"),
+            vec!["/r/web/components/index.tsx"]
+        );
+        assert!(r.resolve("/r/web/pages/home.ts", "react").is_empty());
+    }
+}
@@ -5,10 +5,11 @@
 //! collision resistance for content hashing.
 
 use crate::db::Database;
-use crate::db::FileData;
+use crate::db::{FileData, ImportData, SymbolData};
 use crate::error::{IndexError, ServerError};
 use crate::security;
 use crate::services::TrigramIndex;
+use crate::services::{imports, symbols};
 use crate::types::FileId;
 use ignore::WalkBuilder;
 use rayon::prelude::*;
@@ -156,8 +157,9 @@ impl Indexer {
 
     /// Performs incremental indexing using two-phase parallel processing.
     ///
-    /// **Phase 1 (Parallel):** Read files and compute hashes using rayon.
-    /// This is CPU-bound work that benefits from parallelization.
+    /// **Phase 1 (Parallel):** Read files, compute hashes, and parse symbols
+    /// and imports using rayon. This is CPU-bound work that benefits from
+    /// parallelization, and it runs before any lock is taken.
     ///
     /// **Phase 2 (Sequential):** Batch insert into database and update trigrams.
     /// This is I/O-bound work where batching is more effective than parallelism.
@@ -193,7 +195,7 @@ impl Indexer {
         let total = files.len();
 
         // Phase 1: parallel file reading + hashing
-        let (file_data, seen_paths) = self.phase1_read_and_hash(&files, &existing_hashes);
+        let (file_data, parsed, seen_paths) = self.phase1_read_and_hash(&files, &existing_hashes);
         let files_unchanged = total - file_data.len();
 
         // Phase 2: sequential DB writes + trigrams + deletions
@@ -217,6 +219,7 @@ impl Indexer {
 
                 self.phase2_batch_write(
                     &file_data,
+                    parsed,
                     &indexing_conn,
                     &mut trigram_guard,
                     &progress,
@@ -233,6 +236,9 @@ impl Indexer {
             } // Drop write guard before save_trigrams (which takes a read lock)
 
             self.persist_trigrams(&indexing_conn, &state, force)?;
+            if state.files_indexed > 0 || state.files_deleted > 0 {
+                self.resolve_imports(&indexing_conn)?;
+            }
             Ok(state)
         })();
 
@@ -259,16 +265,18 @@ impl Indexer {
         Ok(state)
     }
 
-    /// Phase 1: Parallel file reading and hash computation.
+    /// Phase 1: Parallel file reading, hash computation, and symbol and
+    /// import extraction.
     ///
-    /// Returns changed files (needing indexing) and the set of all seen paths.
+    /// Returns changed files (needing indexing), their parsed rows in the
+    /// same order, and the set of all seen paths.
     fn phase1_read_and_hash(
         &self,
         files: &[PathBuf],
         existing_hashes: &HashMap<String, u64>,
-    ) -> (Vec<FileData>, HashSet<String>) {
+    ) -> (Vec<FileData>, Vec<ParsedFile>, HashSet<String>) {
         // Embarrassingly parallel — no shared mutable state
-        let file_data: Vec<FileData> = files
+        let (file_data, parsed): (Vec<FileData>, Vec<ParsedFile>) = files
             .par_iter()
             .filter_map(|path| {
                 let content = fs::read_to_string(path).ok()?;
@@ -279,13 +287,22 @@ impl Indexer {
                     return None; // Skip unchanged files
                 }
 
-                Some(FileData {
-                    path: path_str,
-                    content,
-                    hash,
-                })
+                // Tree-sitter parsing is the costliest step; doing it here
+                // keeps it out from under the trigram write lock
+                let parsed = ParsedFile {
+                    symbols: extract_file_symbols(&path_str, &content),
+                    imports: extract_file_imports(&path_str, &content),
+                };
+                Some((
+                    FileData {
+                        path: path_str,
+                        content,
+                        hash,
+                    },
+                    parsed,
+                ))
             })
-            .collect();
+            .unzip();
 
         // Collect all seen paths (including unchanged ones).
         // Uses iter() not par_iter(): to_string_lossy() is pure allocation,
@@ -295,18 +312,23 @@ impl Indexer {
             .map(|p| p.to_string_lossy().to_string())
             .collect();
 
-        (file_data, seen_paths)
+        (file_data, parsed, seen_paths)
     }
 
-    /// Phase 2: Sequential batch upserts and trigram updates.
+    /// Phase 2: Sequential batch upserts, trigram, symbol and import table updates.
+    ///
+    /// `parsed` holds each file's rows from phase 1, in `file_data` order;
+    /// only writes happen here, while the trigram lock is held.
     fn phase2_batch_write(
         &self,
         file_data: &[FileData],
+        parsed: Vec<ParsedFile>,
         conn: &rusqlite::Connection,
         trigram_guard: &mut TrigramIndex,
         progress: &Option<ProgressCallback>,
         state: &mut IndexProgress,
     ) -> Result<(), ServerError> {
+        let mut parsed = parsed.into_iter();
         for batch in file_data.chunks(BATCH_SIZE) {
             if let Some(ref cb) = progress {
                 state.current_file = batch.first().map(|f| PathBuf::from(&f.path));
@@ -315,10 +337,18 @@ impl Indexer {
 
             let file_ids = Database::upsert_files_batch_on(conn, batch)?;
 
-            for (data, file_id) in batch.iter().zip(file_ids) {
-                trigram_guard.add_file(file_id, &data.content);
+            for (data, file_id) in batch.iter().zip(&file_ids) {
+                trigram_guard.add_file(*file_id, &data.content);
             }
 
+            let (file_symbols, file_imports): (Vec<_>, Vec<_>) = file_ids
+                .into_iter()
+                .zip(parsed.by_ref())
+                .map(|(file_id, rows)| ((file_id, rows.symbols), (file_id, rows.imports)))
+                .unzip();
+            Database::replace_symbols_batch_on(conn, &file_symbols)?;
+            Database::replace_imports_batch_on(conn, &file_imports)?;
+
             state.files_indexed += batch.len();
             state.files_processed += batch.len();
         }
@@ -345,6 +375,38 @@ impl Indexer {
         Ok(())
     }
 
+    /// Resolves stored imports without a target against the indexed files.
+    ///
+    /// Runs after every batch of the run is written, so imports of files
+    /// added in the same run resolve. Imports left unresolved by earlier
+    /// runs (or whose target was deleted) are retried as well.
+    fn resolve_imports(&self, conn: &rusqlite::Connection) -> Result<(), ServerError> {
+        let pending = Database::pending_imports_on(conn)?;
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let ids: HashMap<String, FileId> = Database::get_all_file_paths_on(conn)?
+            .into_iter()
+            .map(|(id, path)| (path, id))
+            .collect();
+        let mut resolver = imports::Resolver::new(&self.root, ids.keys().cloned());
+        let resolved: Vec<(FileId, ImportData, Vec<FileId>)> = pending
+            .into_iter()
+            .filter_map(|(file_id, path, import)| {
+                let targets: Vec<FileId> = resolver
+                    .resolve(&path, &import.spec)
+                    .iter()
+                    .filter_map(|target| ids.get(target).copied())
+                    .filter(|&target| target != file_id)
+                    .collect();
+                (!targets.is_empty()).then_some((file_id, import, targets))
+            })
+            .collect();
+        Database::set_import_targets_on(conn, &resolved)?;
+        Ok(())
+    }
+
     /// Persists the trigram index to the database if changes were made.
     fn persist_trigrams(
         &self,
@@ -390,6 +452,12 @@ impl Indexer {
 
         let file_id = self.db.upsert_file(&path_str, &content, hash)?;
         self.index_trigrams(file_id, &content);
+        self.db
+            .replace_symbols(file_id, extract_file_symbols(&path_str, &content))?;
+        self.db
+            .replace_imports(file_id, extract_file_imports(&path_str, &content))?;
+        let conn = self.db.conn()?;
+        self.resolve_imports(&conn)?;
 
         Ok(file_id)
     }
@@ -504,7 +572,7 @@ impl Indexer {
             .filter(|p| p.exists() && self.should_index_path(p))
             .collect();
 
-        let (file_data, _) = self.phase1_read_and_hash(&changed_files, existing_hashes);
+        let (file_data, parsed, _) = self.phase1_read_and_hash(&changed_files, existing_hashes);
 
         let total = existing_paths.len();
         let mut state = IndexProgress {
@@ -524,6 +592,7 @@ impl Indexer {
 
                 self.phase2_batch_write(
                     &file_data,
+                    parsed,
                     &indexing_conn,
                     &mut trigram_guard,
                     progress,
@@ -548,6 +617,9 @@ impl Indexer {
             }
 
             self.persist_trigrams(&indexing_conn, &state, false)?;
+            if state.files_indexed > 0 || state.files_deleted > 0 {
+                self.resolve_imports(&indexing_conn)?;
+            }
             Database::set_last_indexed_commit_on(&indexing_conn, &diff.head_oid)?;
 
             Ok(state)
@@ -621,6 +693,24 @@ fn ascii_lower_check(s: &str, set: &HashSet<String>) -> Option<bool> {
     Some(set.contains(lowered))
 }
 
+/// Extracts symbol rows for a file, picking the extractor by extension.
+fn extract_file_symbols(path: &str, content: &str) -> Vec<SymbolData> {
+    let file_type = symbols::detect_file_type(Path::new(path));
+    symbols::index_records(content, &file_type)
+}
+
+/// A changed file's symbol and import rows, parsed in phase 1.
+struct ParsedFile {
+    symbols: Vec<SymbolData>,
+    imports: Vec<ImportData>,
+}
+
+/// Extracts import rows for a file, picking the parser by extension.
+fn extract_file_imports(path: &str, content: &str) -> Vec<ImportData> {
+    let file_type = symbols::detect_file_type(Path::new(path));
+    imports::extract_imports(content, &file_type)
+}
+
 /// Computes xxHash (xxh3_64) of content.
 ///
 /// xxHash is ~30x faster than SHA256 while providing
@@ -748,6 +838,43 @@ mod tests {
         assert_eq!(db.file_count().unwrap(), 1);
     }
 
+    #[test]
+    fn test_index_persists_symbols() {
+        let dir = TempDir::new().unwrap();
+        let db = Arc::new(Database::in_memory().unwrap());
+        let trigram = Arc::new(RwLock::new(TrigramIndex::new()));
+
+        fs::write(
+            dir.path().join("svc.rs"),
+            "pub struct Service;\n\nimpl Service {\n    pub fn run(&self) {\n    }\n}\n",
+        )
+        .unwrap();
+
+        let indexer = Indexer::new(db.clone(), trigram, dir.path().to_path_buf());
+        indexer.index(None, false).unwrap();
+
+        let path = dir.path().join("svc.rs");
+        let file_id = db
+            .get_file_id(path.to_string_lossy().as_ref())
+            .unwrap()
+            .unwrap();
+        let symbols = db.get_file_symbols(file_id).unwrap();
+        let run = symbols.iter().find(|s| s.name == "run").unwrap();
+        assert_eq!((run.line, run.end_line), (4, Some(5)));
+        assert_eq!(run.parent.as_deref(), Some("Service"));
+
+        // Re-indexing a changed file replaces its rows
+        fs::write(&path, "fn only() {}\n").unwrap();
+        indexer.index(None, false).unwrap();
+        let names: Vec<String> = db
+            .get_file_symbols(file_id)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["only"]);
+    }
+
     #[test]
     fn test_hash_computation() {
         let hash1 = compute_hash("hello");
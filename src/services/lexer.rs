@@ -0,0 +1,476 @@
+//! Lightweight per-language lexing of comment and string regions.
+//!
+//! Not a tokenizer: the scanner tracks just enough state (block comments,
+//! multi-line and raw strings, escapes) to tell whether a byte offset sits
+//! in code, a comment or a string literal. Python docstrings count as
+//! comments. Files of unknown languages are all code.
+
+use std::fmt;
+
+/// Where a byte of source text sits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    Code,
+    Comment,
+    String,
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Code => f.write_str("code"),
+            Self::Comment => f.write_str("comment"),
+            Self::String => f.write_str("string"),
+        }
+    }
+}
+
+/// A string literal delimiter.
+struct Quote {
+    open: &'static str,
+    close: &'static str,
+    /// Whether the literal may span lines (unterminated single-line
+    /// literals end at the newline)
+    multiline: bool,
+    /// Whether `\` escapes the next byte
+    escapes: bool,
+}
+
+const fn quote(delim: &'static str, multiline: bool, escapes: bool) -> Quote {
+    Quote {
+        open: delim,
+        close: delim,
+        multiline,
+        escapes,
+    }
+}
+
+/// Comment and string syntax of a language family.
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comments: &'static [(&'static str, &'static str)],
+    /// Block comments nest (`/* /* */ */`)
+    nested: bool,
+    /// Longest delimiters first
+    quotes: &'static [Quote],
+    /// Rust raw strings (`r#"..."#`) and char literals, told apart from
+    /// lifetimes
+    rust_literals: bool,
+    /// Triple-quoted strings opening a statement are docstrings
+    docstrings: bool,
+}
+
+const C_COMMENTS: &[(&str, &str)] = &[("/*", "*/")];
+
+const RUST: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: C_COMMENTS,
+    nested: true,
+    quotes: &[quote("\"", true, true)],
+    rust_literals: true,
+    docstrings: false,
+};
+
+const C_LIKE: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: C_COMMENTS,
+    nested: false,
+    quotes: &[
+        quote("\"\"\"", true, true),
+        quote("\"", false, true),
+        quote("'", false, true),
+    ],
+    rust_literals: false,
+    docstrings: false,
+};
+
+const JS: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: C_COMMENTS,
+    nested: false,
+    quotes: &[
+        quote("\"", false, true),
+        quote("'", false, true),
+        quote("`", true, true),
+    ],
+    rust_literals: false,
+    docstrings: false,
+};
+
+const GO: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: C_COMMENTS,
+    nested: false,
+    quotes: &[
+        quote("\"", false, true),
+        quote("'", false, true),
+        quote("`", true, false),
+    ],
+    rust_literals: false,
+    docstrings: false,
+};
+
+const SWIFT: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: C_COMMENTS,
+    nested: true,
+    quotes: &[quote("\"\"\"", true, true), quote("\"", false, true)],
+    rust_literals: false,
+    docstrings: false,
+};
+
+const PHP: Syntax = Syntax {
+    line_comments: &["//", "#"],
+    block_comments: C_COMMENTS,
+    nested: false,
+    quotes: &[quote("\"", true, true), quote("'", true, true)],
+    rust_literals: false,
+    docstrings: false,
+};
+
+const PYTHON: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    nested: false,
+    quotes: &[
+        quote("\"\"\"", true, true),
+        quote("'''", true, true),
+        quote("\"", false, true),
+        quote("'", false, true),
+    ],
+    rust_literals: false,
+    docstrings: true,
+};
+
+const HASH: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    nested: false,
+    quotes: &[
+        quote("\"\"\"", true, true),
+        quote("'''", true, false),
+        quote("\"", false, true),
+        quote("'", false, false),
+    ],
+    rust_literals: false,
+    docstrings: false,
+};
+
+const SQL: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: C_COMMENTS,
+    nested: false,
+    quotes: &[quote("'", true, false), quote("\"", false, false)],
+    rust_literals: false,
+    docstrings: false,
+};
+
+const LUA: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("--[[", "]]")],
+    nested: false,
+    quotes: &[
+        Quote {
+            open: "[[",
+            close: "]]",
+            multiline: true,
+            escapes: false,
+        },
+        quote("\"", false, true),
+        quote("'", false, true),
+    ],
+    rust_literals: false,
+    docstrings: false,
+};
+
+const HASKELL: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("{-", "-}")],
+    nested: true,
+    quotes: &[quote("\"", false, true)],
+    rust_literals: false,
+    docstrings: false,
+};
+
+const MARKUP: Syntax = Syntax {
+    line_comments: &[],
+    block_comments: &[("<!--", "-->")],
+    nested: false,
+    quotes: &[],
+    rust_literals: false,
+    docstrings: false,
+};
+
+fn syntax(file_type: &str) -> Option<&'static Syntax> {
+    Some(match file_type {
+        "rs" => &RUST,
+        "c" | "h" | "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" | "java" | "cs" | "kt" | "kts"
+        | "scala" | "dart" => &C_LIKE,
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => &JS,
+        "go" => &GO,
+        "swift" => &SWIFT,
+        "php" => &PHP,
+        "py" | "pyi" => &PYTHON,
+        "rb" | "sh" | "bash" | "zsh" | "yaml" | "yml" | "toml" | "r" | "pl" | "ex" | "exs"
+        | "nix" => &HASH,
+        "sql" => &SQL,
+        "lua" => &LUA,
+        "hs" => &HASKELL,
+        "html" | "htm" | "xml" | "md" | "markdown" | "vue" | "svelte" => &MARKUP,
+        _ => return None,
+    })
+}
+
+/// Comment and string regions of a file.
+#[derive(Debug)]
+pub struct Regions {
+    /// Non-code byte ranges `[start, end)`, in order
+    spans: Vec<(usize, usize, Region)>,
+    /// Byte offset of each line's first byte
+    line_starts: Vec<usize>,
+}
+
+impl Regions {
+    /// Region of the byte at `offset`.
+    pub fn at(&self, offset: usize) -> Region {
+        let i = self.spans.partition_point(|&(start, _, _)| start <= offset);
+        match i.checked_sub(1).map(|i| self.spans[i]) {
+            Some((_, end, region)) if offset < end => region,
+            _ => Region::Code,
+        }
+    }
+
+    /// Region of byte `column` on 1-indexed `line`.
+    pub fn at_line(&self, line: usize, column: usize) -> Region {
+        match line.checked_sub(1).and_then(|i| self.line_starts.get(i)) {
+            Some(start) => self.at(start + column),
+            None => Region::Code,
+        }
+    }
+}
+
+/// Lexes `content` with the comment and string syntax of `file_type`.
+pub fn lex(content: &str, file_type: &str) -> Regions {
+    let line_starts = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let spans = syntax(file_type).map_or_else(Vec::new, |s| scan(content.as_bytes(), s));
+    Regions { spans, line_starts }
+}
+
+fn scan(text: &[u8], syntax: &Syntax) -> Vec<(usize, usize, Region)> {
+    let mut spans = Vec::new();
+    let mut line_start = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        if text[i] == b'\n' {
+            line_start = i + 1;
+            i += 1;
+            continue;
+        }
+        let (end, region) = if let Some((open, close)) = syntax
+            .block_comments
+            .iter()
+            .find(|(open, _)| rest.starts_with(open.as_bytes()))
+        {
+            let end = block_end(text, i + open.len(), open, close, syntax.nested);
+            (end, Region::Comment)
+        } else if syntax
+            .line_comments
+            .iter()
+            .any(|c| rest.starts_with(c.as_bytes()))
+        {
+            (line_end(text, i), Region::Comment)
+        } else if let Some(end) = syntax
+            .rust_literals
+            .then(|| rust_literal(text, i))
+            .flatten()
+        {
+            (end, Region::String)
+        } else if let Some(q) = syntax
+            .quotes
+            .iter()
+            .find(|q| rest.starts_with(q.open.as_bytes()))
+        {
+            let end = string_end(text, i + q.open.len(), q);
+            let docstring = syntax.docstrings && q.multiline && {
+                // Only indentation and a string prefix (`r`, `f`, `rb`) before it
+                let before = text[line_start..i].trim_ascii();
+                before.len() <= 2 && before.iter().all(u8::is_ascii_alphabetic)
+            };
+            let region = if docstring {
+                Region::Comment
+            } else {
+                Region::String
+            };
+            (end, region)
+        } else {
+            i += 1;
+            continue;
+        };
+        // Newlines consumed by multi-line regions still start lines
+        if let Some(last) = text[i..end].iter().rposition(|&b| b == b'\n') {
+            line_start = i + last + 1;
+        }
+        spans.push((i, end, region));
+        i = end;
+    }
+    spans
+}
+
+fn line_end(text: &[u8], from: usize) -> usize {
+    text[from..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(text.len(), |p| from + p)
+}
+
+/// End of a block comment whose opener ends at `from`.
+fn block_end(text: &[u8], from: usize, open: &str, close: &str, nested: bool) -> usize {
+    let mut depth = 1;
+    let mut i = from;
+    while i < text.len() {
+        let rest = &text[i..];
+        if rest.starts_with(close.as_bytes()) {
+            depth -= 1;
+            i += close.len();
+            if depth == 0 {
+                return i;
+            }
+        } else if nested && rest.starts_with(open.as_bytes()) {
+            depth += 1;
+            i += open.len();
+        } else {
+            i += 1;
+        }
+    }
+    text.len()
+}
+
+/// End of a string literal whose opener ends at `from`.
+fn string_end(text: &[u8], from: usize, quote: &Quote) -> usize {
+    let mut i = from;
+    while i < text.len() {
+        if quote.escapes && text[i] == b'\\' {
+            i += 2;
+        } else if text[i..].starts_with(quote.close.as_bytes()) {
+            return i + quote.close.len();
+        } else if !quote.multiline && text[i] == b'\n' {
+            return i;
+        } else {
+            i += 1;
+        }
+    }
+    text.len()
+}
+
+/// End of a Rust raw string (`r"..."`, `br#"..."#`) or char literal
+/// (`'x'`, `b'\n'`) starting at `i`. Lifetimes (`'a`) are code.
+fn rust_literal(text: &[u8], i: usize) -> Option<usize> {
+    if i > 0 && (text[i - 1].is_ascii_alphanumeric() || text[i - 1] == b'_') {
+        return None;
+    }
+    let mut j = i;
+    if text.get(j) == Some(&b'b') {
+        j += 1;
+    }
+    match text.get(j)? {
+        b'r' => {
+            let hashes = text[j + 1..].iter().take_while(|&&b| b == b'#').count();
+            let open = j + 1 + hashes;
+            if text.get(open) != Some(&b'"') {
+                return None;
+            }
+            let close: Vec<u8> = std::iter::once(b'"')
+                .chain(std::iter::repeat_n(b'#', hashes))
+                .collect();
+            let end = text[open + 1..]
+                .windows(close.len())
+                .position(|w| w == close.as_slice())
+                .map_or(text.len(), |p| open + 1 + p + close.len());
+            Some(end)
+        }
+        b'\'' => {
+            let body = j + 1;
+            let len = match text.get(body)? {
+                b'\\' => 2 + text.get(body + 2..)?.iter().position(|&b| b == b'\'')?,
+                // One (possibly multi-byte) char, then the closing quote
+                &b => {
+                    let width = match b {
+                        0x00..=0x7f => 1,
+                        0xc0..=0xdf => 2,
+                        0xe0..=0xef => 3,
+                        _ => 4,
+                    };
+                    if text.get(body + width) != Some(&b'\'') {
+                        return None;
+                    }
+                    width
+                }
+            };
+            Some(body + len + 1)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Region of the first occurrence of `needle`.
+    fn region_of(regions: &Regions, content: &str, needle: &str) -> Region {
+        regions.at(content.find(needle).expect("needle present"))
+    }
+
+    #[test]
+    fn test_rust_regions() {
+        let src = r##"/// Parses the Config.
+fn parse<'a>(s: &'a str) -> Config {
+    /* outer /* nested */ still_comment */
+    let raw = r#"raw "Config" text"#;
+    let c = 'x';
+    log("loading Config"); // trailing Config
+    build(s)
+}
+"##;
+        let regions = lex(src, "rs");
+        assert_eq!(region_of(&regions, src, "Parses"), Region::Comment);
+        assert_eq!(region_of(&regions, src, "str) -> Config"), Region::Code);
+        assert_eq!(region_of(&regions, src, "still_comment"), Region::Comment);
+        assert_eq!(region_of(&regions, src, "raw \"Config"), Region::String);
+        assert_eq!(region_of(&regions, src, "'x'"), Region::String);
+        assert_eq!(region_of(&regions, src, "loading"), Region::String);
+        assert_eq!(region_of(&regions, src, "trailing"), Region::Comment);
+        assert_eq!(region_of(&regions, src, "build"), Region::Code);
+        // Lifetimes are not char literals
+        assert_eq!(region_of(&regions, src, "a str"), Region::Code);
+    }
+
+    #[test]
+    fn test_python_docstrings_and_strings() {
+        let src = "def load():\n    \"\"\"Load the Config\n    from disk.\"\"\"\n    path = 'Config.toml'  # Config file\n    return Config(path)\n";
+        let regions = lex(src, "py");
+        assert_eq!(region_of(&regions, src, "Load"), Region::Comment);
+        assert_eq!(region_of(&regions, src, "from disk"), Region::Comment);
+        assert_eq!(region_of(&regions, src, "Config.toml"), Region::String);
+        assert_eq!(region_of(&regions, src, "Config file"), Region::Comment);
+        assert_eq!(region_of(&regions, src, "Config(path)"), Region::Code);
+        assert_eq!(regions.at_line(5, 11), Region::Code);
+        assert_eq!(regions.at_line(3, 4), Region::Comment);
+    }
+
+    #[test]
+    fn test_js_template_and_unknown_languages() {
+        let src = "const a = `multi\nline ${x}`; // done\nconst b = \"http://x\";\n";
+        let regions = lex(src, "ts");
+        assert_eq!(region_of(&regions, src, "line"), Region::String);
+        assert_eq!(region_of(&regions, src, "done"), Region::Comment);
+        assert_eq!(region_of(&regions, src, "http"), Region::String);
+        assert_eq!(region_of(&regions, src, "const b"), Region::Code);
+
+        let unknown = lex("# not a comment here", "txt");
+        assert_eq!(unknown.at(4), Region::Code);
+    }
+}
@@ -1,16 +1,26 @@
 //! Core services for search, indexing, and file operations.
 
+pub(crate) mod diversify;
 mod fts;
+pub(crate) mod fuzzy;
 mod git_diff;
 pub mod grep;
+mod imports;
 pub mod indexer;
+pub(crate) mod lexer;
 pub(crate) mod ngram;
+pub(crate) mod phrase;
 mod regex_literals;
 mod search;
+pub(crate) mod symbols;
+mod syntax;
 mod trigram;
 
 pub use fts::FtsService;
 pub use grep::{GrepMatch, GrepService};
 pub use indexer::Indexer;
-pub use search::{MatchSnippet, SearchResult as SearchHit, SearchService, SearchSources};
+pub(crate) use search::MAX_SNIPPETS;
+pub use search::{
+    FuzzyCandidate, MatchSnippet, SearchResult as SearchHit, SearchService, SearchSources,
+};
 pub use trigram::TrigramIndex;
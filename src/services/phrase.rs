@@ -0,0 +1,264 @@
+//! Phrase and proximity query syntax.
+//!
+//! Splits a query into `"quoted phrases"`, `NEAR(a b, n)` groups and loose
+//! terms so each backend receives a form it understands: FTS5 gets native
+//! phrase/NEAR syntax, grep gets a whitespace-tolerant literal pattern, and
+//! the trigram index gets the phrase literals for pre-filtering.
+
+/// FTS5's own default distance for `NEAR(...)` groups without an explicit `, n`.
+const DEFAULT_NEAR_DISTANCE: usize = 10;
+
+/// A `NEAR(a b ..., n)` proximity group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearGroup {
+    /// Terms that must appear within `distance` tokens of each other
+    pub terms: Vec<String>,
+    /// Maximum number of tokens between the terms
+    pub distance: usize,
+}
+
+/// A query split into its phrase, proximity and loose-term parts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedQuery {
+    /// Quoted phrases, without the quotes
+    pub phrases: Vec<String>,
+    /// `NEAR(...)` groups
+    pub near: Vec<NearGroup>,
+    /// Words outside any phrase or NEAR group
+    pub terms: Vec<String>,
+}
+
+impl ParsedQuery {
+    /// Returns true if the query uses phrase or NEAR syntax.
+    pub fn has_structure(&self) -> bool {
+        !self.phrases.is_empty() || !self.near.is_empty()
+    }
+
+    /// Returns the single phrase if the query is exactly one quoted phrase.
+    pub fn sole_phrase(&self) -> Option<&str> {
+        match (
+            self.phrases.as_slice(),
+            self.near.is_empty(),
+            self.terms.is_empty(),
+        ) {
+            ([phrase], true, true) => Some(phrase),
+            _ => None,
+        }
+    }
+
+    /// Builds a line-oriented grep pattern for verifying phrase matches.
+    ///
+    /// Phrases become whitespace-tolerant literals; NEAR groups contribute
+    /// their terms as whole words (proximity itself is enforced by FTS5).
+    /// Like FTS5, the pattern ignores case.
+    /// Returns `None` when there is nothing literal to look for.
+    pub fn grep_pattern(&self) -> Option<String> {
+        let mut alternatives: Vec<String> = self
+            .phrases
+            .iter()
+            .filter_map(|p| phrase_words(p))
+            .collect();
+
+        for group in &self.near {
+            alternatives.extend(
+                group
+                    .terms
+                    .iter()
+                    .map(|t| format!(r"\b{}\b", regex::escape(t))),
+            );
+        }
+
+        let pattern = match alternatives.len() {
+            0 => return None,
+            1 => alternatives.pop()?,
+            _ => format!("(?:{})", alternatives.join("|")),
+        };
+        Some(format!("(?i){pattern}"))
+    }
+
+    /// Phrase literals long enough for trigram pre-filtering.
+    ///
+    /// All phrases must be present in a matching file, so callers can
+    /// AND-intersect the bitmaps. Each phrase is split at whitespace since
+    /// the grep pattern tolerates any whitespace run between words.
+    pub fn literals(&self) -> Vec<&str> {
+        self.phrases
+            .iter()
+            .flat_map(|p| p.split_whitespace())
+            .filter(|w| w.len() >= 3)
+            .collect()
+    }
+}
+
+/// Returns true if `query` contains phrase or NEAR syntax worth parsing.
+pub fn has_syntax(query: &str) -> bool {
+    query.contains('"') || query.contains("NEAR(")
+}
+
+/// Parses phrase and NEAR syntax out of a query.
+///
+/// Unterminated quotes and NEAR groups are treated as loose text, so a
+/// stray `"` never turns the whole query into an error.
+pub fn parse(query: &str) -> ParsedQuery {
+    let mut parsed = ParsedQuery::default();
+    let mut loose = String::new();
+    let mut rest = query;
+
+    while !rest.is_empty() {
+        if let Some(after_quote) = rest.strip_prefix('"') {
+            if let Some(end) = after_quote.find('"') {
+                let phrase = after_quote[..end].split_whitespace().collect::<Vec<_>>();
+                if !phrase.is_empty() {
+                    parsed.phrases.push(phrase.join(" "));
+                }
+                rest = &after_quote[end + 1..];
+            } else {
+                loose.push(' ');
+                loose.push_str(after_quote);
+                rest = "";
+            }
+            continue;
+        }
+
+        if rest.starts_with("NEAR(") && loose.chars().last().is_none_or(char::is_whitespace) {
+            if let Some(end) = rest.find(')') {
+                push_near_group(&mut parsed, &rest["NEAR(".len()..end]);
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+
+        let ch = rest.chars().next().unwrap_or(' ');
+        loose.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    parsed
+        .terms
+        .extend(loose.split_whitespace().map(str::to_string));
+    parsed
+}
+
+/// Parses the inside of a `NEAR(...)` group: terms, then an optional `, n`.
+fn push_near_group(parsed: &mut ParsedQuery, inner: &str) {
+    let (terms_part, distance) = match inner.rsplit_once(',') {
+        Some((terms, n)) => match n.trim().parse::<usize>() {
+            Ok(n) => (terms, n),
+            Err(_) => (inner, DEFAULT_NEAR_DISTANCE),
+        },
+        None => (inner, DEFAULT_NEAR_DISTANCE),
+    };
+
+    let terms: Vec<String> = terms_part
+        .split(|c: char| c.is_whitespace() || c == '"' || c == ',')
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    // A single-term NEAR is just a term
+    if terms.len() < 2 {
+        parsed.terms.extend(terms);
+    } else {
+        parsed.near.push(NearGroup { terms, distance });
+    }
+}
+
+/// Builds a grep pattern matching `phrase` literally, ignoring case like
+/// FTS5, with any whitespace run allowed between its words.
+pub fn phrase_pattern(phrase: &str) -> Option<String> {
+    phrase_words(phrase).map(|words| format!("(?i){words}"))
+}
+
+/// The escaped words of `phrase` joined by `\s+`, without flags.
+fn phrase_words(phrase: &str) -> Option<String> {
+    let words: Vec<String> = phrase.split_whitespace().map(regex::escape).collect();
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(r"\s+"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_terms() {
+        let parsed = parse("auth flow");
+        assert!(!parsed.has_structure());
+        assert_eq!(parsed.terms, vec!["auth", "flow"]);
+    }
+
+    #[test]
+    fn test_parse_quoted_segment_inside_query() {
+        let parsed = parse(r#"login "error  handling" retry"#);
+        assert_eq!(parsed.phrases, vec!["error handling"]);
+        assert_eq!(parsed.terms, vec!["login", "retry"]);
+        assert!(parsed.sole_phrase().is_none());
+    }
+
+    #[test]
+    fn test_parse_sole_phrase() {
+        let parsed = parse(r#""exact phrase""#);
+        assert_eq!(parsed.sole_phrase(), Some("exact phrase"));
+    }
+
+    #[test]
+    fn test_parse_unterminated_quote_is_loose() {
+        let parsed = parse(r#"foo "bar baz"#);
+        assert!(parsed.phrases.is_empty());
+        assert_eq!(parsed.terms, vec!["foo", "bar", "baz"]);
+    }
+
+    #[test]
+    fn test_parse_near_group() {
+        let parsed = parse("NEAR(cache invalidate, 5) redis");
+        assert_eq!(
+            parsed.near,
+            vec![NearGroup {
+                terms: vec!["cache".into(), "invalidate".into()],
+                distance: 5,
+            }]
+        );
+        assert_eq!(parsed.terms, vec!["redis"]);
+    }
+
+    #[test]
+    fn test_parse_near_default_distance() {
+        let parsed = parse("NEAR(open close)");
+        assert_eq!(parsed.near[0].distance, DEFAULT_NEAR_DISTANCE);
+    }
+
+    #[test]
+    fn test_parse_near_single_term_is_term() {
+        let parsed = parse("NEAR(alone, 3)");
+        assert!(parsed.near.is_empty());
+        assert_eq!(parsed.terms, vec!["alone"]);
+    }
+
+    #[test]
+    fn test_parse_near_requires_word_boundary() {
+        // "xNEAR(" is not NEAR syntax
+        let parsed = parse("xNEAR(a b)");
+        assert!(parsed.near.is_empty());
+    }
+
+    #[test]
+    fn test_grep_pattern_phrase() {
+        let parsed = parse(r#""fn main()""#);
+        assert_eq!(parsed.grep_pattern().unwrap(), r"(?i)fn\s+main\(\)");
+    }
+
+    #[test]
+    fn test_grep_pattern_multiple_parts() {
+        let parsed = parse(r#""a b" NEAR(x y, 2)"#);
+        assert_eq!(parsed.grep_pattern().unwrap(), r"(?i)(?:a\s+b|\bx\b|\by\b)");
+    }
+
+    #[test]
+    fn test_literals_skip_short_words() {
+        let parsed = parse(r#""fn search_files""#);
+        assert_eq!(parsed.literals(), vec!["search_files"]);
+    }
+}
@@ -5,7 +5,9 @@
 
 use crate::db::Database;
 use crate::error::{DbResult, SearchError};
-use crate::services::grep::GrepMatch;
+use crate::services::fuzzy;
+use crate::services::grep::{FileMatches, GrepMatch};
+use crate::services::phrase::{self, ParsedQuery};
 use crate::services::{FtsService, GrepService, TrigramIndex};
 use crate::types::{FileId, Score};
 use std::collections::{HashMap, HashSet};
@@ -25,6 +27,8 @@ pub enum QueryIntent {
     ExactSymbol,
     /// Short token < 4 chars (e.g., "fn", "if")
     ShortToken,
+    /// Quoted phrase or `NEAR(...)` group (e.g., `"error handling"`)
+    Phrase,
 }
 
 /// Classifies a query to determine optimal backend weights.
@@ -68,10 +72,58 @@ pub struct MatchSnippet {
     pub line_number: u64,
     /// The content of the matching line (trimmed)
     pub line_content: String,
-    /// Byte offset within the line where the match starts
-    pub match_start: usize,
-    /// Byte offset within the line where the match ends
-    pub match_end: usize,
+    /// Byte ranges `(start, end)` of every match within `line_content`
+    pub spans: Vec<(usize, usize)>,
+}
+
+/// Maximum snippets kept per result.
+pub(crate) const MAX_SNIPPETS: usize = 3;
+
+/// Snippet lines longer than this are clipped to a window around the
+/// first match (~50 tokens), keeping minified or generated lines cheap.
+const SNIPPET_MAX_BYTES: usize = 200;
+
+/// Bytes of leading context kept before the first match when clipping.
+const SNIPPET_LEAD_BYTES: usize = 40;
+
+/// Marker prepended/appended where a snippet line was clipped.
+const CLIP_MARKER: &str = "…";
+
+impl MatchSnippet {
+    /// Clips an over-long line to `SNIPPET_MAX_BYTES` around its first
+    /// match, dropping spans that fall outside the window and shifting the
+    /// rest to the clipped text.
+    pub(crate) fn clipped(mut self) -> Self {
+        if self.line_content.len() <= SNIPPET_MAX_BYTES {
+            return self;
+        }
+
+        let line = &self.line_content;
+        let anchor = self.spans.first().map_or(0, |&(start, _)| start);
+        let start = floor_char_boundary(line, anchor.saturating_sub(SNIPPET_LEAD_BYTES));
+        let end = floor_char_boundary(line, (start + SNIPPET_MAX_BYTES).min(line.len()));
+
+        let prefix = if start > 0 { CLIP_MARKER } else { "" };
+        let suffix = if end < line.len() { CLIP_MARKER } else { "" };
+        let text = format!("{prefix}{}{suffix}", &line[start..end]);
+
+        self.spans = self
+            .spans
+            .iter()
+            .filter(|&&(s, e)| s >= start && e <= end)
+            .map(|&(s, e)| (s - start + prefix.len(), e - start + prefix.len()))
+            .collect();
+        self.line_content = text;
+        self
+    }
+}
+
+/// Largest char boundary in `s` at or below `index`.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    (0..=index.min(s.len()))
+        .rev()
+        .find(|&i| s.is_char_boundary(i))
+        .unwrap_or(0)
 }
 
 /// A search result with merged scores.
@@ -82,8 +134,12 @@ pub struct SearchResult {
     pub score: Score,
     /// Which search methods contributed to this result
     pub sources: SearchSources,
-    /// Top matching snippets from this file (up to 3)
+    /// Top matching snippets from this file (up to 3), from grep or,
+    /// for FTS-only hits, from FTS5 `highlight()`
     pub snippets: Vec<MatchSnippet>,
+    /// Matching lines in this file (0 when unknown). Bounded by the
+    /// search's match budget, so treat large counts as lower bounds.
+    pub match_count: usize,
 }
 
 /// Tracks which search methods found a result.
@@ -116,6 +172,23 @@ impl SearchSources {
     }
 }
 
+/// An identifier within a small edit distance of a fuzzy query.
+#[derive(Debug, Clone)]
+pub struct FuzzyCandidate {
+    /// The identifier as written in the source
+    pub identifier: String,
+    /// Case-insensitive edit distance from the query (0 = same name)
+    pub distance: usize,
+    /// First occurrence in each file containing the identifier
+    pub occurrences: Vec<(PathBuf, MatchSnippet)>,
+}
+
+/// Files scanned for identifiers per fuzzy source (n-gram overlap, vocabulary).
+const FUZZY_MAX_FILES: usize = 32;
+
+/// Closest FTS vocabulary tokens whose files are scanned.
+const FUZZY_MAX_VOCAB_TERMS: usize = 8;
+
 /// Tokens shorter than this are classified as `ShortToken` (low selectivity).
 const SHORT_TOKEN_MAX_LEN: usize = 4;
 
@@ -336,6 +409,12 @@ impl SearchService {
     /// - Natural language queries favor FTS
     /// - Short/exact tokens use balanced weights
     ///
+    /// Queries containing `"quoted phrases"` or `NEAR(a b, n)` groups are
+    /// routed as phrase queries: FTS5 gets the native syntax, and grep
+    /// verifies the phrases within the FTS candidate files instead of
+    /// treating the quoted string as a regex. FTS hits that grep cannot
+    /// confirm (e.g. `login(); validate()` for `"login validate"`) are dropped.
+    ///
     /// This is a blocking operation - use `spawn_blocking` in async contexts.
     ///
     /// # Errors
@@ -347,16 +426,28 @@ impl SearchService {
         } else {
             DEFAULT_SEARCH_LIMIT
         };
-        let intent = classify_query(query);
+        let parsed = if phrase::has_syntax(query) {
+            phrase::parse(query)
+        } else {
+            ParsedQuery::default()
+        };
+        let intent = if parsed.has_structure() {
+            QueryIntent::Phrase
+        } else {
+            classify_query(query)
+        };
+        let overcollect = (limit * 5 / 4).max(limit + 1);
 
         // Run searches based on intent
         // For regex queries, skip FTS (it can't handle regex)
-        let fts_results = if intent != QueryIntent::Regex {
-            self.fts
-                .search(query, (limit * 5 / 4).max(limit + 1))
-                .unwrap_or_default()
-        } else {
-            Vec::new()
+        let fts_results = match intent {
+            QueryIntent::Regex => Vec::new(),
+            QueryIntent::Phrase => match parsed.sole_phrase() {
+                Some(p) => self.fts.search_phrase(p, overcollect),
+                None => self.fts.search(query, overcollect),
+            }
+            .unwrap_or_default(),
+            _ => self.fts.search(query, overcollect).unwrap_or_default(),
         };
 
         // Phase 3: Run trigram BEFORE grep to build a file filter.
@@ -364,43 +455,52 @@ impl SearchService {
         // to a path set and restrict grep to only those files.
         let trigram_results = {
             let trigram = self.trigram.read().unwrap_or_else(|e| e.into_inner());
-            if intent == QueryIntent::Regex {
-                // Extract literal segments from regex pattern for smarter trigram filtering
-                let literals = super::regex_literals::extract_literals(query);
-                if literals.is_empty() {
-                    None
-                } else {
-                    // AND-intersect bitmaps from all literals
-                    let mut combined: Option<roaring::RoaringBitmap> = None;
-                    for literal in &literals {
-                        if let Some(bitmap) = trigram.search(literal) {
-                            combined = Some(match combined {
-                                Some(existing) => existing & bitmap,
-                                None => bitmap,
-                            });
-                            // Early exit if intersection is already empty
-                            if combined.as_ref().is_some_and(|b| b.is_empty()) {
-                                break;
-                            }
-                        }
-                    }
-                    combined
+            match intent {
+                QueryIntent::Regex => {
+                    // Extract literal segments from regex pattern for smarter trigram filtering
+                    let literals = super::regex_literals::extract_literals(query);
+                    intersect_literals(&trigram, literals.iter().map(String::as_str))
                 }
-            } else {
-                trigram.search(query)
+                QueryIntent::Phrase => intersect_literals(&trigram, parsed.literals()),
+                _ => trigram.search(query),
             }
         };
 
-        let file_filter = self.build_trigram_filter(&trigram_results);
+        let mut file_filter = self.build_trigram_filter(&trigram_results);
 
-        let (grep_results, grep_matches) = self
-            .grep
-            .search_files_with_matches_filtered(
-                query,
-                (limit * 5 / 4).max(limit + 1),
-                file_filter.as_ref(),
-            )
-            .unwrap_or_default();
+        // Phrase queries: grep verifies phrases inside FTS candidates only.
+        // When FTS finds nothing (e.g. punctuation-only phrases the tokenizer
+        // drops), grep runs unrestricted so the literal can still be found.
+        // The trigram index is case-sensitive while the phrase check is not,
+        // so it never narrows the files here.
+        let grep_pattern = if intent == QueryIntent::Phrase {
+            file_filter = (!fts_results.is_empty()).then(|| self.fts_candidate_paths(&fts_results));
+            parsed.grep_pattern()
+        } else {
+            Some(query.to_string())
+        };
+
+        let (grep_results, grep_matches) = match grep_pattern {
+            Some(pattern) => self
+                .grep
+                .search_files_with_matches_filtered(&pattern, overcollect, file_filter.as_ref())
+                .unwrap_or_default(),
+            None => Default::default(),
+        };
+
+        // Phrase verification: FTS ignores punctuation between tokens, so
+        // keep only FTS hits whose literal phrase grep actually found.
+        let mut fts_results = fts_results;
+        if intent == QueryIntent::Phrase && !fts_results.is_empty() {
+            let confirmed: HashSet<&Path> = grep_results.iter().map(|(p, _)| p.as_path()).collect();
+            let ids: Vec<FileId> = fts_results.iter().map(|(id, _)| *id).collect();
+            let paths = self.get_paths_cached(&ids);
+            fts_results.retain(|(id, _)| {
+                paths
+                    .get(id)
+                    .is_some_and(|p| confirmed.contains(Path::new(&**p)))
+            });
+        }
 
         // Override weights based on intent.
         // Common case (ExactSymbol/ShortToken ~80% of queries) borrows self.config directly.
@@ -425,17 +525,23 @@ impl SearchService {
                 };
                 &override_config
             }
-            QueryIntent::ExactSymbol | QueryIntent::ShortToken => &self.config,
+            QueryIntent::ExactSymbol | QueryIntent::ShortToken | QueryIntent::Phrase => {
+                &self.config
+            }
         };
 
-        self.merge_results(
+        let mut results = self.merge_results(
             fts_results,
             grep_results,
             grep_matches,
             trigram_results,
             limit,
             config_ref,
-        )
+        )?;
+        if intent != QueryIntent::Regex {
+            self.fill_fts_snippets(&mut results, query);
+        }
+        Ok(results)
     }
 
     /// Performs FTS-only search.
@@ -445,13 +551,40 @@ impl SearchService {
     /// Returns `DbError` if the FTS database query fails.
     pub fn search_fts(&self, query: &str, limit: usize) -> DbResult<Vec<SearchResult>> {
         let results = self.fts.search(query, limit)?;
-        Ok(self.enrich_results(
+        let mut results = self.enrich_results(
             results,
             SearchSources {
                 fts: true,
                 ..Default::default()
             },
-        ))
+        );
+        self.fill_fts_snippets(&mut results, query);
+        Ok(results)
+    }
+
+    /// Performs an exact phrase search.
+    ///
+    /// The query is treated as one phrase unless it already contains quoted
+    /// segments or `NEAR(...)` groups, in which case it is used as written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError` if result merging or database access fails.
+    pub fn search_phrase(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, SearchError> {
+        let trimmed = query.trim();
+        if phrase::has_syntax(trimmed) && phrase::parse(trimmed).has_structure() {
+            return self.search(trimmed, limit);
+        }
+
+        let words = trimmed.replace('"', " ");
+        if words.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        self.search(&format!("\"{words}\""), limit)
     }
 
     /// Performs grep-only search.
@@ -460,7 +593,7 @@ impl SearchService {
     ///
     /// Returns `SearchError::InvalidPattern` if the regex pattern is invalid.
     pub fn search_grep(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>, SearchError> {
-        let results = self.grep.search_files(query, limit)?;
+        let (results, counts) = self.grep.search_files_with_matches(query, limit)?;
 
         // Batch resolve paths to file IDs via cache
         let path_strings: Vec<String> = results
@@ -474,6 +607,7 @@ impl SearchService {
             .map(|(path, score)| {
                 let path_str = path.to_string_lossy().to_string();
                 let file_id = id_map.get(&path_str).copied().unwrap_or(FileId::new(0));
+                let match_count = counts.get(path.as_path()).map_or(0, |m| m.count);
 
                 SearchResult {
                     file_id,
@@ -484,6 +618,7 @@ impl SearchService {
                         ..Default::default()
                     },
                     snippets: Vec::new(),
+                    match_count,
                 }
             })
             .collect();
@@ -505,7 +640,201 @@ impl SearchService {
         limit: usize,
     ) -> Result<HashMap<Arc<Path>, Vec<GrepMatch>>, SearchError> {
         let (_, matches) = self.grep.search_files_with_matches(query, limit)?;
-        Ok(matches)
+        Ok(matches
+            .into_iter()
+            .map(|(path, file)| (path, file.matches))
+            .collect())
+    }
+
+    /// Like `search_grep_with_matches`, for a pattern matching any of
+    /// `literals` (an alternation). Files are pre-filtered to the union of
+    /// each literal's trigram candidates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::InvalidPattern` if the regex pattern is invalid.
+    pub fn search_grep_any_with_matches(
+        &self,
+        pattern: &str,
+        literals: &[&str],
+        limit: usize,
+    ) -> Result<HashMap<Arc<Path>, Vec<GrepMatch>>, SearchError> {
+        let trigram_results = {
+            let trigram = self.trigram.read().unwrap_or_else(|e| e.into_inner());
+            union_literals(&trigram, literals.iter().copied())
+        };
+        let file_filter = self.build_trigram_filter(&trigram_results);
+        let (_, matches) =
+            self.grep
+                .search_files_with_matches_filtered(pattern, limit, file_filter.as_ref())?;
+        Ok(matches
+            .into_iter()
+            .map(|(path, file)| (path, file.matches))
+            .collect())
+    }
+
+    /// Finds identifiers within a small edit distance of the query's
+    /// longest identifier ("did you mean").
+    ///
+    /// Candidate files come from two sources: files sharing most of the
+    /// name's 3-byte n-grams (a typo breaks at most three per edit), and
+    /// files containing FTS vocabulary tokens close to the lowercased name
+    /// (catches case slips the case-sensitive n-grams miss). Those files'
+    /// identifiers are then compared by edit distance. Returns up to
+    /// `limit` candidates, closest and most widespread first.
+    pub fn fuzzy_candidates(&self, query: &str, limit: usize) -> Vec<FuzzyCandidate> {
+        let Some(target) = fuzzy::target_identifier(query) else {
+            return Vec::new();
+        };
+        let max = fuzzy::max_distance(target.len());
+
+        // Source 1: n-gram overlap
+        let windows = target.len().saturating_sub(2);
+        let min_shared = windows.saturating_sub(3 * max).max(1);
+        let mut overlap: Vec<(u32, usize)> = {
+            let trigram = self.trigram.read().unwrap_or_else(|e| e.into_inner());
+            trigram
+                .shared_trigram_counts(target)
+                .into_iter()
+                .filter(|&(_, n)| n >= min_shared)
+                .collect()
+        };
+        overlap.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let mut file_ids: Vec<FileId> = overlap
+            .into_iter()
+            .take(FUZZY_MAX_FILES)
+            .map(|(id, _)| FileId::new(id))
+            .collect();
+
+        // Source 2: FTS vocabulary. Tokens are lowercased, split at `_` and
+        // stemmed, so match the target word by word and allow an extra edit
+        // for stripped suffixes.
+        let mut close: Vec<(usize, String)> = Vec::new();
+        for word in fuzzy::words(target).into_iter().filter(|w| w.len() >= 3) {
+            let word_max = fuzzy::max_distance(word.len()) + 1;
+            let vocab = self
+                .db
+                .vocab_terms(word.len().saturating_sub(word_max), word.len() + word_max)
+                .unwrap_or_default();
+            close.extend(
+                vocab
+                    .into_iter()
+                    .filter_map(|term| fuzzy::distance(&word, &term, word_max).map(|d| (d, term))),
+            );
+        }
+        close.sort_unstable();
+        close.dedup_by(|a, b| a.1 == b.1);
+        let mut vocab_files = 0;
+        for (_, term) in close.into_iter().take(FUZZY_MAX_VOCAB_TERMS) {
+            for id in self
+                .db
+                .files_with_term(&term, FUZZY_MAX_FILES)
+                .unwrap_or_default()
+            {
+                if vocab_files < FUZZY_MAX_FILES && !file_ids.contains(&id) {
+                    file_ids.push(id);
+                    vocab_files += 1;
+                }
+            }
+        }
+
+        // Scan candidate files' identifiers
+        let mut distances: HashMap<String, Option<usize>> = HashMap::new();
+        let mut found: HashMap<String, (usize, Vec<(PathBuf, MatchSnippet)>)> = HashMap::new();
+        for id in file_ids {
+            let Ok(Some((path, content))) = self.db.get_file(id) else {
+                continue;
+            };
+            let mut seen_in_file: HashSet<&str> = HashSet::new();
+            for (line_number, offset, ident) in fuzzy::identifiers(&content) {
+                if ident.len().abs_diff(target.len()) > max || !seen_in_file.insert(ident) {
+                    continue;
+                }
+                let d = *distances
+                    .entry(ident.to_string())
+                    .or_insert_with(|| fuzzy::distance(target, ident, max));
+                let Some(d) = d else { continue };
+
+                let line = content
+                    .lines()
+                    .nth(line_number as usize - 1)
+                    .unwrap_or_default()
+                    .trim_end();
+                let snippet = MatchSnippet {
+                    line_number,
+                    line_content: line.to_string(),
+                    spans: vec![(offset, offset + ident.len())],
+                }
+                .clipped();
+                found
+                    .entry(ident.to_string())
+                    .or_insert_with(|| (d, Vec::new()))
+                    .1
+                    .push((PathBuf::from(&path), snippet));
+            }
+        }
+
+        let mut candidates: Vec<FuzzyCandidate> = found
+            .into_iter()
+            .map(|(identifier, (distance, mut occurrences))| {
+                occurrences.sort_by(|a, b| a.0.cmp(&b.0));
+                FuzzyCandidate {
+                    identifier,
+                    distance,
+                    occurrences,
+                }
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then_with(|| b.occurrences.len().cmp(&a.occurrences.len()))
+                .then_with(|| a.identifier.cmp(&b.identifier))
+        });
+        candidates.truncate(limit);
+        candidates
+    }
+
+    /// Counts matching lines per file for a regex, for aggregation.
+    ///
+    /// Unlike ranked search, this walks until `budget` matches are found
+    /// (not `limit` files), so per-file counts are exact unless the budget
+    /// runs out. Returns the counts and whether the budget was exhausted.
+    /// Literal segments of the pattern drive the same trigram pre-filter
+    /// as regex search.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::InvalidPattern` if the regex pattern is invalid.
+    pub fn count_matches(
+        &self,
+        pattern: &str,
+        budget: usize,
+    ) -> Result<(Vec<(PathBuf, usize)>, bool), SearchError> {
+        let trigram_results = {
+            let trigram = self.trigram.read().unwrap_or_else(|e| e.into_inner());
+            let literals = super::regex_literals::extract_literals(pattern);
+            intersect_literals(&trigram, literals.iter().map(String::as_str))
+        };
+        let file_filter = self.build_trigram_filter(&trigram_results);
+
+        let matches = self
+            .grep
+            .search_parallel_filtered(pattern, budget, file_filter.as_ref())?;
+        let exhausted = matches.len() >= budget;
+
+        let mut counts: HashMap<Arc<Path>, usize> = HashMap::new();
+        for m in matches {
+            *counts.entry(m.path).or_insert(0) += 1;
+        }
+
+        Ok((
+            counts
+                .into_iter()
+                .map(|(path, count)| (path.to_path_buf(), count))
+                .collect(),
+            exhausted,
+        ))
     }
 
     /// Gets the trigram index for modifications.
@@ -568,6 +897,15 @@ impl SearchService {
         }
     }
 
+    /// Resolves FTS hits to a path set usable as a grep file filter.
+    fn fts_candidate_paths(&self, fts: &[(FileId, Score)]) -> HashSet<Arc<Path>> {
+        let ids: Vec<FileId> = fts.iter().map(|(id, _)| *id).collect();
+        self.get_paths_cached(&ids)
+            .into_values()
+            .map(|s| Arc::from(Path::new(&*s)))
+            .collect()
+    }
+
     /// Merges results from multiple search methods.
     ///
     /// Performance optimizations:
@@ -583,7 +921,7 @@ impl SearchService {
         &self,
         fts: Vec<(FileId, Score)>,
         grep: Vec<(PathBuf, Score)>,
-        grep_matches: HashMap<Arc<Path>, Vec<GrepMatch>>,
+        grep_matches: HashMap<Arc<Path>, FileMatches>,
         trigram: Option<roaring::RoaringBitmap>,
         limit: usize,
         config: &SearchConfig,
@@ -700,6 +1038,7 @@ impl SearchService {
                     score: Score::new(base_score * bonus_mult),
                     sources,
                     snippets: Vec::new(), // Populated below for top-N only
+                    match_count: 0,
                 }
             })
             .collect();
@@ -716,21 +1055,25 @@ impl SearchService {
         // Lazy snippet extraction (1B): only for surviving top-N results.
         // Deduplicate consecutive same-line matches (cheap for N≤3).
         for result in &mut results {
-            if let Some(matches) = grep_matches.get(result.path.as_path()) {
+            if let Some(file) = grep_matches.get(result.path.as_path()) {
                 let mut last_line = None;
-                result.snippets = matches
+                result.match_count = file.count;
+                result.snippets = file
+                    .matches
                     .iter()
                     .filter(|m| {
                         let dominated = last_line == Some(m.line_number);
                         last_line = Some(m.line_number);
                         !dominated
                     })
-                    .take(3)
-                    .map(|m| MatchSnippet {
-                        line_number: m.line_number,
-                        line_content: m.line_content.clone(),
-                        match_start: m.match_start,
-                        match_end: m.match_end,
+                    .take(MAX_SNIPPETS)
+                    .map(|m| {
+                        MatchSnippet {
+                            line_number: m.line_number,
+                            line_content: m.line_content.clone(),
+                            spans: m.spans.clone(),
+                        }
+                        .clipped()
                     })
                     .collect();
             }
@@ -773,6 +1116,31 @@ impl SearchService {
         Ok(results)
     }
 
+    /// Gives FTS hits without grep snippets line snippets (and a count of
+    /// highlighted lines) from FTS5 `highlight()`.
+    ///
+    /// Best-effort: a failed lookup leaves the results without snippets.
+    fn fill_fts_snippets(&self, results: &mut [SearchResult], query: &str) {
+        let ids: Vec<FileId> = results
+            .iter()
+            .filter(|r| r.sources.fts && r.snippets.is_empty())
+            .map(|r| r.file_id)
+            .collect();
+        if ids.is_empty() {
+            return;
+        }
+
+        let Ok(mut by_id) = self.fts.snippets(query, &ids, MAX_SNIPPETS) else {
+            return;
+        };
+        for result in results.iter_mut().filter(|r| r.snippets.is_empty()) {
+            if let Some((count, snippets)) = by_id.remove(&result.file_id) {
+                result.snippets = snippets.into_iter().map(MatchSnippet::clipped).collect();
+                result.match_count = count;
+            }
+        }
+    }
+
     /// Enriches file IDs with paths using the path cache.
     fn enrich_results(
         &self,
@@ -791,6 +1159,7 @@ impl SearchService {
                     score,
                     sources,
                     snippets: Vec::new(),
+                    match_count: 0,
                 });
             }
         }
@@ -799,6 +1168,43 @@ impl SearchService {
     }
 }
 
+/// AND-intersects the trigram bitmaps of every literal.
+///
+/// Literals too short for n-gram lookup are skipped. Returns `None` when
+/// no literal produced a bitmap.
+fn intersect_literals<'a>(
+    trigram: &TrigramIndex,
+    literals: impl IntoIterator<Item = &'a str>,
+) -> Option<roaring::RoaringBitmap> {
+    let mut combined: Option<roaring::RoaringBitmap> = None;
+    for literal in literals {
+        if let Some(bitmap) = trigram.search(literal) {
+            combined = Some(match combined {
+                Some(existing) => existing & bitmap,
+                None => bitmap,
+            });
+            // Early exit if intersection is already empty
+            if combined.as_ref().is_some_and(|b| b.is_empty()) {
+                break;
+            }
+        }
+    }
+    combined
+}
+
+/// Files that may contain any of `literals`. `None` (no filter) when one
+/// is too short to look up, since any file could then contain it.
+fn union_literals<'a>(
+    trigram: &TrigramIndex,
+    literals: impl IntoIterator<Item = &'a str>,
+) -> Option<roaring::RoaringBitmap> {
+    let mut combined = roaring::RoaringBitmap::new();
+    for literal in literals {
+        combined |= trigram.search(literal)?;
+    }
+    Some(combined)
+}
+
 // Compile-time assertions for thread safety.
 // These ensure Send+Sync remain implemented and catch regressions.
 #[cfg(test)]
@@ -948,6 +1354,72 @@ mod tests {
     // Integration tests
     // ========================================================================
 
+    #[test]
+    fn test_phrase_query_does_not_grep_quotes() {
+        let (dir, db, service) = setup_multi_file_env();
+        fs::write(dir.path().join("notes.rs"), "// logging in again later").unwrap();
+        db.upsert_file(
+            dir.path().join("notes.rs").to_string_lossy().as_ref(),
+            "// logging in again later",
+            0x4,
+        )
+        .unwrap();
+        service.refresh_total_files();
+
+        let results = service.search("\"logging in\"", 10).unwrap();
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert!(result.sources.fts && result.sources.grep);
+            // Snippets come from the phrase pattern, not the quoted regex
+            assert!(result.snippets[0].line_content.contains("logging in"));
+        }
+    }
+
+    #[test]
+    fn test_phrase_mode_requires_adjacency() {
+        let (_dir, _db, service) = setup_multi_file_env();
+
+        // auth.rs has "login(); validate();" — adjacent tokens for FTS,
+        // but not the literal phrase, so grep verification drops it
+        let results = service.search_phrase("login validate", 10).unwrap();
+        assert!(results.is_empty());
+
+        let results = service.search_phrase("fn login", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].path.ends_with("login.rs"));
+    }
+
+    #[test]
+    fn test_phrase_mode_ignores_case_like_fts() {
+        let (dir, db, service) = setup_multi_file_env();
+        for (name, content, hash) in [
+            ("notes.md", "Error handling matters.", 0x5),
+            ("strings.rs", "let s = \"error handling\";", 0x6),
+        ] {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            db.upsert_file(path.to_string_lossy().as_ref(), content, hash)
+                .unwrap();
+        }
+        service.refresh_total_files();
+
+        for query in ["error handling", "Error Handling"] {
+            let results = service.search_phrase(query, 10).unwrap();
+            assert_eq!(results.len(), 2, "{query}");
+            assert!(results.iter().all(|r| r.sources.fts && r.sources.grep));
+        }
+    }
+
+    #[test]
+    fn test_phrase_mode_punctuation_falls_back_to_grep() {
+        let (_dir, _db, service) = setup_multi_file_env();
+
+        // FTS drops "();" entirely; grep still finds the literal
+        let results = service.search_phrase("();", 10).unwrap();
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.sources.grep && !r.sources.fts));
+    }
+
     #[test]
     fn test_combined_search() {
         let (dir, db) = setup_test_env();
@@ -972,6 +1444,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_fts_only_has_snippets() {
+        let (_dir, _db, service) = setup_multi_file_env();
+
+        let results = service.search_fts("authenticate", 10).unwrap();
+        let snippet = &results[0].snippets[0];
+        assert_eq!(snippet.line_number, 1);
+        let (start, end) = snippet.spans[0];
+        assert_eq!(&snippet.line_content[start..end], "authenticate");
+    }
+
+    #[test]
+    fn test_combined_search_records_all_spans() {
+        let (_dir, _db, service) = setup_multi_file_env();
+
+        let results = service.search("login", 10).unwrap();
+        let auth = results
+            .iter()
+            .find(|r| r.path.ends_with("auth.rs"))
+            .unwrap();
+        assert_eq!(auth.snippets[0].spans.len(), 1);
+
+        let results = service.search("in", 10).unwrap();
+        let login = results
+            .iter()
+            .find(|r| r.path.ends_with("login.rs"))
+            .unwrap();
+        // "login", "println", "logging", "in"
+        assert_eq!(login.snippets[0].spans.len(), 4);
+    }
+
+    #[test]
+    fn test_snippet_clipped_around_first_match() {
+        let line = format!("{}needle{}", "a".repeat(300), "b".repeat(300));
+        let snippet = MatchSnippet {
+            line_number: 1,
+            line_content: line,
+            spans: vec![(300, 306), (0, 1)],
+        }
+        .clipped();
+
+        assert!(snippet.line_content.starts_with(CLIP_MARKER));
+        assert!(snippet.line_content.ends_with(CLIP_MARKER));
+        assert!(snippet.line_content.len() <= SNIPPET_MAX_BYTES + 2 * CLIP_MARKER.len());
+        // Out-of-window span dropped, in-window span shifted
+        assert_eq!(snippet.spans.len(), 1);
+        let (start, end) = snippet.spans[0];
+        assert_eq!(&snippet.line_content[start..end], "needle");
+    }
+
+    #[test]
+    fn test_snippet_clip_respects_char_boundaries() {
+        let line = format!("{}x", "é".repeat(200));
+        let snippet = MatchSnippet {
+            line_number: 1,
+            line_content: line.clone(),
+            spans: vec![(line.len() - 1, line.len())],
+        }
+        .clipped();
+        let (start, end) = snippet.spans[0];
+        assert_eq!(&snippet.line_content[start..end], "x");
+    }
+
     #[test]
     fn test_search_grep_only() {
         let (_dir, _db, service) = setup_multi_file_env();
@@ -1135,6 +1670,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_union_literals() {
+        let mut index = TrigramIndex::new();
+        index.add_file(FileId::new(1), "fn authenticate() {}");
+        index.add_file(FileId::new(2), "fn validate() {}");
+        index.add_file(FileId::new(3), "struct Config;");
+
+        let union = union_literals(&index, ["authenticate", "validate"]).unwrap();
+        assert_eq!(union.iter().collect::<Vec<_>>(), [1, 2]);
+        // A literal too short to look up could be in any file
+        assert!(union_literals(&index, ["authenticate", "id"]).is_none());
+    }
+
+    #[test]
+    fn test_grep_any_with_matches() {
+        let (_dir, _db, service) = setup_multi_file_env();
+
+        let matches = service
+            .search_grep_any_with_matches(r"\b(?:login|Config)\b", &["login", "Config"], 100)
+            .unwrap();
+        let mut names: Vec<String> = matches
+            .keys()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["auth.rs", "config.rs", "login.rs"]);
+    }
+
     #[test]
     fn test_db_access() {
         let (_dir, db, service) = setup_multi_file_env();
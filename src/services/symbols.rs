@@ -0,0 +1,1168 @@
+//! Symbol extraction for outlines and the symbol index.
+//!
+//! Languages with a compiled-in grammar are parsed with tree-sitter (see
+//! `syntax`). Otherwise line heuristics apply: each declaration line becomes
+//! a [`Symbol`], and its extent is found by brace matching (Rust, Go, JS/TS,
+//! Kotlin) or indentation (Python). The same extraction backs the `outline` tool
+//! and the `symbols` table written at index time.
+
+use crate::db::SymbolData;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::Path;
+
+/// A symbol extracted from the file.
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct Symbol {
+    /// Symbol name
+    pub name: String,
+    /// Symbol kind (fn, class, struct, etc.)
+    pub kind: String,
+    /// Start line number
+    pub line: usize,
+    /// End line number (if detectable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    /// Nesting depth (0 = top level)
+    #[serde(skip_serializing_if = "is_zero")]
+    pub level: usize,
+    /// Declaration up to its body, whitespace collapsed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Declared or conventional visibility (`pub(crate)`, `private`, `export`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    /// First line of the doc comment or docstring
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// Name of the enclosing symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+}
+
+/// Lines scanned for the end of a multi-line signature.
+const SIGNATURE_MAX_LINES: usize = 6;
+
+/// Signatures longer than this are clipped.
+const SIGNATURE_MAX_CHARS: usize = 300;
+
+const fn is_zero(v: &usize) -> bool {
+    *v == 0
+}
+
+/// Returns the lowercased extension used to pick a symbol extractor,
+/// or `"unknown"`.
+pub fn detect_file_type(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Extracts top-level and nested declarations from `content`, in line order.
+///
+/// `file_type` is an extension as returned by [`detect_file_type`];
+/// unsupported types yield no symbols.
+pub fn extract_symbols(content: &str, file_type: &str) -> Vec<Symbol> {
+    super::syntax::extract(content, file_type)
+        .unwrap_or_else(|| heuristic_symbols(content, file_type))
+}
+
+/// Whether [`extract_symbols`] can find anything in `file_type`.
+pub fn has_extractor(file_type: &str) -> bool {
+    super::syntax::supports(file_type) || matches!(file_type, "kt" | "kts")
+}
+
+/// Line-based fallback for [`extract_symbols`] when no syntax tree is
+/// available.
+fn heuristic_symbols(content: &str, file_type: &str) -> Vec<Symbol> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut symbols = Vec::new();
+
+    for (line_num, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        let indent = line.len() - line.trim_start().len();
+
+        let symbol = match file_type {
+            "rs" | "go" | "js" | "ts" | "jsx" | "tsx" => {
+                let raw = match file_type {
+                    "rs" => extract_rust_symbol(trimmed, line_num + 1),
+                    "go" => extract_go_symbol(trimmed, line_num + 1),
+                    _ => extract_js_symbol(trimmed, line_num + 1),
+                };
+                // Compute end_line via brace tracking for brace-delimited languages
+                raw.map(|mut s| {
+                    s.end_line = find_brace_end(&lines, line_num);
+                    s
+                })
+            }
+            "kt" | "kts" => extract_kotlin_symbol(trimmed, line_num + 1).map(|mut s| {
+                // Expression bodies and body-less declarations end on their own
+                // line; Kotlin has no `;` for find_brace_end to stop at.
+                let opens = trimmed.matches('(').count();
+                s.end_line = if !trimmed.contains('{') && opens == trimmed.matches(')').count() {
+                    Some(line_num + 1)
+                } else {
+                    find_brace_end(&lines, line_num)
+                };
+                s
+            }),
+            "py" => {
+                let raw = extract_python_symbol(trimmed, line_num + 1);
+                // Compute end_line via indent tracking for Python
+                raw.map(|mut s| {
+                    s.end_line = find_indent_end(&lines, line_num, indent);
+                    s
+                })
+            }
+            _ => None,
+        };
+
+        if let Some(s) = symbol {
+            symbols.push(s);
+        }
+    }
+
+    assign_range_levels(&mut symbols);
+    symbols
+}
+
+/// Sets each symbol's `level` from the line ranges enclosing it, so any
+/// indentation width (tabs, 2 or 4 spaces) nests correctly.
+fn assign_range_levels(symbols: &mut [Symbol]) {
+    let mut stack: Vec<(usize, usize)> = Vec::new(); // (end_line, level)
+    for sym in symbols.iter_mut() {
+        while stack.last().is_some_and(|&(end, _)| end < sym.line) {
+            stack.pop();
+        }
+        sym.level = stack.last().map_or(0, |&(_, level)| level + 1);
+        if let Some(end) = sym.end_line.filter(|&end| end > sym.line) {
+            stack.push((end, sym.level));
+        }
+    }
+}
+
+/// Finds the closing brace for a symbol starting at `start_line` (0-indexed).
+/// Returns the 1-indexed line number of the closing brace, or None.
+///
+/// Items without a body (`struct Unit;`, `const X: u8 = 1;`, trait method
+/// declarations) end at the first `;` outside parentheses or brackets.
+fn find_brace_end(lines: &[&str], start_line: usize) -> Option<usize> {
+    let mut depth: i32 = 0;
+    let mut found_open = false;
+    let mut nesting: i32 = 0; // () and [] before the body
+
+    for (i, line) in lines.iter().enumerate().skip(start_line) {
+        for ch in line.chars() {
+            if !found_open {
+                match ch {
+                    '(' | '[' => nesting += 1,
+                    ')' | ']' => nesting -= 1,
+                    ';' if nesting <= 0 => return Some(i + 1),
+                    _ => {}
+                }
+            }
+            if ch == '{' {
+                depth += 1;
+                found_open = true;
+            } else if ch == '}' {
+                depth -= 1;
+                if found_open && depth == 0 {
+                    return Some(i + 1); // 1-indexed
+                }
+            }
+        }
+        // If we found the opening brace but are past it and depth is back to 0
+        if found_open && depth == 0 {
+            return Some(i + 1);
+        }
+    }
+
+    None
+}
+
+/// Finds the end of an indentation block for Python (0-indexed start line).
+/// Returns the 1-indexed line number of the last line in the block.
+fn find_indent_end(lines: &[&str], start_line: usize, base_indent: usize) -> Option<usize> {
+    let mut last_content_line = start_line;
+
+    for (i, line) in lines.iter().enumerate().skip(start_line + 1) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue; // Skip blank lines
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent <= base_indent {
+            // We've exited the block
+            return Some(last_content_line + 1); // 1-indexed
+        }
+        last_content_line = i;
+    }
+
+    // Block extends to end of file
+    Some(last_content_line + 1)
+}
+
+/// Strips visibility (`pub`, `pub(crate)`, `pub(in a::b)`) and item
+/// qualifiers (`async`, `unsafe`, `default`, `extern "C"`, and `const`
+/// when it qualifies a `fn`) from the start of a Rust item line.
+fn strip_rust_modifiers(mut line: &str) -> &str {
+    if let Some(rest) = line.strip_prefix("pub") {
+        if let Some(restricted) = rest.strip_prefix('(') {
+            line = restricted
+                .split_once(')')
+                .map_or(restricted, |(_, r)| r)
+                .trim_start();
+        } else if rest.starts_with(' ') {
+            line = rest.trim_start();
+        }
+    }
+
+    loop {
+        let before = line;
+        for qualifier in ["async ", "unsafe ", "default "] {
+            if let Some(rest) = line.strip_prefix(qualifier) {
+                line = rest.trim_start();
+            }
+        }
+        if let Some(rest) = line.strip_prefix("extern ") {
+            line = rest.trim_start();
+            if let Some(abi) = line.strip_prefix('"') {
+                line = abi.split_once('"').map_or(line, |(_, r)| r.trim_start());
+            }
+        }
+        // `const fn` vs. `const NAME: T`
+        if let Some(rest) = line.strip_prefix("const ") {
+            let next = rest
+                .split_whitespace()
+                .find(|t| !matches!(*t, "async" | "unsafe" | "extern") && !t.starts_with('"'));
+            if next == Some("fn") {
+                line = rest.trim_start();
+            }
+        }
+        if line == before {
+            return line;
+        }
+    }
+}
+
+/// Returns the identifier at the start of `s` (after whitespace).
+fn leading_ident(s: &str) -> Option<&str> {
+    let s = s.trim_start();
+    let s = s.strip_prefix("r#").unwrap_or(s);
+    let end = s
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(s.len());
+    (end > 0).then(|| &s[..end])
+}
+
+/// Returns the implemented type of an `impl` header (`impl<T> Trait for
+/// Type<T> {` → `Trait for Type<T>`).
+fn impl_target(rest: &str) -> Option<String> {
+    let mut rest = rest.trim_start();
+    // Skip the generic parameter list, which may nest
+    if rest.starts_with('<') {
+        let mut depth = 0;
+        let end = rest.char_indices().find_map(|(i, c)| {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(i)
+        })?;
+        rest = &rest[end + 1..];
+    }
+    let target = rest.split('{').next()?;
+    let target = target.split(" where").next()?.trim();
+    (!target.is_empty()).then(|| target.to_string())
+}
+
+fn extract_rust_symbol(line: &str, line_num: usize) -> Option<Symbol> {
+    let item = strip_rust_modifiers(line);
+    let symbol = |name: &str, kind: &str| {
+        Some(Symbol {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        })
+    };
+
+    if let Some(rest) = item.strip_prefix("impl") {
+        if rest.starts_with([' ', '<']) {
+            return symbol(&impl_target(rest)?, "impl");
+        }
+    }
+    if let Some(rest) = item.strip_prefix("macro_rules!") {
+        return symbol(leading_ident(rest)?, "macro");
+    }
+
+    let (keyword, rest) = item.split_once(' ')?;
+    let kind = match keyword {
+        "fn" | "struct" | "enum" | "trait" | "mod" | "type" | "const" | "union" => keyword,
+        "static" => {
+            let rest = rest.trim_start();
+            let rest = rest.strip_prefix("mut ").unwrap_or(rest);
+            return symbol(leading_ident(rest)?, "static");
+        }
+        _ => return None,
+    };
+    // `const _: () = ...` declares nothing nameable
+    let name = leading_ident(rest).filter(|n| *n != "_")?;
+    symbol(name, kind)
+}
+fn extract_python_symbol(line: &str, line_num: usize) -> Option<Symbol> {
+    if line.starts_with("def ") {
+        let name = line
+            .trim_start_matches("def ")
+            .split('(')
+            .next()?
+            .to_string();
+        return Some(Symbol {
+            name,
+            kind: "fn".to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        });
+    }
+
+    if line.starts_with("class ") {
+        let name = line
+            .trim_start_matches("class ")
+            .split('(')
+            .next()?
+            .split(':')
+            .next()?
+            .to_string();
+        return Some(Symbol {
+            name,
+            kind: "class".to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        });
+    }
+
+    if line.starts_with("async def ") {
+        let name = line
+            .trim_start_matches("async def ")
+            .split('(')
+            .next()?
+            .to_string();
+        return Some(Symbol {
+            name,
+            kind: "fn".to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        });
+    }
+
+    None
+}
+
+fn extract_js_symbol(line: &str, line_num: usize) -> Option<Symbol> {
+    if line.starts_with("function ")
+        || line.starts_with("async function ")
+        || line.starts_with("export function ")
+        || line.starts_with("export async function ")
+    {
+        let name = line
+            .split('(')
+            .next()?
+            .split_whitespace()
+            .last()?
+            .to_string();
+        return Some(Symbol {
+            name,
+            kind: "fn".to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        });
+    }
+
+    if line.starts_with("class ") || line.starts_with("export class ") {
+        let name = line
+            .split('{')
+            .next()?
+            .split_whitespace()
+            .find(|w| *w != "class" && *w != "export" && *w != "extends")?
+            .to_string();
+        return Some(Symbol {
+            name,
+            kind: "class".to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        });
+    }
+
+    if line.contains("const ")
+        && line.contains(" = ")
+        && (line.contains("=>") || line.contains("function"))
+    {
+        let name = line
+            .split('=')
+            .next()?
+            .split_whitespace()
+            .last()?
+            .to_string();
+        return Some(Symbol {
+            name,
+            kind: "fn".to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        });
+    }
+
+    None
+}
+
+/// Kotlin declaration modifiers that may precede the keyword.
+const KOTLIN_MODIFIERS: &[&str] = &[
+    "public",
+    "private",
+    "internal",
+    "protected",
+    "open",
+    "abstract",
+    "final",
+    "sealed",
+    "data",
+    "inner",
+    "value",
+    "annotation",
+    "override",
+    "suspend",
+    "inline",
+    "operator",
+    "infix",
+    "tailrec",
+    "external",
+    "expect",
+    "actual",
+    "const",
+    "lateinit",
+    "companion",
+];
+
+fn extract_kotlin_symbol(line: &str, line_num: usize) -> Option<Symbol> {
+    let mut words = line.split_whitespace().peekable();
+    let mut is_enum = false;
+    while let Some(&word) = words.peek() {
+        if word.starts_with('@') || KOTLIN_MODIFIERS.contains(&word) {
+            words.next();
+        } else if word == "enum" {
+            is_enum = true;
+            words.next();
+        } else {
+            break;
+        }
+    }
+
+    let keyword = words.next()?;
+    let kind = match keyword {
+        "class" if is_enum => "enum",
+        "class" | "object" => "class",
+        "interface" => "iface",
+        "fun" => "fn",
+        "typealias" => "type",
+        _ => return None,
+    };
+
+    let rest = line.split_once(keyword).map(|(_, r)| r.trim_start())?;
+    let name = if kind == "fn" {
+        // fun <T> Receiver.name(...)
+        let rest = if rest.starts_with('<') {
+            rest.split_once('>').map_or(rest, |(_, r)| r.trim_start())
+        } else {
+            rest
+        };
+        let head = rest.split('(').next()?;
+        head.rsplit('.').next()?.trim().to_string()
+    } else {
+        match leading_ident(rest) {
+            Some(ident) => ident.to_string(),
+            // `companion object { ... }`
+            None if keyword == "object" => "Companion".to_string(),
+            None => return None,
+        }
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(Symbol {
+        name,
+        kind: kind.to_string(),
+        line: line_num,
+        end_line: None,
+        ..Default::default()
+    })
+}
+
+fn extract_go_symbol(line: &str, line_num: usize) -> Option<Symbol> {
+    if line.starts_with("func ") {
+        let rest = line.trim_start_matches("func ");
+        // Handle method syntax: func (r *Receiver) MethodName()
+        let name = if rest.starts_with('(') {
+            rest.split(')')
+                .nth(1)?
+                .trim()
+                .split('(')
+                .next()?
+                .to_string()
+        } else {
+            rest.split('(').next()?.to_string()
+        };
+        return Some(Symbol {
+            name,
+            kind: "fn".to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        });
+    }
+
+    if line.starts_with("type ") && line.contains(" struct") {
+        let name = line
+            .trim_start_matches("type ")
+            .split_whitespace()
+            .next()?
+            .to_string();
+        return Some(Symbol {
+            name,
+            kind: "struct".to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        });
+    }
+
+    if line.starts_with("type ") && line.contains(" interface") {
+        let name = line
+            .trim_start_matches("type ")
+            .split_whitespace()
+            .next()?
+            .to_string();
+        return Some(Symbol {
+            name,
+            kind: "iface".to_string(),
+            line: line_num,
+            end_line: None,
+            ..Default::default()
+        });
+    }
+
+    None
+}
+
+/// Returns, for each symbol, the index of the innermost earlier symbol
+/// at a shallower `level` whose line range encloses it.
+///
+/// `symbols` must be in line order, as returned by [`extract_symbols`].
+/// Symbols without an `end_line` never enclose anything.
+pub fn parent_indices(symbols: &[Symbol]) -> Vec<Option<usize>> {
+    let mut stack: Vec<usize> = Vec::new();
+    symbols
+        .iter()
+        .enumerate()
+        .map(|(i, sym)| {
+            while let Some(&top) = stack.last() {
+                let enclosing = &symbols[top];
+                if enclosing.level < sym.level
+                    && enclosing.end_line.is_some_and(|end| end >= sym.line)
+                {
+                    break;
+                }
+                stack.pop();
+            }
+            let parent = stack.last().copied();
+            if sym.end_line.is_some() {
+                stack.push(i);
+            }
+            parent
+        })
+        .collect()
+}
+
+/// Name a symbol is known by as a parent: the implemented type for
+/// `impl Trait for Type` blocks, without generics.
+pub fn parent_name(symbol: &Symbol) -> &str {
+    let name = match symbol.kind.as_str() {
+        "impl" => symbol.name.rsplit(" for ").next().unwrap_or(&symbol.name),
+        _ => &symbol.name,
+    };
+    name.split('<').next().unwrap_or(name).trim()
+}
+
+/// Keywords that open a supertype list in a class-like declaration.
+const SUPERTYPE_KEYWORDS: &[&str] = &["extends", "implements"];
+
+/// Keywords that end a supertype list.
+const SUPERTYPE_END_KEYWORDS: &[&str] = &["where", "permits"];
+
+/// Names of the types a declaration extends or implements, without
+/// generics or path qualifiers: the trait of a Rust `impl Trait for Type`
+/// (or a trait's supertraits), Python base classes, and the
+/// `extends`/`implements` or `:` lists of other languages.
+///
+/// `signature` is the declaration as returned by [`signature_at`].
+pub fn supertypes(name: &str, kind: &str, signature: &str, file_type: &str) -> Vec<String> {
+    let items = match (file_type, kind) {
+        ("rs", "impl") => name
+            .split_once(" for ")
+            .map(|(tr, _)| vec![tr])
+            .unwrap_or_default(),
+        ("py" | "pyi", _) => after_name(signature, name)
+            .and_then(|rest| rest.trim_start().strip_prefix('('))
+            .map(|rest| {
+                supertype_items(rest, true)
+                    .into_iter()
+                    // `metaclass=ABCMeta` and other class keywords
+                    .filter(|item| !item.contains('='))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => after_name(signature, name)
+            .map(|rest| supertype_items(rest, false))
+            .unwrap_or_default(),
+    };
+    items.into_iter().filter_map(base_type_name).collect()
+}
+
+/// The part of `signature` after the first whole-word `name` and any
+/// generic parameter list right after it.
+fn after_name<'s>(signature: &'s str, name: &str) -> Option<&'s str> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let (pos, _) = signature.match_indices(name).find(|&(pos, _)| {
+        !signature[..pos].ends_with(is_ident)
+            && !signature[pos + name.len()..].starts_with(is_ident)
+    })?;
+    let rest = &signature[pos + name.len()..];
+    let Some(close) = rest.chars().next().and_then(|c| match c {
+        '<' => Some('>'),
+        '[' => Some(']'),
+        _ => None,
+    }) else {
+        return Some(rest);
+    };
+    let open = rest.chars().next()?;
+    let mut depth = 0;
+    let end = rest.char_indices().find_map(|(i, c)| {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+        }
+        (depth == 0).then_some(i)
+    })?;
+    Some(&rest[end + 1..])
+}
+
+/// Splits the supertype lists of a declaration tail (`extends A<T>
+/// implements B, C`, `: Base(x), Iface`, `: Clone + Send where`) into
+/// items, ignoring separators inside brackets. With `in_list`, `rest`
+/// starts inside a list closed by `)` (Python bases).
+fn supertype_items(rest: &str, in_list: bool) -> Vec<&str> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut items = Vec::new();
+    let mut start = in_list.then_some(0);
+    let mut depth = 0i32;
+    let mut close = |start: &mut Option<usize>, end: usize, next: Option<usize>| {
+        if let Some(s) = *start {
+            items.push(&rest[s..end]);
+        }
+        *start = next;
+    };
+
+    let mut chars = rest.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => {
+                depth -= 1;
+                if depth < 0 {
+                    close(&mut start, i, None);
+                    break;
+                }
+            }
+            _ if depth > 0 => {}
+            ',' | '+' => {
+                let next = start.map(|_| i + 1);
+                close(&mut start, i, next);
+            }
+            ':' if chars.peek().is_some_and(|&(_, n)| n == ':') => {
+                chars.next();
+            }
+            ':' => close(&mut start, i, Some(i + 1)),
+            _ if rest[..i].ends_with(is_ident) => {}
+            _ => {
+                let word = &rest[i..];
+                let word = &word[..word.find(|c: char| !is_ident(c)).unwrap_or(word.len())];
+                if SUPERTYPE_KEYWORDS.contains(&word) {
+                    close(&mut start, i, Some(i + word.len()));
+                } else if SUPERTYPE_END_KEYWORDS.contains(&word) {
+                    close(&mut start, i, None);
+                    break;
+                }
+            }
+        }
+    }
+    close(&mut start, rest.len(), None);
+    items
+}
+
+/// The bare type name of a supertype list item: `public Base` →
+/// `Base`, `fmt::Display` → `Display`, `Repo<T>` → `Repo`. `None` for
+/// lifetimes, `?Sized` and negative impls.
+fn base_type_name(item: &str) -> Option<String> {
+    let item = item.trim();
+    let item = &item[..item.find(['<', '(', '[']).unwrap_or(item.len())];
+    // Access and inheritance modifiers (C++ `public virtual Base`)
+    let item = item.split_whitespace().next_back()?;
+    let name = item.rsplit(['.', ':', '\\']).next()?;
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        .then(|| name.to_string())
+}
+
+/// Fills the descriptive fields of `symbols`: `parent` always,
+/// `signature` and `visibility` for code, and `doc` when `with_docs`.
+pub fn annotate(symbols: &mut [Symbol], content: &str, file_type: &str, with_docs: bool) {
+    let parents: Vec<Option<String>> = parent_indices(symbols)
+        .into_iter()
+        .map(|p| p.map(|p| parent_name(&symbols[p]).to_string()))
+        .collect();
+    let lines: Vec<&str> = content.lines().collect();
+    let code = !is_document_type(file_type);
+
+    for (sym, parent) in symbols.iter_mut().zip(parents) {
+        sym.parent = parent;
+        if code {
+            let signature = signature_at(&lines, sym.line);
+            sym.visibility = visibility(sym, &signature, file_type);
+            sym.signature = Some(signature).filter(|s| !s.is_empty());
+            if with_docs {
+                sym.doc = doc_line(&lines, sym, file_type);
+            }
+        }
+    }
+}
+
+/// Returns the declaration starting at 1-indexed `line`, up to (not
+/// including) its body, with whitespace collapsed.
+pub fn signature_at(lines: &[&str], line: usize) -> String {
+    let mut signature = String::new();
+    for text in lines
+        .iter()
+        .skip(line.saturating_sub(1))
+        .take(SIGNATURE_MAX_LINES)
+    {
+        let (part, done) = match text.find('{') {
+            Some(brace) => (&text[..brace], true),
+            None => (*text, text.trim_end().ends_with([';', ':'])),
+        };
+        for word in part.split_whitespace() {
+            if !signature.is_empty() {
+                signature.push(' ');
+            }
+            signature.push_str(word);
+        }
+        if done {
+            break;
+        }
+    }
+    if signature.len() > SIGNATURE_MAX_CHARS {
+        let mut cut = SIGNATURE_MAX_CHARS;
+        while !signature.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        signature.truncate(cut);
+        signature.push('…');
+    }
+    signature
+}
+
+/// Visibility keywords recognized in signatures of other languages.
+const VISIBILITY_KEYWORDS: &[&str] = &[
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "fileprivate",
+    "open",
+    "export",
+];
+
+/// Visibility of a symbol: the declared modifier, or the language's naming
+/// convention (Go capitalization, Python underscores). `None` when the
+/// language has no such notion or nothing is declared.
+fn visibility(symbol: &Symbol, signature: &str, file_type: &str) -> Option<String> {
+    let name = symbol.name.as_str();
+    match file_type {
+        "rs" if symbol.kind == "impl" => None,
+        "rs" => Some(match signature.strip_prefix("pub") {
+            Some(rest) if rest.starts_with('(') => format!("pub{}", &rest[..=rest.find(')')?]),
+            Some(rest) if rest.starts_with(' ') => "pub".to_string(),
+            _ => "private".to_string(),
+        }),
+        "go" => Some(
+            if name.starts_with(char::is_uppercase) {
+                "public"
+            } else {
+                "private"
+            }
+            .to_string(),
+        ),
+        "py" => Some(
+            if name.starts_with('_') && !name.ends_with("__") {
+                "private"
+            } else {
+                "public"
+            }
+            .to_string(),
+        ),
+        _ if name.starts_with('#') => Some("private".to_string()),
+        _ => signature
+            .split_whitespace()
+            .take_while(|word| !word.contains(name))
+            .find(|word| VISIBILITY_KEYWORDS.contains(word))
+            .map(str::to_string),
+    }
+}
+
+/// Whether `#` starts a comment in `file_type` (rather than an attribute).
+pub fn hash_comments(file_type: &str) -> bool {
+    matches!(file_type, "py" | "rb")
+}
+
+/// Whether a line directly above an item belongs to it: doc or line
+/// comments, attributes (`#[...]`) and decorators (`@...`).
+pub fn is_doc_or_attribute(line: &str, hash_comments: bool) -> bool {
+    let t = line.trim_start();
+    t.starts_with("//")
+        || t.starts_with("/*")
+        || t.starts_with('*')
+        || t.starts_with("#[")
+        || t.starts_with('@')
+        || (hash_comments && t.starts_with('#'))
+}
+
+/// First line of the comment block above `symbol` (or of a Python
+/// docstring), without comment markers.
+fn doc_line(lines: &[&str], symbol: &Symbol, file_type: &str) -> Option<String> {
+    if file_type == "py" {
+        return docstring_line(lines, symbol);
+    }
+    let hash = hash_comments(file_type);
+    let end = symbol.line.saturating_sub(1).min(lines.len());
+    let mut start = end;
+    while start > 0 && is_doc_or_attribute(lines[start - 1], hash) {
+        start -= 1;
+    }
+    lines[start..end].iter().find_map(|line| {
+        let t = line.trim();
+        if t.starts_with("#[") || t.starts_with('@') {
+            return None;
+        }
+        let text = ["///", "//!", "/**", "/*", "//", "*", "#"]
+            .iter()
+            .find_map(|marker| t.strip_prefix(marker))?;
+        let text = text.trim_end_matches("*/").trim();
+        (!text.is_empty()).then(|| text.to_string())
+    })
+}
+
+/// First line of the docstring opening the body of a Python `def`/`class`.
+fn docstring_line(lines: &[&str], symbol: &Symbol) -> Option<String> {
+    let start = symbol.line.checked_sub(1)?;
+    let header_end = (start..lines.len().min(start + SIGNATURE_MAX_LINES))
+        .find(|&i| lines[i].trim_end().ends_with(':'))?;
+    let mut body = lines[header_end + 1..]
+        .iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty());
+    let first = body.next()?.trim_start_matches(['r', 'u', 'R', 'U']);
+    let rest = ["\"\"\"", "'''"]
+        .iter()
+        .find_map(|quote| first.strip_prefix(quote))?;
+    let text = match rest.trim_end_matches(['"', '\'']).trim() {
+        "" => body.next()?.trim_end_matches(['"', '\'']).trim(),
+        text => text,
+    };
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Whether `file_type` is a document or data format whose outline lists
+/// headings, tables or keys rather than code declarations.
+pub fn is_document_type(file_type: &str) -> bool {
+    matches!(
+        file_type,
+        "md" | "markdown" | "toml" | "yaml" | "yml" | "json"
+    )
+}
+
+/// Symbol kinds whose declaration headers can name supertypes.
+const TYPE_KINDS: &[&str] = &[
+    "class", "struct", "enum", "iface", "trait", "impl", "object",
+];
+
+/// Extracts the symbols of a file as rows for the `symbols` table, with
+/// each symbol's enclosing parent resolved by name and, for types, the
+/// [`supertypes`] their declaration lists.
+///
+/// Document formats are skipped: their headings and keys are not symbols
+/// to look up by name.
+pub fn index_records(content: &str, file_type: &str) -> Vec<SymbolData> {
+    if is_document_type(file_type) {
+        return Vec::new();
+    }
+    let symbols = extract_symbols(content, file_type);
+    let parents = parent_indices(&symbols);
+    let lines: Vec<&str> = content.lines().collect();
+    symbols
+        .iter()
+        .zip(parents)
+        .map(|(sym, parent)| SymbolData {
+            name: sym.name.clone(),
+            kind: sym.kind.clone(),
+            line: sym.line,
+            end_line: sym.end_line,
+            parent: parent.map(|p| parent_name(&symbols[p]).to_string()),
+            supertypes: if TYPE_KINDS.contains(&sym.kind.as_str()) {
+                let signature = signature_at(&lines, sym.line);
+                supertypes(&sym.name, &sym.kind, &signature, file_type)
+            } else {
+                Vec::new()
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_rust_symbols_with_ranges() {
+        let content = "pub struct Config {\n    a: u32,\n}\n\nimpl Config {\n    pub fn load() -> Self {\n        todo!()\n    }\n}\n";
+        let symbols = extract_symbols(content, "rs");
+
+        let summary: Vec<_> = symbols
+            .iter()
+            .map(|s| (s.kind.as_str(), s.name.as_str(), s.line, s.end_line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("struct", "Config", 1, Some(3)),
+                ("impl", "Config", 5, Some(9)),
+                ("fn", "load", 6, Some(8)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rust_modifiers_and_bodyless_items() {
+        let content = "pub(crate) async fn load() {\n}\nconst LIMIT: usize = 3;\npub static mut COUNT: u32 = 0;\npub(in crate::a) type Alias = Vec<u8>;\nconst unsafe fn raw(x: [u8; 2]) {\n}\nunsafe impl<T: Send> Send for Wrapper<T> {}\nextern \"C\" fn ffi();\nconst _: () = ();\n";
+        // The heuristics remain the fallback, so both paths must agree
+        for symbols in [
+            extract_symbols(content, "rs"),
+            heuristic_symbols(content, "rs"),
+        ] {
+            let summary: Vec<_> = symbols
+                .iter()
+                .map(|s| (s.kind.as_str(), s.name.as_str(), s.line, s.end_line))
+                .collect();
+            assert_eq!(
+                summary,
+                vec![
+                    ("fn", "load", 1, Some(2)),
+                    ("const", "LIMIT", 3, Some(3)),
+                    ("static", "COUNT", 4, Some(4)),
+                    ("type", "Alias", 5, Some(5)),
+                    ("fn", "raw", 6, Some(7)),
+                    ("impl", "Send for Wrapper<T>", 8, Some(8)),
+                    ("fn", "ffi", 9, Some(9)),
+                ]
+            );
+            assert_eq!(parent_name(&symbols[5]), "Wrapper");
+        }
+    }
+
+    #[test]
+    fn test_parent_indices_follow_nesting() {
+        let content = "impl Display for Mode {\n    fn fmt(&self) {\n    }\n}\nfn free() {}\n";
+        let symbols = extract_symbols(content, "rs");
+        let parents = parent_indices(&symbols);
+
+        assert_eq!(parents, vec![None, Some(0), None]);
+        assert_eq!(parent_name(&symbols[0]), "Mode");
+    }
+
+    #[test]
+    fn test_python_parent_by_indentation() {
+        let content = "class Service:\n    def run(self):\n        pass\n\ndef main():\n    pass\n";
+        let symbols = extract_symbols(content, "py");
+
+        assert_eq!(parent_indices(&symbols), vec![None, Some(0), None]);
+    }
+
+    #[test]
+    fn test_index_records_name_parents() {
+        let content = "impl<T> Trait for Wrapper {\n    fn call(&self) {\n    }\n}\n";
+        let records = index_records(content, "rs");
+
+        assert_eq!(records[1].name, "call");
+        assert_eq!(records[1].parent.as_deref(), Some("Wrapper"));
+        assert_eq!(records[0].parent, None);
+    }
+
+    #[test]
+    fn test_kotlin_heuristic_symbols() {
+        let content = "@Serializable\ndata class User(val id: Int)\n\nsealed interface Shape {\n    fun area(): Double\n}\n\nenum class Mode { A, B }\n\nclass Repo {\n    companion object {\n        const val MAX = 3\n    }\n\n    suspend fun load(\n        id: Int,\n    ): User {\n        return User(id)\n    }\n\n    fun <T> List<T>.second() = this[1]\n}\n";
+        let symbols = extract_symbols(content, "kt");
+
+        let summary: Vec<_> = symbols
+            .iter()
+            .map(|s| (s.kind.as_str(), s.name.as_str(), s.line, s.end_line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("class", "User", 2, Some(2)),
+                ("iface", "Shape", 4, Some(6)),
+                ("fn", "area", 5, Some(5)),
+                ("enum", "Mode", 8, Some(8)),
+                ("class", "Repo", 10, Some(22)),
+                ("class", "Companion", 11, Some(13)),
+                ("fn", "load", 15, Some(19)),
+                ("fn", "second", 21, Some(21)),
+            ]
+        );
+        assert_eq!(parent_indices(&symbols)[6], Some(4));
+    }
+
+    #[test]
+    fn test_heuristic_levels_follow_ranges_not_indent_width() {
+        let content = "class Repo {\n  fun load() {\n  }\n}\n";
+        let levels: Vec<_> = extract_symbols(content, "kt")
+            .iter()
+            .map(|s| s.level)
+            .collect();
+        assert_eq!(levels, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_annotate_signatures_visibility_and_docs() {
+        let content = "/// Loaded settings.\n///\n/// More detail.\n#[derive(Debug)]\npub(crate) struct Config {\n    a: u32,\n}\n\nimpl Config {\n    /** Reads the file. */\n    fn load(\n        path: &str,\n    ) -> Self {\n        todo!()\n    }\n}\n";
+        let mut symbols = extract_symbols(content, "rs");
+        annotate(&mut symbols, content, "rs", true);
+
+        let config = &symbols[0];
+        assert_eq!(
+            config.signature.as_deref(),
+            Some("pub(crate) struct Config")
+        );
+        assert_eq!(config.visibility.as_deref(), Some("pub(crate)"));
+        assert_eq!(config.doc.as_deref(), Some("Loaded settings."));
+        assert_eq!(symbols[1].visibility, None);
+
+        let load = &symbols[2];
+        assert_eq!(
+            load.signature.as_deref(),
+            Some("fn load( path: &str, ) -> Self")
+        );
+        assert_eq!(load.visibility.as_deref(), Some("private"));
+        assert_eq!(load.doc.as_deref(), Some("Reads the file."));
+        assert_eq!(load.parent.as_deref(), Some("Config"));
+    }
+
+    #[test]
+    fn test_annotate_python_docstrings_and_keyword_visibility() {
+        let content = "class Repo:\n    def _load(self):\n        \"\"\"Load rows.\"\"\"\n\n    def save(self):\n        '''\n        Persist.\n        '''\n";
+        let mut symbols = extract_symbols(content, "py");
+        annotate(&mut symbols, content, "py", true);
+        assert_eq!(symbols[1].doc.as_deref(), Some("Load rows."));
+        assert_eq!(symbols[1].visibility.as_deref(), Some("private"));
+        assert_eq!(symbols[2].doc.as_deref(), Some("Persist."));
+        assert_eq!(symbols[2].signature.as_deref(), Some("def save(self):"));
+
+        let java = "class A {\n  // Runs it.\n  protected void run() {}\n}\n";
+        let mut symbols = extract_symbols(java, "java");
+        annotate(&mut symbols, java, "java", false);
+        assert_eq!(symbols[1].visibility.as_deref(), Some("protected"));
+        assert_eq!(symbols[1].doc, None);
+    }
+
+    #[test]
+    fn test_signature_stops_at_body() {
+        let content = "/// doc\npub fn search(\n    query: &str,\n    limit: usize,\n) -> Vec<Hit> {\n    todo!()\n}\nconst MAX: usize = 3;\n";
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(
+            signature_at(&lines, 2),
+            "pub fn search( query: &str, limit: usize, ) -> Vec<Hit>"
+        );
+        assert_eq!(signature_at(&lines, 8), "const MAX: usize = 3;");
+    }
+
+    #[test]
+    fn test_supertypes() {
+        let cases = [
+            ("fmt::Display for Mode<T>", "impl", "impl<T> fmt::Display for Mode<T>", "rs", vec!["Display"]),
+            ("Config", "impl", "impl Config", "rs", vec![]),
+            ("Store", "trait", "pub trait Store<K>: Clone + Send where K: Hash", "rs", vec!["Clone", "Send"]),
+            ("Sized", "trait", "trait Sized: ?Sized + 'static", "rs", vec![]),
+            ("Repo", "class", "class Repo(base.Model, Generic[T], metaclass=Meta):", "py", vec!["Model", "Generic"]),
+            ("Api", "class", "@Injectable() export class Api<T extends Base> extends Client<T> implements Svc, Log", "ts", vec!["Client", "Svc", "Log"]),
+            ("Repo", "class", "public sealed class Repo : IRepo<User>, IDisposable where T : new()", "cs", vec!["IRepo", "IDisposable"]),
+            ("View", "class", "class View(val id: Int) : Base(id), Listener", "kt", vec!["Base", "Listener"]),
+            ("Widget", "class", "class Widget : public virtual Base", "cpp", vec!["Base"]),
+            ("Shape", "iface", "public sealed interface Shape extends Comparable<Shape> permits Circle", "java", vec!["Comparable"]),
+        ];
+        for (name, kind, signature, file_type, expected) in cases {
+            assert_eq!(
+                supertypes(name, kind, signature, file_type),
+                expected,
+                "{signature}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_index_records_store_supertypes() {
+        let content = "class Base:\n    pass\n\nclass User(Base, Mixin):\n    def save(self):\n        pass\n";
+        let records = index_records(content, "py");
+
+        let summary: Vec<_> = records
+            .iter()
+            .map(|r| (r.name.as_str(), r.supertypes.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Base", vec![]),
+                ("User", vec!["Base".to_string(), "Mixin".to_string()]),
+                ("save", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_documents_outline_but_are_not_indexed() {
+        let content = "# Guide\n\n## Setup\n";
+        assert_eq!(extract_symbols(content, "md").len(), 2);
+        assert!(index_records(content, "md").is_empty());
+    }
+
+    #[test]
+    fn test_unknown_file_type_has_no_symbols() {
+        assert!(extract_symbols("fn main() {}", "txt").is_empty());
+        assert_eq!(detect_file_type(Path::new("a/B.RS")), "rs");
+    }
+}
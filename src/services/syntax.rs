@@ -0,0 +1,877 @@
+//! Syntax-tree symbol extraction with tree-sitter.
+//!
+//! Grammars come from the `tree-sitter-*` crates, pinned to exact versions
+//! in `Cargo.toml`, rather than being vendored: their generated C parsers
+//! total roughly 100MB. Cargo compiles them into the binary, so no grammar
+//! is fetched or loaded at runtime, and a grammar upgrade is a deliberate
+//! version bump.
+//! Walking the tree instead of matching line prefixes handles any
+//! visibility or qualifier (`pub(crate) unsafe fn`), methods regardless of
+//! indentation, decorators, and braces inside strings or comments. Nesting
+//! comes from the tree: methods are children of their `impl`/class.
+//!
+//! Only declarations are reported; function bodies are not descended into.
+//! Markdown, TOML, YAML and JSON use the same [`Symbol`] shape for
+//! headings, tables and keys.
+
+use super::symbols::Symbol;
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use tree_sitter::{Language, Node, Parser};
+
+/// Languages with a compiled-in grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Lang {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Go,
+    Java,
+    C,
+    Cpp,
+    CSharp,
+    Ruby,
+    Php,
+    Swift,
+    Markdown,
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl Lang {
+    fn from_file_type(file_type: &str) -> Option<Self> {
+        match file_type {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "go" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            "c" | "h" => Some(Self::C),
+            "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Some(Self::Cpp),
+            "cs" => Some(Self::CSharp),
+            "rb" => Some(Self::Ruby),
+            "php" => Some(Self::Php),
+            "swift" => Some(Self::Swift),
+            "md" | "markdown" => Some(Self::Markdown),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    fn language(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::Java => tree_sitter_java::LANGUAGE.into(),
+            Self::C => tree_sitter_c::LANGUAGE.into(),
+            Self::Cpp => tree_sitter_cpp::LANGUAGE.into(),
+            Self::CSharp => tree_sitter_c_sharp::LANGUAGE.into(),
+            Self::Ruby => tree_sitter_ruby::LANGUAGE.into(),
+            Self::Php => tree_sitter_php::LANGUAGE_PHP.into(),
+            Self::Swift => tree_sitter_swift::LANGUAGE.into(),
+            Self::Markdown => tree_sitter_md::LANGUAGE.into(),
+            Self::Toml => tree_sitter_toml_ng::LANGUAGE.into(),
+            Self::Yaml => tree_sitter_yaml::LANGUAGE.into(),
+            Self::Json => tree_sitter_json::LANGUAGE.into(),
+        }
+    }
+}
+
+/// Nesting depth reported for YAML/JSON keys (top level is depth 1).
+const DATA_KEY_DEPTH: usize = 3;
+
+thread_local! {
+    /// Parsers are reused per thread (indexing parses on the rayon pool).
+    static PARSERS: RefCell<HashMap<Lang, Parser>> = RefCell::new(HashMap::new());
+}
+
+/// Whether `file_type` has a compiled-in grammar.
+pub fn supports(file_type: &str) -> bool {
+    Lang::from_file_type(file_type).is_some()
+}
+
+/// Extracts declarations from `content` using the grammar for
+/// `file_type`, in source order.
+///
+/// Returns `None` when there is no grammar for the file type or parsing
+/// fails, so the caller can fall back to line heuristics.
+pub fn extract(content: &str, file_type: &str) -> Option<Vec<Symbol>> {
+    let lang = Lang::from_file_type(file_type)?;
+    let tree = PARSERS.with(|parsers| {
+        let mut parsers = parsers.borrow_mut();
+        let parser = match parsers.entry(lang) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => {
+                let mut parser = Parser::new();
+                parser.set_language(&lang.language()).ok()?;
+                e.insert(parser)
+            }
+        };
+        parser.parse(content, None)
+    })?;
+
+    let mut walker = Walker {
+        src: content.as_bytes(),
+        lang,
+        max_level: match lang {
+            Lang::Yaml | Lang::Json => DATA_KEY_DEPTH - 1,
+            _ => usize::MAX,
+        },
+        symbols: Vec::new(),
+    };
+    walker.visit_children(tree.root_node(), 0);
+    Some(walker.symbols)
+}
+
+/// How a syntax node contributes to the outline.
+enum Item<'t> {
+    /// A declaration; `body` holds nested declarations, if any.
+    Symbol {
+        name: String,
+        kind: &'static str,
+        body: Option<Node<'t>>,
+    },
+    /// A wrapper (`export`, decorators, `let` lists) whose children are
+    /// visited at the same level.
+    Transparent(Node<'t>),
+}
+
+struct Walker<'s> {
+    src: &'s [u8],
+    lang: Lang,
+    /// Deepest level whose symbols are reported.
+    max_level: usize,
+    symbols: Vec<Symbol>,
+}
+
+impl<'s> Walker<'s> {
+    fn visit_children(&mut self, node: Node<'_>, level: usize) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            self.visit(child, level);
+        }
+    }
+
+    fn visit(&mut self, node: Node<'_>, level: usize) {
+        let item = match self.lang {
+            Lang::Rust => self.rust_item(node),
+            Lang::Python => self.python_item(node),
+            Lang::JavaScript | Lang::TypeScript | Lang::Tsx => self.js_item(node),
+            Lang::Go => self.go_item(node),
+            Lang::Java => self.java_item(node),
+            Lang::C | Lang::Cpp => self.c_item(node),
+            Lang::CSharp => self.csharp_item(node),
+            Lang::Ruby => self.ruby_item(node),
+            Lang::Php => self.php_item(node),
+            Lang::Swift => self.swift_item(node),
+            Lang::Markdown => self.markdown_item(node),
+            Lang::Toml => self.toml_item(node),
+            Lang::Yaml | Lang::Json => self.data_item(node),
+        };
+        match item {
+            Some(Item::Symbol { name, kind, body }) => {
+                if name.is_empty() || name == "_" {
+                    return;
+                }
+                self.symbols.push(Symbol {
+                    name,
+                    kind: kind.to_string(),
+                    line: node.start_position().row + 1,
+                    end_line: Some(self.end_line(node)),
+                    level,
+                    ..Default::default()
+                });
+                if let Some(body) = body.filter(|_| level < self.max_level) {
+                    self.visit_children(body, level + 1);
+                }
+            }
+            Some(Item::Transparent(inner)) => self.visit_children(inner, level),
+            None => {}
+        }
+    }
+
+    /// Last line of `node`, ignoring trailing whitespace (Markdown sections
+    /// and TOML tables run up to the next heading or table).
+    fn end_line(&self, node: Node<'_>) -> usize {
+        let span = &self.src[node.start_byte()..node.end_byte()];
+        let trimmed = span.trim_ascii_end().len();
+        let trailing_newlines = span[trimmed..].iter().filter(|&&b| b == b'\n').count();
+        node.end_position().row + 1 - trailing_newlines
+    }
+
+    fn text(&self, node: Node<'_>) -> &'s str {
+        node.utf8_text(self.src).unwrap_or_default()
+    }
+
+    fn field_text(&self, node: Node<'_>, field: &str) -> Option<String> {
+        node.child_by_field_name(field)
+            .map(|n| self.text(n).to_string())
+    }
+
+    fn symbol<'t>(
+        &self,
+        node: Node<'t>,
+        kind: &'static str,
+        body: Option<Node<'t>>,
+    ) -> Option<Item<'t>> {
+        Some(Item::Symbol {
+            name: self.field_text(node, "name")?,
+            kind,
+            body,
+        })
+    }
+
+    fn rust_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        let body = node.child_by_field_name("body");
+        match node.kind() {
+            "function_item" | "function_signature_item" => self.symbol(node, "fn", None),
+            "struct_item" => self.symbol(node, "struct", None),
+            "union_item" => self.symbol(node, "union", None),
+            "enum_item" => self.symbol(node, "enum", None),
+            "trait_item" => self.symbol(node, "trait", body),
+            "mod_item" => self.symbol(node, "mod", body),
+            "const_item" => self.symbol(node, "const", None),
+            "static_item" => self.symbol(node, "static", None),
+            "type_item" => self.symbol(node, "type", None),
+            "macro_definition" => self.symbol(node, "macro", None),
+            "impl_item" => {
+                let target = self.field_text(node, "type")?;
+                let name = match self.field_text(node, "trait") {
+                    Some(tr) => format!("{tr} for {target}"),
+                    None => target,
+                };
+                Some(Item::Symbol {
+                    name,
+                    kind: "impl",
+                    body,
+                })
+            }
+            // extern "C" { ... }
+            "foreign_mod_item" => body.map(Item::Transparent),
+            _ => None,
+        }
+    }
+
+    fn python_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        match node.kind() {
+            "function_definition" => self.symbol(node, "fn", None),
+            "class_definition" => self.symbol(node, "class", node.child_by_field_name("body")),
+            // The decorated item keeps its own `def`/`class` line
+            "decorated_definition" => Some(Item::Transparent(node)),
+            _ => None,
+        }
+    }
+
+    fn js_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        let body = node.child_by_field_name("body");
+        match node.kind() {
+            "function_declaration"
+            | "generator_function_declaration"
+            | "function_signature"
+            | "method_definition"
+            | "abstract_method_signature" => self.symbol(node, "fn", None),
+            "class_declaration" | "abstract_class_declaration" | "class" => {
+                self.symbol(node, "class", body)
+            }
+            "interface_declaration" => self.symbol(node, "iface", None),
+            "type_alias_declaration" => self.symbol(node, "type", None),
+            "enum_declaration" => self.symbol(node, "enum", None),
+            "internal_module" | "module" => self.symbol(node, "mod", body),
+            // const handler = () => ..., class fields holding functions
+            "variable_declarator" | "public_field_definition" | "field_definition" => {
+                let value = node.child_by_field_name("value")?;
+                if !matches!(
+                    value.kind(),
+                    "arrow_function" | "function_expression" | "function" | "generator_function"
+                ) {
+                    return None;
+                }
+                let name = node
+                    .child_by_field_name("name")
+                    .or_else(|| node.child_by_field_name("property"))?;
+                Some(Item::Symbol {
+                    name: self.text(name).to_string(),
+                    kind: "fn",
+                    body: None,
+                })
+            }
+            "export_statement"
+            | "lexical_declaration"
+            | "variable_declaration"
+            | "ambient_declaration" => Some(Item::Transparent(node)),
+            _ => None,
+        }
+    }
+
+    fn go_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        match node.kind() {
+            "function_declaration" | "method_declaration" => self.symbol(node, "fn", None),
+            "type_declaration" => Some(Item::Transparent(node)),
+            "type_spec" | "type_alias" => {
+                let kind = match node.child_by_field_name("type").map(|t| t.kind()) {
+                    Some("struct_type") => "struct",
+                    Some("interface_type") => "iface",
+                    _ => "type",
+                };
+                self.symbol(node, kind, None)
+            }
+            _ => None,
+        }
+    }
+
+    fn java_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        let body = node.child_by_field_name("body");
+        match node.kind() {
+            "class_declaration" | "record_declaration" => self.symbol(node, "class", body),
+            "interface_declaration" | "annotation_type_declaration" => {
+                self.symbol(node, "iface", body)
+            }
+            "enum_declaration" => self.symbol(node, "enum", body),
+            "method_declaration" | "constructor_declaration" => self.symbol(node, "fn", None),
+            // Methods after the constants of an enum
+            "enum_body_declarations" => Some(Item::Transparent(node)),
+            _ => None,
+        }
+    }
+
+    fn c_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        let body = node.child_by_field_name("body");
+        match node.kind() {
+            "function_definition" => Some(Item::Symbol {
+                name: self.declarator_name(node)?,
+                kind: "fn",
+                body: None,
+            }),
+            // Prototypes (headers, in-class method declarations)
+            "declaration" | "field_declaration" => {
+                let mut declarator = node.child_by_field_name("declarator")?;
+                while matches!(
+                    declarator.kind(),
+                    "pointer_declarator" | "reference_declarator"
+                ) {
+                    declarator = declarator.child_by_field_name("declarator")?;
+                }
+                if declarator.kind() != "function_declarator" {
+                    return None;
+                }
+                Some(Item::Symbol {
+                    name: self.declarator_name(node)?,
+                    kind: "fn",
+                    body: None,
+                })
+            }
+            // Forward declarations (`struct s;`) have no body
+            "struct_specifier" if body.is_some() => self.symbol(node, "struct", body),
+            "union_specifier" if body.is_some() => self.symbol(node, "union", None),
+            "enum_specifier" if body.is_some() => self.symbol(node, "enum", None),
+            "class_specifier" if body.is_some() => self.symbol(node, "class", body),
+            "type_definition" => Some(Item::Symbol {
+                name: self.field_text(node, "declarator")?,
+                kind: "type",
+                body: None,
+            }),
+            "namespace_definition" => self.symbol(node, "mod", body),
+            "linkage_specification" => body.map(Item::Transparent),
+            "template_declaration"
+            | "preproc_ifdef"
+            | "preproc_if"
+            | "preproc_else"
+            | "preproc_elif"
+            | "access_specifier" => Some(Item::Transparent(node)),
+            _ => None,
+        }
+    }
+
+    /// Unwraps C/C++ declarators (`*f(...)`, `&Type::m(...)`) to the
+    /// declared name; qualified names keep only the last segment.
+    fn declarator_name(&self, node: Node<'_>) -> Option<String> {
+        let mut current = node.child_by_field_name("declarator")?;
+        loop {
+            match current.kind() {
+                "identifier" | "field_identifier" | "destructor_name" | "operator_name" => {
+                    return Some(self.text(current).to_string());
+                }
+                "qualified_identifier" => current = current.child_by_field_name("name")?,
+                _ => current = current.child_by_field_name("declarator")?,
+            }
+        }
+    }
+
+    fn csharp_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        let body = node.child_by_field_name("body");
+        match node.kind() {
+            "namespace_declaration" | "file_scoped_namespace_declaration" => {
+                self.symbol(node, "mod", body)
+            }
+            "class_declaration" | "record_declaration" => self.symbol(node, "class", body),
+            "struct_declaration" => self.symbol(node, "struct", body),
+            "interface_declaration" => self.symbol(node, "iface", body),
+            "enum_declaration" => self.symbol(node, "enum", None),
+            "method_declaration" | "constructor_declaration" | "local_function_statement" => {
+                self.symbol(node, "fn", None)
+            }
+            _ => None,
+        }
+    }
+
+    fn ruby_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        let body = node.child_by_field_name("body");
+        match node.kind() {
+            "module" => self.symbol(node, "mod", body),
+            "class" => self.symbol(node, "class", body),
+            "method" | "singleton_method" => self.symbol(node, "fn", None),
+            _ => None,
+        }
+    }
+
+    fn php_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        let body = node.child_by_field_name("body");
+        match node.kind() {
+            "namespace_definition" => self.symbol(node, "mod", body),
+            "class_declaration" => self.symbol(node, "class", body),
+            "interface_declaration" => self.symbol(node, "iface", body),
+            "trait_declaration" => self.symbol(node, "trait", body),
+            "enum_declaration" => self.symbol(node, "enum", body),
+            "function_definition" | "method_declaration" => self.symbol(node, "fn", None),
+            _ => None,
+        }
+    }
+
+    fn swift_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        let body = node.child_by_field_name("body");
+        match node.kind() {
+            // class, struct, enum, actor and extension share one node kind
+            "class_declaration" => {
+                let keyword = node
+                    .child_by_field_name("declaration_kind")
+                    .map(|k| self.text(k));
+                let kind = match keyword {
+                    Some("struct") => "struct",
+                    Some("enum") => "enum",
+                    Some("extension") => "impl",
+                    _ => "class",
+                };
+                self.symbol(node, kind, body)
+            }
+            "protocol_declaration" => self.symbol(node, "iface", body),
+            "function_declaration" | "protocol_function_declaration" => {
+                self.symbol(node, "fn", None)
+            }
+            "init_declaration" => Some(Item::Symbol {
+                name: "init".to_string(),
+                kind: "fn",
+                body: None,
+            }),
+            "typealias_declaration" => self.symbol(node, "type", None),
+            _ => None,
+        }
+    }
+
+    fn markdown_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        match node.kind() {
+            // A section spans its heading and everything up to the next
+            // heading of the same or a higher level
+            "section" => {
+                let (name, kind) = self.markdown_heading(node.named_child(0)?)?;
+                Some(Item::Symbol {
+                    name,
+                    kind,
+                    body: Some(node),
+                })
+            }
+            // Setext headings other than the one opening a section
+            "setext_heading"
+                if node
+                    .parent()
+                    .is_none_or(|p| p.kind() != "section" || p.named_child(0) != Some(node)) =>
+            {
+                let (name, kind) = self.markdown_heading(node)?;
+                Some(Item::Symbol {
+                    name,
+                    kind,
+                    body: None,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Text and level (`h1`..`h6`) of an ATX (`## Usage`) or setext
+    /// (`Usage` over `-----`) heading.
+    fn markdown_heading(&self, heading: Node<'_>) -> Option<(String, &'static str)> {
+        let kind = match heading.kind() {
+            "atx_heading" => match heading.named_child(0)?.kind() {
+                "atx_h1_marker" => "h1",
+                "atx_h2_marker" => "h2",
+                "atx_h3_marker" => "h3",
+                "atx_h4_marker" => "h4",
+                "atx_h5_marker" => "h5",
+                _ => "h6",
+            },
+            "setext_heading" => match heading.named_child(1).map(|u| u.kind()) {
+                Some("setext_h1_underline") => "h1",
+                _ => "h2",
+            },
+            _ => return None,
+        };
+        let name = heading
+            .child_by_field_name("heading_content")
+            .map_or("", |c| self.text(c).trim());
+        Some((name.to_string(), kind))
+    }
+
+    fn toml_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        let kind = match node.kind() {
+            "table" => "table",
+            "table_array_element" => "array",
+            // Top-level keys; keys inside tables are not listed
+            "pair" => "key",
+            _ => return None,
+        };
+        let key = node.named_child(0)?;
+        Some(Item::Symbol {
+            name: self.text(key).to_string(),
+            kind,
+            body: None,
+        })
+    }
+
+    /// YAML mappings and JSON objects: keys nest under their parent key.
+    fn data_item<'t>(&self, node: Node<'t>) -> Option<Item<'t>> {
+        match node.kind() {
+            "block_mapping_pair" | "pair" => {
+                let key = self.text(node.child_by_field_name("key")?);
+                let name = key.trim().trim_matches(|c| c == '"' || c == '\'');
+                Some(Item::Symbol {
+                    name: name.to_string(),
+                    kind: "key",
+                    // Keys inside list items are not listed
+                    body: node
+                        .child_by_field_name("value")
+                        .filter(|v| v.kind() != "array"),
+                })
+            }
+            "stream" | "document" | "block_node" | "block_mapping" | "object" => {
+                Some(Item::Transparent(node))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(content: &str, file_type: &str) -> Vec<(String, String, usize, usize)> {
+        extract(content, file_type)
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.kind, s.name, s.line, s.level))
+            .collect()
+    }
+
+    fn expect(items: &[(&str, &str, usize, usize)]) -> Vec<(String, String, usize, usize)> {
+        items
+            .iter()
+            .map(|(k, n, l, lv)| (k.to_string(), n.to_string(), *l, *lv))
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_braces_in_strings_and_tab_indent() {
+        let content = "impl Parser {\n\tpub(crate) fn open(&self) -> &str {\n\t\t\"}\" // }\n\t}\n\tfn close(&self) {}\n}\nmod inner {\n\tpub struct Item;\n}\n";
+        assert_eq!(
+            summary(content, "rs"),
+            expect(&[
+                ("impl", "Parser", 1, 0),
+                ("fn", "open", 2, 1),
+                ("fn", "close", 5, 1),
+                ("mod", "inner", 7, 0),
+                ("struct", "Item", 8, 1),
+            ])
+        );
+        let symbols = extract(content, "rs").unwrap();
+        assert_eq!(symbols[0].end_line, Some(6));
+        assert_eq!(symbols[1].end_line, Some(4));
+    }
+
+    #[test]
+    fn test_python_decorators_and_nested_classes() {
+        let content = "@dataclass\nclass Outer:\n  class Inner:\n    def run(self): pass\n  @property\n  def name(self):\n    return 1\n";
+        assert_eq!(
+            summary(content, "py"),
+            expect(&[
+                ("class", "Outer", 2, 0),
+                ("class", "Inner", 3, 1),
+                ("fn", "run", 4, 2),
+                ("fn", "name", 6, 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_typescript_arrow_functions_and_declarations() {
+        let content = "export const handler = async (req: Req) => {\n  return req;\n};\nexport interface Opts { a: string }\ntype Id = string;\nexport default class Api {\n  private fetch = () => 1;\n  get(id: Id) { return id; }\n}\nconst limit = 3;\n";
+        assert_eq!(
+            summary(content, "ts"),
+            expect(&[
+                ("fn", "handler", 1, 0),
+                ("iface", "Opts", 4, 0),
+                ("type", "Id", 5, 0),
+                ("class", "Api", 6, 0),
+                ("fn", "fetch", 7, 1),
+                ("fn", "get", 8, 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_go_types_and_methods() {
+        let content = "package main\n\ntype Server struct {\n\tAddr string\n}\n\ntype Handler interface {\n\tServe()\n}\n\nfunc (s *Server) Start() error {\n\treturn nil\n}\n";
+        assert_eq!(
+            summary(content, "go"),
+            expect(&[
+                ("struct", "Server", 3, 0),
+                ("iface", "Handler", 7, 0),
+                ("fn", "Start", 11, 0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_java_classes_and_members() {
+        let content = "package a;\n@Service\npublic class Foo extends Bar {\n  private int x;\n  public Foo() {}\n  void run() {\n  }\n  interface Listener { void on(); }\n  enum Mode { A; void m() {} }\n}\n";
+        assert_eq!(
+            summary(content, "java"),
+            expect(&[
+                ("class", "Foo", 2, 0),
+                ("fn", "Foo", 5, 1),
+                ("fn", "run", 6, 1),
+                ("iface", "Listener", 8, 1),
+                ("fn", "on", 8, 2),
+                ("enum", "Mode", 9, 1),
+                ("fn", "m", 9, 2),
+            ])
+        );
+        assert_eq!(extract(content, "java").unwrap()[2].end_line, Some(7));
+    }
+
+    #[test]
+    fn test_c_functions_structs_and_header_guards() {
+        let content = "#ifndef UTIL_H\n#define UTIL_H\nstruct point { int x; };\ntypedef struct { int a; } pair_t;\nint decl(void);\n#endif\nstatic int *make(int a) {\n  return 0;\n}\n";
+        assert_eq!(
+            summary(content, "c"),
+            expect(&[
+                ("struct", "point", 3, 0),
+                ("type", "pair_t", 4, 0),
+                ("fn", "decl", 5, 0),
+                ("fn", "make", 7, 0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cpp_namespaces_classes_and_methods() {
+        let content = "namespace app {\nclass Widget : public Base {\npublic:\n  Widget();\n  void draw() const override { }\n};\nvoid Widget::resize(int w) {\n}\ntemplate <typename T> T clamp(T a) { return a; }\n}\n";
+        assert_eq!(
+            summary(content, "cpp"),
+            expect(&[
+                ("mod", "app", 1, 0),
+                ("class", "Widget", 2, 1),
+                ("fn", "Widget", 4, 2),
+                ("fn", "draw", 5, 2),
+                ("fn", "resize", 7, 1),
+                ("fn", "clamp", 9, 1),
+            ])
+        );
+        assert_eq!(extract(content, "cpp").unwrap()[1].end_line, Some(6));
+    }
+
+    #[test]
+    fn test_csharp_namespaces_and_members() {
+        let content = "namespace App.Core {\n  public interface IRepo { void Save(); }\n  public class Repo : IRepo {\n    public Repo() {}\n    public void Save() {\n    }\n  }\n}\n";
+        assert_eq!(
+            summary(content, "cs"),
+            expect(&[
+                ("mod", "App.Core", 1, 0),
+                ("iface", "IRepo", 2, 1),
+                ("fn", "Save", 2, 2),
+                ("class", "Repo", 3, 1),
+                ("fn", "Repo", 4, 2),
+                ("fn", "Save", 5, 2),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_ruby_modules_classes_and_methods() {
+        let content = "module Billing\n  class Invoice < Base\n    def total\n      1\n    end\n    def self.build; end\n  end\nend\n";
+        assert_eq!(
+            summary(content, "rb"),
+            expect(&[
+                ("mod", "Billing", 1, 0),
+                ("class", "Invoice", 2, 1),
+                ("fn", "total", 3, 2),
+                ("fn", "build", 6, 2),
+            ])
+        );
+        assert_eq!(extract(content, "rb").unwrap()[2].end_line, Some(5));
+    }
+
+    #[test]
+    fn test_php_classes_traits_and_functions() {
+        let content = "<?php\nnamespace App;\ninterface Repo { public function save(); }\nclass User extends Model {\n  public function name(): string {\n    return '}';\n  }\n}\ntrait Loggable {}\nfunction helper() {}\n";
+        assert_eq!(
+            summary(content, "php"),
+            expect(&[
+                ("mod", "App", 2, 0),
+                ("iface", "Repo", 3, 0),
+                ("fn", "save", 3, 1),
+                ("class", "User", 4, 0),
+                ("fn", "name", 5, 1),
+                ("trait", "Loggable", 9, 0),
+                ("fn", "helper", 10, 0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_swift_types_extensions_and_protocols() {
+        let content = "protocol Shape {\n  func area() -> Double\n}\nstruct Circle: Shape {\n  func area() -> Double { return 1 }\n}\nclass View {\n  init() {}\n}\nextension View {\n  func hide() {}\n}\nfunc top() {}\n";
+        assert_eq!(
+            summary(content, "swift"),
+            expect(&[
+                ("iface", "Shape", 1, 0),
+                ("fn", "area", 2, 1),
+                ("struct", "Circle", 4, 0),
+                ("fn", "area", 5, 1),
+                ("class", "View", 7, 0),
+                ("fn", "init", 8, 1),
+                ("impl", "View", 10, 0),
+                ("fn", "hide", 11, 1),
+                ("fn", "top", 13, 0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_markdown_sections_nest_by_heading_level() {
+        let content = "# Title\n\nIntro\n\n## Install\n\n```sh\n# not a heading\n```\n\n### From source\nx\n\n## Usage\n\ny\n";
+        let symbols = extract(content, "md").unwrap();
+        let ranges: Vec<_> = symbols
+            .iter()
+            .map(|s| {
+                (
+                    s.kind.as_str(),
+                    s.name.as_str(),
+                    s.line,
+                    s.end_line,
+                    s.level,
+                )
+            })
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                ("h1", "Title", 1, Some(16), 0),
+                ("h2", "Install", 5, Some(12), 1),
+                ("h3", "From source", 11, Some(12), 2),
+                ("h2", "Usage", 14, Some(16), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markdown_setext_headings_open_sections() {
+        let content = "Intro\n=====\n\ntext\n\nUsage\n-----\n\n## Sub\n\nx\n\n# Atx\n";
+        let symbols = extract(content, "md").unwrap();
+        let ranges: Vec<_> = symbols
+            .iter()
+            .map(|s| (s.kind.as_str(), s.name.as_str(), s.line, s.level))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                ("h1", "Intro", 1, 0),
+                ("h2", "Usage", 6, 1),
+                // The grammar ends a setext section at the next ATX heading
+                ("h2", "Sub", 9, 0),
+                ("h1", "Atx", 13, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toml_tables_and_arrays_of_tables() {
+        let content = "name = \"x\"\n\n[package]\nversion = \"1\"\n\n[dependencies.serde]\nversion = \"1\"\n\n[[bin]]\nname = \"a\"\n";
+        let symbols = extract(content, "toml").unwrap();
+        let ranges: Vec<_> = symbols
+            .iter()
+            .map(|s| (s.kind.as_str(), s.name.as_str(), s.line, s.end_line))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                ("key", "name", 1, Some(1)),
+                ("table", "package", 3, Some(4)),
+                ("table", "dependencies.serde", 6, Some(7)),
+                ("array", "bin", 9, Some(10)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yaml_keys_up_to_depth() {
+        let content = "version: 2\nservices:\n  web:\n    image: nginx\n    ports:\n      - 80\n  db:\n    environment:\n      DEEP: 1\n";
+        let symbols = extract(content, "yaml").unwrap();
+        assert_eq!(
+            summary(content, "yaml"),
+            expect(&[
+                ("key", "version", 1, 0),
+                ("key", "services", 2, 0),
+                ("key", "web", 3, 1),
+                ("key", "image", 4, 2),
+                ("key", "ports", 5, 2),
+                ("key", "db", 7, 1),
+                ("key", "environment", 8, 2),
+            ])
+        );
+        assert_eq!(symbols[1].end_line, Some(9));
+        assert_eq!(symbols[4].end_line, Some(6));
+    }
+
+    #[test]
+    fn test_json_keys_nest_under_objects() {
+        let content = "{\n  \"name\": \"x\",\n  \"scripts\": {\n    \"build\": \"tsc\"\n  },\n  \"list\": [{\"a\": 1}]\n}\n";
+        let symbols = extract(content, "json").unwrap();
+        assert_eq!(
+            summary(content, "json"),
+            expect(&[
+                ("key", "name", 2, 0),
+                ("key", "scripts", 3, 0),
+                ("key", "build", 4, 1),
+                ("key", "list", 6, 0),
+            ])
+        );
+        assert_eq!(symbols[1].end_line, Some(5));
+    }
+
+    #[test]
+    fn test_unsupported_file_type() {
+        assert!(extract("fn main() {}", "txt").is_none());
+        assert!(extract("fun main() {}", "kt").is_none());
+    }
+}
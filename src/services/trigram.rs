@@ -117,6 +117,27 @@ impl TrigramIndex {
         Some(result)
     }
 
+    /// Counts, per file, how many of the query's distinct 3-byte windows it contains.
+    ///
+    /// Every 3-byte window is a valid sparse n-gram (no interior bigram to
+    /// dominate), so each one is indexed. Unlike `search`, this is an OR
+    /// with counts: a typo breaks at most three windows per edit, so files
+    /// containing a near-miss of the query still share most of them.
+    #[must_use]
+    pub fn shared_trigram_counts(&self, query: &str) -> AHashMap<u32, usize> {
+        let windows: AHashSet<&[u8]> = query.as_bytes().windows(3).collect();
+        let mut counts: AHashMap<u32, usize> = AHashMap::new();
+        for window in windows {
+            let key = NgramKey::new(ngram::ngram_key(window));
+            if let Some(bitmap) = self.index.get(&key) {
+                for id in bitmap {
+                    *counts.entry(id).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
     /// Returns the number of unique n-gram keys indexed.
     #[must_use]
     pub fn trigram_count(&self) -> usize {
@@ -296,6 +317,18 @@ mod tests {
         assert!(!results.contains(1));
     }
 
+    #[test]
+    fn test_shared_trigram_counts_tolerates_typos() {
+        let mut index = TrigramIndex::new();
+        index.add_file(FileId::new(1), "struct SearchService;");
+        index.add_file(FileId::new(2), "fn unrelated() {}");
+
+        // "SearchSerivce" keeps 7 of its 11 windows ("Sea" .. "Ser")
+        let counts = index.shared_trigram_counts("SearchSerivce");
+        assert_eq!(counts.get(&1), Some(&7));
+        assert!(counts.get(&2).is_none_or(|&n| n <= 1));
+    }
+
     #[test]
     fn test_short_query() {
         let mut index = TrigramIndex::new();
@@ -2,18 +2,26 @@
 //!
 //! # Security
 //!
-//! The `refs` tool validates paths to prevent traversal
-//! attacks and blocks access to sensitive files.
+//! The `refs` and `tests_for` tools skip sensitive files, and `tests_for`
+//! validates file targets to prevent traversal attacks.
 //!
 //! See [`crate::security`] for details.
 
-use crate::security;
+use super::search::RegionFilter;
+use super::symbols::{is_test_path, prominence, relativize, split_qualified, CANDIDATE_POOL};
+use crate::db::{escape_like, SymbolData};
+use crate::error::ServerError;
+use crate::security::{self, MAX_PATTERN_LENGTH};
+use crate::services::lexer::{self, Region};
+use crate::services::symbols;
 use crate::services::{Indexer, SearchService};
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 /// Classification of how a symbol is used at a reference site.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,6 +29,10 @@ pub enum RefKind {
     Definition,
     Import,
     TypeUsage,
+    Call,
+    FieldAccess,
+    TraitImpl,
+    Comment,
     Usage,
 }
 
@@ -30,6 +42,10 @@ impl fmt::Display for RefKind {
             Self::Definition => f.write_str("definition"),
             Self::Import => f.write_str("import"),
             Self::TypeUsage => f.write_str("type_usage"),
+            Self::Call => f.write_str("call"),
+            Self::FieldAccess => f.write_str("field_access"),
+            Self::TraitImpl => f.write_str("trait_impl"),
+            Self::Comment => f.write_str("comment"),
             Self::Usage => f.write_str("usage"),
         }
     }
@@ -108,14 +124,36 @@ pub fn execute_stats(
     })
 }
 
+/// Files grepped for references; every one is read so that files can be
+/// ranked before the limit applies.
+const REFS_FILE_POOL: usize = 500;
+
 /// Input for the refs tool (find references).
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct RefsInput {
-    /// Symbol/identifier to find references for
+    /// Symbol to find references for: a bare name, or qualified
+    /// (`Type::method`, `module.func`, `obj.method`) to keep only uses
+    /// tied to that type, module or receiver. A leading `.` keeps member
+    /// accesses, a trailing `(` calls.
+    #[serde(default)]
     pub symbol: String,
-    /// Maximum references to return
+    /// Further symbols looked up in the same pass; results are then
+    /// grouped per symbol, with `limit` applying to each
+    #[serde(default)]
+    pub symbols: Vec<String>,
+    /// Maximum references to return (files, in summary mode)
     #[serde(default = "default_refs_limit")]
     pub limit: usize,
+    /// Keep only references in code, comments or string literals
+    #[serde(default)]
+    pub filter: RegionFilter,
+    /// Return per-file counts by reference type and the definition sites
+    /// instead of every reference
+    #[serde(default)]
+    pub summary: bool,
+    /// Only list references in this file (relative to root)
+    #[serde(default)]
+    pub path: Option<String>,
 }
 
 const fn default_refs_limit() -> usize {
@@ -125,12 +163,49 @@ const fn default_refs_limit() -> usize {
 /// Output for the refs tool.
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct RefsOutput {
-    /// References found
+    /// References found, most relevant files first (empty in summary mode)
     pub references: Vec<Reference>,
+    /// Per-file counts, most relevant first (summary mode only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<RefsFileSummary>,
+    /// Definition sites (summary mode only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub definitions: Vec<Reference>,
+    /// References across all files, before the limit
+    pub total: usize,
+    /// Whether references (or files, in summary mode) were cut at the limit
+    pub has_more: bool,
+    /// Hint on narrowing or drilling into the results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// Results per symbol, in request order (batch lookups only; totals
+    /// above are summed across them)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<RefsGroup>,
 }
 
-/// A reference to a symbol.
+/// One symbol's results in a batch refs lookup.
 #[derive(Debug, Serialize, JsonSchema)]
+pub struct RefsGroup {
+    /// The symbol as requested
+    pub symbol: String,
+    #[serde(flatten)]
+    pub refs: RefsOutput,
+}
+
+/// Reference counts for one file.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RefsFileSummary {
+    /// File path
+    pub path: String,
+    /// References in the file
+    pub total: usize,
+    /// Count per reference type
+    pub counts: BTreeMap<String, usize>,
+}
+
+/// A reference to a symbol.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct Reference {
     /// File path
     pub path: String,
@@ -138,68 +213,1136 @@ pub struct Reference {
     pub line: usize,
     /// Line content
     pub content: String,
-    /// Reference type (definition, usage, import)
+    /// Reference type (definition, import, type_usage, call, field_access,
+    /// trait_impl, comment, usage)
     pub ref_type: String,
+    /// Region of the match (code, comment, string)
+    pub region: String,
+    /// Innermost indexed symbol containing the reference (`Type::method`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enclosing: Option<String>,
+    /// How a qualified symbol's reference was matched (qualified,
+    /// definition, self, import, receiver_type, unique_name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
 }
 
+/// Most symbols looked up in one refs call.
+const MAX_REFS_SYMBOLS: usize = 20;
+
 /// Executes the refs tool.
 ///
+/// Files are ranked by relevance: those defining the symbol first, test
+/// files last, then by reference count and path. With `symbols`, every
+/// symbol is looked up in one grep pass and the results are grouped per
+/// symbol, each under its own `limit`.
+///
 /// # Security
 ///
 /// - Search results are filtered to exclude sensitive files
 /// - Grep already constrains search to the root directory
+/// - A `path` is validated to stay within the root and not be sensitive
 ///
 /// # Errors
 ///
-/// Returns a `ServerError` if the grep search fails.
+/// Returns a `ServerError` if no or too many symbols are given, the grep
+/// search fails or `path` is invalid.
 pub fn execute_refs(
     service: &Arc<SearchService>,
     input: RefsInput,
 ) -> crate::error::Result<RefsOutput> {
-    // Use grep to find exact symbol matches, keeping raw GrepMatch data
-    // to avoid re-reading files (the old approach doubled I/O).
-    let matches_by_file = service.search_grep_with_matches(
-        &format!(r"\b{}\b", regex::escape(&input.symbol)),
-        input.limit * 2,
-    )?;
-
     let root = service.root();
-    let mut references = Vec::new();
+    let mut symbols: Vec<&str> = Vec::new();
+    for symbol in std::iter::once(&input.symbol).chain(&input.symbols) {
+        let symbol = symbol.trim();
+        if !symbol.is_empty() && !symbols.contains(&symbol) {
+            symbols.push(symbol);
+        }
+    }
+    if symbols.is_empty() {
+        return Err(ServerError::Tool("refs needs a symbol".into()));
+    }
+    if symbols.len() > MAX_REFS_SYMBOLS {
+        return Err(ServerError::Tool(format!(
+            "refs takes at most {MAX_REFS_SYMBOLS} symbols at once, got {}",
+            symbols.len()
+        )));
+    }
+    let mut lookups = symbols
+        .iter()
+        .map(|symbol| RefLookup::new(service, symbol))
+        .collect::<crate::error::Result<Vec<_>>>()?;
 
-    for (path, matches) in &matches_by_file {
-        // Security: skip sensitive files from search results
-        if security::is_sensitive_file(path).is_some() {
-            continue;
+    // Grep finds the candidate files. Each is then read whole: comment and
+    // string state spans lines, and grep keeps only a few matches per file.
+    let paths: Vec<PathBuf> = match &input.path {
+        Some(path) => {
+            let resolved = security::validate_read_access(root, path)?;
+            if resolved.is_dir() {
+                return Err(ServerError::Tool(
+                    "refs path must be a file, not a directory".into(),
+                ));
+            }
+            let canonical_root = dunce::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+            let rel = resolved.strip_prefix(&canonical_root).unwrap_or(&resolved);
+            vec![root.join(rel)]
+        }
+        None => {
+            let mut names: Vec<&str> = Vec::new();
+            for lookup in &lookups {
+                if !names.contains(&lookup.target.name) {
+                    names.push(lookup.target.name);
+                }
+            }
+            let alternation: Vec<String> = names.iter().map(|n| regex::escape(n)).collect();
+            let pattern = format!(r"\b(?:{})\b", alternation.join("|"));
+            service
+                .search_grep_any_with_matches(&pattern, &names, REFS_FILE_POOL * names.len())?
+                .into_keys()
+                .filter(|path| security::is_sensitive_file(path).is_none())
+                .map(|path| path.to_path_buf())
+                .collect()
+        }
+    };
+
+    let mut found: Vec<Vec<FileRefs>> = vec![Vec::new(); lookups.len()];
+    for path in &paths {
+        let per_lookup = file_references(service, path, &mut lookups, &input)?;
+        let rel = relativize(&path.to_string_lossy(), root);
+        for (files, refs) in found.iter_mut().zip(per_lookup) {
+            if !refs.is_empty() {
+                files.push((rel.clone(), refs));
+            }
+        }
+    }
+    let mut groups: Vec<RefsGroup> = lookups
+        .iter()
+        .zip(found)
+        .map(|(lookup, files)| RefsGroup {
+            symbol: lookup.symbol.to_string(),
+            refs: ranked_refs(files, &input, lookup),
+        })
+        .collect();
+
+    if input.symbols.is_empty() {
+        return Ok(groups.remove(0).refs);
+    }
+    Ok(RefsOutput {
+        references: Vec::new(),
+        files: Vec::new(),
+        definitions: Vec::new(),
+        total: groups.iter().map(|g| g.refs.total).sum(),
+        has_more: groups.iter().any(|g| g.refs.has_more),
+        hint: None,
+        groups,
+    })
+}
+
+/// A file's relative path and its classified references to one symbol.
+type FileRefs = (String, Vec<(RefKind, Reference)>);
+
+/// One symbol's references, files ranked and cut at the limit.
+fn ranked_refs(mut files: Vec<FileRefs>, input: &RefsInput, lookup: &RefLookup) -> RefsOutput {
+    files.sort_by_cached_key(|(rel, refs)| {
+        let defines = refs.iter().any(|(kind, _)| *kind == RefKind::Definition);
+        (
+            !defines,
+            is_test_path(rel),
+            std::cmp::Reverse(refs.len()),
+            rel.clone(),
+        )
+    });
+    let total = files.iter().map(|(_, refs)| refs.len()).sum();
+    let unresolved = unresolved_hint(&lookup.target, lookup.unresolved);
+
+    if input.summary {
+        let has_more = files.len() > input.limit;
+        let mut summaries = Vec::new();
+        let mut definitions = Vec::new();
+        for (rel, refs) in files {
+            let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+            for (kind, reference) in refs.iter() {
+                *counts.entry(kind.to_string()).or_default() += 1;
+                if *kind == RefKind::Definition {
+                    definitions.push(reference.clone());
+                }
+            }
+            if summaries.len() < input.limit {
+                summaries.push(RefsFileSummary {
+                    path: rel,
+                    total: refs.len(),
+                    counts,
+                });
+            }
         }
+        let hint = summaries
+            .first()
+            .map(|f| {
+                format!(
+                    "Pass path (e.g. '{}') to list one file's references.",
+                    f.path
+                )
+            })
+            .into_iter()
+            .chain(unresolved)
+            .reduce(|a, b| format!("{a} {b}"));
+        return RefsOutput {
+            references: Vec::new(),
+            files: summaries,
+            definitions,
+            total,
+            has_more,
+            hint,
+            groups: Vec::new(),
+        };
+    }
 
-        let relative = path
-            .strip_prefix(root)
-            .unwrap_or(path)
-            .to_string_lossy()
-            .to_string();
+    let references: Vec<Reference> = files
+        .into_iter()
+        .flat_map(|(_, refs)| refs)
+        .map(|(_, reference)| reference)
+        .take(input.limit)
+        .collect();
+    let has_more = total > references.len();
+    let hint = (has_more && input.path.is_none())
+        .then(|| {
+            "More references than the limit. Use summary=true for per-file counts, then path to drill in."
+                .to_string()
+        })
+        .into_iter()
+        .chain(unresolved)
+        .reduce(|a, b| format!("{a} {b}"));
+    RefsOutput {
+        references,
+        files: Vec::new(),
+        definitions: Vec::new(),
+        total,
+        has_more,
+        hint,
+        groups: Vec::new(),
+    }
+}
+
+/// One symbol being looked up: its target, word matcher and index facts.
+struct RefLookup<'a> {
+    symbol: &'a str,
+    target: RefTarget<'a>,
+    word: Regex,
+    /// The qualifier owns every indexed method of the name, so bare
+    /// `.name(` calls can be tied to it
+    unique: bool,
+    /// Lines using the name on receivers of unknown type
+    unresolved: usize,
+}
 
-        for m in matches {
-            let trimmed = m.line_content.trim();
-            let ref_type = classify_reference(trimmed, &input.symbol);
+impl<'a> RefLookup<'a> {
+    fn new(service: &SearchService, symbol: &'a str) -> crate::error::Result<Self> {
+        let target = RefTarget::parse(symbol);
+        if target.name.is_empty() {
+            return Err(ServerError::Tool(format!(
+                "'{symbol}' names no symbol to find references for"
+            )));
+        }
+        let word = Regex::new(&format!(r"\b{}\b", regex::escape(target.name)))
+            .map_err(|e| ServerError::Tool(e.to_string()))?;
+        let unique = match target.qualifier {
+            Some(qualifier) => {
+                let owners: Vec<Option<String>> = service
+                    .db()
+                    .find_symbols(&escape_like(target.name), None, None, CANDIDATE_POOL)?
+                    .into_iter()
+                    .filter(|(_, sym)| sym.name == target.name && sym.kind != "impl")
+                    .map(|(_, sym)| sym.parent)
+                    .collect();
+                !owners.is_empty() && owners.iter().all(|p| p.as_deref() == Some(qualifier))
+            }
+            None => false,
+        };
+        Ok(Self {
+            symbol,
+            target,
+            word,
+            unique,
+            unresolved: 0,
+        })
+    }
+}
+
+/// Every reference to each lookup's target in one file, classified. The
+/// file is read, lexed and its symbols loaded once for all lookups.
+fn file_references(
+    service: &SearchService,
+    path: &Path,
+    lookups: &mut [RefLookup],
+    input: &RefsInput,
+) -> crate::error::Result<Vec<Vec<(RefKind, Reference)>>> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Ok(vec![Vec::new(); lookups.len()]);
+    };
+    let db = service.db();
+    let relative = relativize(&path.to_string_lossy(), service.root());
+    let file_type = symbols::detect_file_type(path);
+    let regions = lexer::lex(&content, &file_type);
+    // Indexed files classify by scope; others by line rules alone
+    let file_symbols = match db.get_file_id(&path.to_string_lossy())? {
+        Some(file_id) => db.get_file_symbols(file_id)?,
+        None => Vec::new(),
+    };
+
+    let mut per_lookup = Vec::with_capacity(lookups.len());
+    for lookup in lookups.iter_mut() {
+        let target = &lookup.target;
+        let name = target.name;
+        let mut scope = target.qualifier.map(|qualifier| {
+            QualifiedScope::new(
+                qualifier,
+                name,
+                path,
+                &content,
+                &file_type,
+                &file_symbols,
+                lookup.unique,
+            )
+        });
+
+        let mut references = Vec::new();
+        for (i, text) in content.lines().enumerate() {
+            let line = i + 1;
+            // The first match in a region the filter keeps, of the target's shape
+            let mut unknown = false;
+            let Some((at, region, via)) = lookup
+                .word
+                .find_iter(text)
+                .map(|m| (m.start(), regions.at_line(line, m.start())))
+                .filter(|&(at, region)| {
+                    input.filter.allows(region) && target.fits(text, at, &file_type)
+                })
+                .find_map(|(at, region)| match scope.as_mut() {
+                    None => Some((at, region, None)),
+                    Some(scope) => match scope.verdict(line, text, at, region) {
+                        Verdict::Match(via) => Some((at, region, Some(via))),
+                        Verdict::Unknown => {
+                            unknown = true;
+                            None
+                        }
+                        Verdict::Other => None,
+                    },
+                })
+            else {
+                lookup.unresolved += usize::from(unknown);
+                continue;
+            };
+            let trimmed = text.trim();
+            let ref_type = match (region, via) {
+                (Region::Comment, _) => RefKind::Comment,
+                (Region::String, _) => RefKind::Usage,
+                // Only the matched use counts: the line may also bind or
+                // declare the bare name (`let conn = self.conn()`)
+                (Region::Code, Some(via))
+                    if via != MatchVia::Definition && !is_import(trimmed, &file_type) =>
+                {
+                    occurrence_kind(text, at, name, &file_type).unwrap_or(RefKind::Usage)
+                }
+                (Region::Code, _) => indexed_reference(&file_symbols, line, trimmed, name)
+                    .unwrap_or_else(|| classify_reference(trimmed, name, &file_type)),
+            };
+            references.push((
+                ref_type,
+                Reference {
+                    path: relative.clone(),
+                    line,
+                    content: trim_around_match(trimmed, name),
+                    ref_type: ref_type.to_string(),
+                    region: region.to_string(),
+                    enclosing: enclosing_symbol(&file_symbols, line, name),
+                    via: via.map(|via| via.to_string()),
+                },
+            ));
+        }
+        per_lookup.push(references);
+    }
+    Ok(per_lookup)
+}
+
+/// Hint on uses a qualified target could not be tied to, if any.
+fn unresolved_hint(target: &RefTarget, unresolved: usize) -> Option<String> {
+    let qualifier = target.qualifier?;
+    (unresolved > 0).then(|| {
+        format!(
+            "Skipped {unresolved} line(s) using '.{}' on receivers not known to be {qualifier}; \
+             search for the bare name to see them.",
+            target.name
+        )
+    })
+}
+
+/// What refs looks for: a bare name, or one narrowed by a qualifier
+/// (`Type::method`, `module.func`, `obj.method`), to member accesses
+/// (`.method`) or to calls (`method(`).
+struct RefTarget<'a> {
+    name: &'a str,
+    /// Innermost qualifier (`Type` of `crate::db::Type::method`)
+    qualifier: Option<&'a str>,
+    /// Only member accesses (`value.name`)
+    member: bool,
+    /// Only calls (`name(..)`)
+    call: bool,
+}
+
+impl<'a> RefTarget<'a> {
+    fn parse(symbol: &'a str) -> Self {
+        let symbol = symbol.trim();
+        let (symbol, call) = match symbol
+            .strip_suffix("()")
+            .or_else(|| symbol.strip_suffix('('))
+        {
+            Some(rest) => (rest, true),
+            None => (symbol, false),
+        };
+        let (symbol, member) = match symbol.strip_prefix('.') {
+            Some(rest) => (rest, true),
+            None => (symbol, false),
+        };
+        let (qualifier, name) = split_qualified(symbol);
+        Self {
+            name,
+            qualifier,
+            member,
+            call,
+        }
+    }
+
+    /// Whether the occurrence at `at` has the requested shape.
+    fn fits(&self, text: &str, at: usize, file_type: &str) -> bool {
+        let after = text[at + self.name.len()..].trim_start();
+        (!self.member || qualifier_before(text, at, file_type).is_some_and(|q| !q.path))
+            && (!self.call
+                || after.starts_with('(')
+                || after.starts_with("::<")
+                || after.starts_with('!'))
+    }
+}
+
+/// How an occurrence was tied to a qualified target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatchVia {
+    /// Written through the qualifier (`Type::name`, `module.name`)
+    Qualified,
+    /// A declaration of the name inside the qualifier
+    Definition,
+    /// Through `self`/`Self`/`this`, or unqualified, inside the qualifier
+    SelfMember,
+    /// Imported from the qualifier, or used after such an import
+    Import,
+    /// On a receiver declared or constructed with the qualifier's type
+    ReceiverType,
+    /// On any receiver: the qualifier owns every indexed method of the name
+    UniqueName,
+}
+
+impl fmt::Display for MatchVia {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Qualified => "qualified",
+            Self::Definition => "definition",
+            Self::SelfMember => "self",
+            Self::Import => "import",
+            Self::ReceiverType => "receiver_type",
+            Self::UniqueName => "unique_name",
+        })
+    }
+}
 
-            references.push(Reference {
-                path: relative.clone(),
-                line: m.line_number as usize,
-                content: trim_around_match(trimmed, &input.symbol),
-                ref_type: ref_type.to_string(),
+/// Whether an occurrence refers to a qualified target.
+enum Verdict {
+    Match(MatchVia),
+    /// A member use on a receiver whose type could not be determined
+    Unknown,
+    Other,
+}
+
+/// What precedes an occurrence: a `Type::`/`module::` path, or a `value.`
+/// member access. `word` is `None` for members of expressions (`f().name`).
+struct Qualifier<'a> {
+    path: bool,
+    word: Option<&'a str>,
+}
+
+fn qualifier_before<'a>(text: &'a str, at: usize, file_type: &str) -> Option<Qualifier<'a>> {
+    let before = text[..at].trim_end();
+    let (rest, path) = match before.strip_suffix("::") {
+        Some(rest) => (rest, true),
+        None => {
+            let arrow = matches!(file_type, "c" | "h" | "cpp" | "cc" | "hpp" | "php");
+            let rest = before
+                .strip_suffix("?.")
+                .or_else(|| before.strip_suffix('.'))
+                .or_else(|| arrow.then(|| before.strip_suffix("->")).flatten())?;
+            (rest, false)
+        }
+    };
+    let rest = rest.trim_end();
+    let start = rest
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| !is_ident_char(c))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let word = &rest[start..];
+    Some(Qualifier {
+        path,
+        word: (!word.is_empty()).then_some(word),
+    })
+}
+
+/// One file's view of a qualified target.
+struct QualifiedScope<'a> {
+    qualifier: &'a str,
+    content: &'a str,
+    file_type: &'a str,
+    symbols: &'a [SymbolData],
+    /// The file is the qualifier's module (`db.rs` for `db::open`)
+    module_file: bool,
+    /// The file imports the name from the qualifier
+    imports_name: bool,
+    /// The qualifier owns every indexed method of the name
+    unique: bool,
+    /// Receivers already checked for the qualifier's type
+    receivers: HashMap<String, bool>,
+}
+
+impl<'a> QualifiedScope<'a> {
+    fn new(
+        qualifier: &'a str,
+        name: &str,
+        path: &Path,
+        content: &'a str,
+        file_type: &'a str,
+        symbols: &'a [SymbolData],
+        unique: bool,
+    ) -> Self {
+        let imports_name = content.lines().any(|line| {
+            let line = line.trim();
+            is_import(line, file_type)
+                && word_occurrences(line, qualifier).next().is_some()
+                && word_occurrences(line, name).next().is_some()
+        });
+        Self {
+            qualifier,
+            content,
+            file_type,
+            symbols,
+            module_file: path.file_stem().is_some_and(|stem| stem == qualifier),
+            imports_name,
+            unique,
+            receivers: HashMap::new(),
+        }
+    }
+
+    fn verdict(&mut self, line: usize, text: &str, at: usize, region: Region) -> Verdict {
+        let name_end = at
+            + text[at..]
+                .find(|c: char| !is_ident_char(c))
+                .unwrap_or(text.len() - at);
+        let name = &text[at..name_end];
+        let before = qualifier_before(text, at, self.file_type);
+        if before.as_ref().and_then(|q| q.word) == Some(self.qualifier) {
+            return Verdict::Match(MatchVia::Qualified);
+        }
+        if region != Region::Code {
+            return Verdict::Other;
+        }
+        match before {
+            Some(Qualifier {
+                word: Some("self" | "Self" | "this" | "static"),
+                ..
+            }) => self.match_if(self.in_qualifier(line), MatchVia::SelfMember),
+            // `Other::name`, `crate::other::name`
+            Some(Qualifier { path: true, .. }) => Verdict::Other,
+            // `Other.name`: a different class or module
+            Some(Qualifier { word: Some(w), .. }) if w.starts_with(char::is_uppercase) => {
+                Verdict::Other
+            }
+            Some(Qualifier { word: Some(w), .. }) if self.receiver_has_type(w) => {
+                Verdict::Match(MatchVia::ReceiverType)
+            }
+            // Unindexed library types share method names, so only calls on
+            // a named value count
+            Some(Qualifier { word: Some(_), .. })
+                if self.unique && text[name_end..].trim_start().starts_with('(') =>
+            {
+                Verdict::Match(MatchVia::UniqueName)
+            }
+            Some(_) => Verdict::Unknown,
+            None if self.defines(line, text, name) => Verdict::Match(MatchVia::Definition),
+            None if self.imports_name => Verdict::Match(MatchVia::Import),
+            None => {
+                // Implicit `this` calls; Rust has none
+                let call = text[name_end..].trim_start().starts_with('(');
+                self.match_if(
+                    call && self.file_type != "rs" && self.in_qualifier(line),
+                    MatchVia::SelfMember,
+                )
+            }
+        }
+    }
+
+    fn match_if(&self, matched: bool, via: MatchVia) -> Verdict {
+        if matched {
+            Verdict::Match(via)
+        } else {
+            Verdict::Other
+        }
+    }
+
+    /// Whether `line` lies in the qualifier's body: a type declaration or
+    /// `impl` block for it, or a method whose parent it is.
+    fn in_qualifier(&self, line: usize) -> bool {
+        self.symbols
+            .iter()
+            .filter(|s| s.line <= line && line <= s.end_line.unwrap_or(s.line))
+            .any(|s| {
+                let is_type = MEMBER_CONTAINER_KINDS.contains(&s.kind.as_str())
+                    || matches!(s.kind.as_str(), "impl" | "trait");
+                s.parent.as_deref() == Some(self.qualifier)
+                    || (is_type && declared_type(s) == self.qualifier)
+            })
+    }
+
+    /// Whether `line` declares `name` as a member of the qualifier, or as a
+    /// top-level item of the qualifier's module file.
+    fn defines(&self, line: usize, text: &str, name: &str) -> bool {
+        let indexed = self
+            .symbols
+            .iter()
+            .filter(|s| s.line == line && s.name == name && s.kind != "impl")
+            .any(|s| match s.parent.as_deref() {
+                Some(parent) => parent == self.qualifier,
+                None => self.module_file,
             });
+        indexed
+            || (self.symbols.is_empty()
+                && (self.module_file || self.in_qualifier(line))
+                && declared_name(text.trim()).is_some_and(|(_, declared)| declared == name))
+    }
+
+    /// Whether the file declares `receiver` with the qualifier's type
+    /// (`db: &Database`, `Database db`, `db *Database`) or assigns it one
+    /// (`db = Database::open(..)`, `db = new Database()`), or names it
+    /// after the type (`database`).
+    fn receiver_has_type(&mut self, receiver: &str) -> bool {
+        if let Some(&known) = self.receivers.get(receiver) {
+            return known;
+        }
+        let (r, t) = (regex::escape(receiver), regex::escape(self.qualifier));
+        let pattern = format!(
+            r"\b{r}\s*\??:\s*[\w:&<\[ ,]*?\b{t}\b|\b{r}\s*:?=\s*(?:new\s+|&|await\s+)?(?:[\w.]+(?:::|\.))?{t}\b|\b{t}(?:<[^>\n]*>)?[ \t&*]+{r}\b|\b{r}[ \t]+\*?{t}\b"
+        );
+        let known = snake_case(self.qualifier) == receiver
+            || Regex::new(&pattern).is_ok_and(|re| re.is_match(self.content));
+        self.receivers.insert(receiver.to_string(), known);
+        known
+    }
+}
+
+/// Name of the type a symbol declares; for `impl Trait for Type`, `Type`.
+fn declared_type(sym: &SymbolData) -> &str {
+    let name = match sym.kind.as_str() {
+        "impl" => sym.name.rsplit(" for ").next().unwrap_or(&sym.name),
+        _ => &sym.name,
+    };
+    name.split('<').next().unwrap_or(name).trim()
+}
 
-            if references.len() >= input.limit {
-                break;
+/// `SearchService` as `search_service`.
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
             }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
         }
+    }
+    out
+}
 
-        if references.len() >= input.limit {
-            break;
+/// Files grepped for the target's names when looking for tests.
+const TEST_FILE_POOL: usize = 500;
+
+/// Names of a file's declarations looked for in test bodies.
+const MAX_SUBJECT_NAMES: usize = 50;
+
+/// Input for the tests_for tool (test locator).
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TestsForInput {
+    /// Symbol name (optionally qualified) or file path (relative to root)
+    pub target: String,
+    /// Maximum test functions to return
+    #[serde(default = "default_tests_for_limit")]
+    pub limit: usize,
+}
+
+const fn default_tests_for_limit() -> usize {
+    20
+}
+
+/// Output for the tests_for tool.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TestsForOutput {
+    /// The resolved target: a file path, or `path:line name` for a symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Test functions, most relevant first
+    pub tests: Vec<TestHit>,
+    /// Whether the list was cut at the limit
+    pub has_more: bool,
+    /// Hint when the target is unknown or no test was found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// A test function likely to cover the target.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TestHit {
+    /// Test function name, or the `it`/`test` description for JS/TS
+    pub name: String,
+    /// File path
+    pub path: String,
+    /// Line of the test declaration
+    pub line: usize,
+    /// Why it was picked, strongest first
+    pub reasons: Vec<String>,
+}
+
+/// Why a file's tests are candidates, with its ranking weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TestFileReason {
+    /// Tests in the target's own file (`#[cfg(test)] mod tests`)
+    SameFile,
+    /// Named after the target's file (`tests/foo.rs`, `test_foo.py`)
+    NamingConvention,
+    /// Imports the target's file
+    Imports,
+}
+
+impl TestFileReason {
+    const fn weight(self) -> u32 {
+        match self {
+            Self::SameFile | Self::NamingConvention => 2,
+            Self::Imports => 1,
+        }
+    }
+}
+
+impl fmt::Display for TestFileReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SameFile => f.write_str("same file"),
+            Self::NamingConvention => f.write_str("naming convention"),
+            Self::Imports => f.write_str("imports target file"),
+        }
+    }
+}
+
+/// Weight of a test body referencing the target.
+const REFERENCE_WEIGHT: u32 = 4;
+
+/// Executes the tests_for tool.
+///
+/// Gathers candidate test files from the target's own file, test files
+/// named after it, test files importing it and test files mentioning its
+/// name(s), then ranks the test functions in them: bodies referencing the
+/// target count most, then the file-level signals. For a file target the
+/// names are its top-level declarations.
+///
+/// # Security
+///
+/// - Validates a file target stays within the root and is not sensitive
+/// - Results are filtered to exclude sensitive files
+///
+/// # Errors
+///
+/// Returns `ServerError::Tool` for an empty target or a directory,
+/// `ServerError::Security` for an invalid path, and `ServerError::Database`
+/// or a search error if a lookup fails.
+pub fn execute_tests_for(
+    service: &Arc<SearchService>,
+    input: TestsForInput,
+) -> crate::error::Result<TestsForOutput> {
+    let target = input.target.trim();
+    if target.is_empty() {
+        return Err(ServerError::Tool(
+            "tests_for requires a symbol name or file path".into(),
+        ));
+    }
+    let root = service.root();
+    let db = service.db();
+    let empty = |target: Option<String>, hint: String| TestsForOutput {
+        target,
+        tests: Vec::new(),
+        has_more: false,
+        hint: Some(hint),
+    };
+
+    // The files under test and the names their tests would mention
+    let (subjects, names, described) =
+        if target.contains(['/', '\\']) || root.join(target).is_file() {
+            let resolved = security::validate_read_access(root, target)?;
+            if resolved.is_dir() {
+                return Err(ServerError::Tool(
+                    "tests_for expects a file path or symbol name, not a directory".into(),
+                ));
+            }
+            let canonical_root = dunce::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+            let rel = resolved.strip_prefix(&canonical_root).unwrap_or(&resolved);
+            let path = root.join(rel).to_string_lossy().to_string();
+            let Some(file_id) = db.get_file_id(&path)? else {
+                return Ok(empty(
+                    Some(relativize(&path, root)),
+                    "File is not indexed. Run 'index' first.".to_string(),
+                ));
+            };
+            let mut names: Vec<String> = Vec::new();
+            for sym in db.get_file_symbols(file_id)? {
+                if sym.parent.is_none()
+                    && !matches!(sym.kind.as_str(), "impl" | "mod")
+                    && !sym.name.to_lowercase().starts_with("test")
+                    && sym.name.len() >= 3
+                    && !names.contains(&sym.name)
+                {
+                    names.push(sym.name);
+                }
+            }
+            names.truncate(MAX_SUBJECT_NAMES);
+            let described = relativize(&path, root);
+            (vec![path], names, described)
+        } else {
+            let name = split_qualified(target).1;
+            let mut definitions: Vec<(String, SymbolData)> = db
+                .find_symbols(&escape_like(name), None, None, CANDIDATE_POOL)?
+                .into_iter()
+                .filter(|(path, sym)| {
+                    sym.name == name
+                        && security::is_sensitive_file(Path::new(path)).is_none()
+                        && !is_test_path(&relativize(path, root))
+                })
+                .collect();
+            definitions.sort_by_cached_key(|(path, sym)| (prominence(sym), path.clone(), sym.line));
+            let Some((path, sym)) = definitions.first() else {
+                let hint = if db.symbol_count().unwrap_or(0) == 0 {
+                    "No symbols indexed. Run 'index' first.".to_string()
+                } else {
+                    format!(
+                    "No symbol named '{name}' outside test files. Try 'symbols' with mode=fuzzy."
+                )
+                };
+                return Ok(empty(None, hint));
+            };
+            let described = format!("{}:{} {}", relativize(path, root), sym.line, sym.name);
+            let mut subjects: Vec<String> = Vec::new();
+            for (path, _) in &definitions {
+                if !subjects.contains(path) {
+                    subjects.push(path.clone());
+                }
+            }
+            (subjects, vec![name.to_string()], described)
+        };
+
+    // Candidate test files, each with its file-level reasons
+    let mut files: BTreeMap<String, Vec<TestFileReason>> = BTreeMap::new();
+    let mut add = |path: &str, reason: Option<TestFileReason>| {
+        let reasons = files.entry(path.to_string()).or_default();
+        if let Some(reason) = reason.filter(|r| !reasons.contains(r)) {
+            reasons.push(reason);
+        }
+    };
+    let subject_stems: Vec<String> = subjects
+        .iter()
+        .filter_map(|p| subject_stem(&relativize(p, root)))
+        .collect();
+    for path in &subjects {
+        add(path, Some(TestFileReason::SameFile));
+        if let Some(file_id) = db.get_file_id(path)? {
+            for (importer, _) in db.get_importers(file_id)? {
+                if is_test_path(&relativize(&importer, root)) {
+                    add(&importer, Some(TestFileReason::Imports));
+                }
+            }
+        }
+    }
+    for (_, path) in db.get_all_file_paths()? {
+        let rel = relativize(&path, root);
+        if is_test_path(&rel)
+            && test_subject_stem(&rel)
+                .is_some_and(|stem| subject_stems.iter().any(|s| s.eq_ignore_ascii_case(&stem)))
+        {
+            add(&path, Some(TestFileReason::NamingConvention));
+        }
+    }
+    let escaped: Vec<String> = names.iter().map(|n| regex::escape(n)).collect();
+    // Grep patterns are length-capped, so long name lists are split
+    let mut alternations: Vec<String> = Vec::new();
+    for name in &escaped {
+        match alternations.last_mut() {
+            Some(alt) if alt.len() + name.len() < MAX_PATTERN_LENGTH - 10 => {
+                alt.push('|');
+                alt.push_str(name);
+            }
+            _ => alternations.push(name.clone()),
+        }
+    }
+    for alternation in &alternations {
+        let pattern = format!(r"\b(?:{alternation})\b");
+        for path in service
+            .search_grep_with_matches(&pattern, TEST_FILE_POOL)?
+            .keys()
+        {
+            let path = path.to_string_lossy();
+            if is_test_path(&relativize(&path, root)) {
+                add(&path, None);
+            }
+        }
+    }
+    let words = word_regex(&escaped)?;
+
+    // Rank the test functions of every candidate file
+    let mut ranked: Vec<(u32, TestHit)> = Vec::new();
+    for (path, file_reasons) in &files {
+        if security::is_sensitive_file(Path::new(path)).is_some() {
+            continue;
+        }
+        let Some((file_id, content)) = db.get_file_by_path(path)? else {
+            continue;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let file_type = symbols::detect_file_type(Path::new(path));
+        let symbols = db.get_file_symbols(file_id)?;
+        let rel = relativize(path, root);
+        let tests = test_functions(&lines, &symbols, &file_type, is_test_path(&rel));
+
+        // Helpers in test files that reference the target stand in for it
+        let mut helpers: Vec<String> = Vec::new();
+        if let Some(words) = words.as_ref().filter(|_| is_test_path(&rel)) {
+            for sym in symbols.iter().filter(|s| s.kind == "fn") {
+                let end = sym.end_line.unwrap_or(sym.line);
+                if !tests.iter().any(|t| t.line == sym.line)
+                    && !matched_names(words, &lines, sym.line, end).is_empty()
+                {
+                    helpers.push(regex::escape(&sym.name));
+                }
+            }
+        }
+        let helper_calls = word_regex(&helpers)?;
+
+        for test in tests {
+            let mut score: u32 = file_reasons.iter().map(|r| r.weight()).sum();
+            let mut reasons = Vec::new();
+            let referenced = words
+                .as_ref()
+                .map(|w| matched_names(w, &lines, test.line, test.end))
+                .unwrap_or_default();
+            let via = helper_calls
+                .as_ref()
+                .map(|h| matched_names(h, &lines, test.line, test.end))
+                .unwrap_or_default();
+            if !referenced.is_empty() {
+                score += REFERENCE_WEIGHT;
+                reasons.push(format!("references {}", list_names(&referenced)));
+            } else if !via.is_empty() {
+                score += REFERENCE_WEIGHT - 1;
+                reasons.push(format!("via helper {}", list_names(&via)));
+            }
+            if score == 0 {
+                continue;
+            }
+            reasons.extend(file_reasons.iter().map(ToString::to_string));
+            ranked.push((
+                score,
+                TestHit {
+                    name: test.name,
+                    path: rel.clone(),
+                    line: test.line,
+                    reasons,
+                },
+            ));
+        }
+    }
+    ranked.sort_by(|(a, x), (b, y)| {
+        b.cmp(a)
+            .then_with(|| x.path.cmp(&y.path))
+            .then(x.line.cmp(&y.line))
+    });
+    let has_more = ranked.len() > input.limit;
+    let tests: Vec<TestHit> = ranked
+        .into_iter()
+        .take(input.limit)
+        .map(|(_, hit)| hit)
+        .collect();
+
+    let hint = tests
+        .is_empty()
+        .then(|| format!("No tests found for '{target}'. Try 'refs' to see every use."));
+    Ok(TestsForOutput {
+        target: Some(described),
+        tests,
+        has_more,
+        hint,
+    })
+}
+
+/// Builds a whole-word regex matching any of the escaped `names`.
+fn word_regex(names: &[String]) -> crate::error::Result<Option<Regex>> {
+    if names.is_empty() {
+        return Ok(None);
+    }
+    Regex::new(&format!(r"\b(?:{})\b", names.join("|")))
+        .map(Some)
+        .map_err(|e| ServerError::Tool(e.to_string()))
+}
+
+/// Distinct names `words` matches in the body lines after the 1-indexed
+/// `line`, through `end`, in order of appearance.
+fn matched_names<'l>(words: &Regex, lines: &[&'l str], line: usize, end: usize) -> Vec<&'l str> {
+    let mut found: Vec<&str> = Vec::new();
+    for text in lines.iter().take(end).skip(line) {
+        for m in words.find_iter(text) {
+            if !found.contains(&m.as_str()) {
+                found.push(m.as_str());
+            }
         }
     }
+    found
+}
+
+/// Joins up to three names, counting the rest (`a, b, c (+2)`).
+fn list_names(names: &[&str]) -> String {
+    let shown = names[..names.len().min(3)].join(", ");
+    match names.len().saturating_sub(3) {
+        0 => shown,
+        more => format!("{shown} (+{more})"),
+    }
+}
+
+/// A test function's name and 1-indexed line range.
+struct TestFn {
+    name: String,
+    line: usize,
+    end: usize,
+}
+
+/// Test functions in a file: `it`/`test` calls for JS/TS, otherwise
+/// functions marked by a test attribute or annotation (see
+/// [`is_test_marker`]) or named by the language's convention (`test_*` in
+/// Python, `Test*` in Go, `test*` in test files).
+fn test_functions(
+    lines: &[&str],
+    symbols: &[SymbolData],
+    file_type: &str,
+    in_test_file: bool,
+) -> Vec<TestFn> {
+    if matches!(
+        file_type,
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts"
+    ) {
+        static JS_TEST: OnceLock<Regex> = OnceLock::new();
+        let re = JS_TEST.get_or_init(|| {
+            Regex::new(r#"^\s*(?:it|test)(?:\.\w+)?\s*\(\s*(['"`])(.*?)['"`]"#)
+                .expect("valid test call regex")
+        });
+        let starts: Vec<(usize, String)> = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| Some((i + 1, re.captures(line)?.get(2)?.as_str().to_string())))
+            .collect();
+        // Each test runs until the next one starts
+        return starts
+            .iter()
+            .enumerate()
+            .map(|(i, (line, name))| TestFn {
+                name: name.clone(),
+                line: *line,
+                end: starts.get(i + 1).map_or(lines.len(), |(next, _)| next - 1),
+            })
+            .collect();
+    }
+
+    let hash = symbols::hash_comments(file_type);
+    symbols
+        .iter()
+        .filter(|sym| sym.kind == "fn")
+        .filter(|sym| {
+            let mut start = sym.line.saturating_sub(1).min(lines.len());
+            while start > 0
+                && (symbols::is_doc_or_attribute(lines[start - 1], hash)
+                    || lines[start - 1].trim_start().starts_with('['))
+            {
+                start -= 1;
+            }
+            let marked = lines[start..sym.line.min(lines.len())]
+                .iter()
+                .any(|l| is_test_marker(l));
+            let named = match file_type {
+                "py" | "pyi" => sym.name.starts_with("test"),
+                "go" => sym.name.starts_with("Test"),
+                _ => in_test_file && sym.name.to_lowercase().starts_with("test"),
+            };
+            marked || named
+        })
+        .map(|sym| TestFn {
+            name: sym.name.clone(),
+            line: sym.line,
+            end: sym.end_line.unwrap_or(sym.line),
+        })
+        .collect()
+}
 
-    Ok(RefsOutput { references })
+/// Whether `line` is an attribute or annotation that makes a function a
+/// test: `#[test]`, `#[tokio::test]`, `@Test`, `@pytest.mark.*`, `[Fact]`,
+/// `[Theory]` or `[Test*]`. Fixtures and configuration that merely mention
+/// tests (`@pytest.fixture`, `@TestConfiguration`, `#[cfg(test)]`) are not.
+fn is_test_marker(line: &str) -> bool {
+    static MARKER: OnceLock<Regex> = OnceLock::new();
+    let re = MARKER.get_or_init(|| {
+        Regex::new(
+            r"^(?:#\[(?:\w+::)*test\b|@Test\b|@pytest\.mark\.\w|\[(?:Fact|Theory|Test\w*)\b)",
+        )
+        .expect("valid test marker regex")
+    });
+    re.is_match(line.trim_start())
+}
+
+/// The name tests of a file are named after: its stem, or the directory
+/// name for module files (`mod.rs`, `index.ts`, `__init__.py`).
+fn subject_stem(rel: &str) -> Option<String> {
+    let path = Path::new(rel);
+    let stem = path.file_stem()?.to_str()?;
+    let stem = if matches!(stem, "mod" | "index" | "__init__" | "lib" | "main") {
+        path.parent()?.file_name()?.to_str()?
+    } else {
+        stem
+    };
+    Some(stem.to_string())
+}
+
+/// The stem of the file a test file is named after: `test_foo.py`,
+/// `foo_test.go`, `foo.test.ts`, `FooTest.java` and `tests/foo.rs` all
+/// give `foo`/`Foo`.
+fn test_subject_stem(rel: &str) -> Option<String> {
+    let name = Path::new(rel).file_name()?.to_str()?;
+    let stem = name.split('.').next()?;
+    // `foo.test.ts`, `foo.spec.ts`
+    if name.contains(".test.") || name.contains(".spec.") {
+        return Some(stem.to_string());
+    }
+    let stem = stem.strip_prefix("test_").unwrap_or(stem);
+    let stem = ["_tests", "_test", "_spec", "Tests", "Test"]
+        .iter()
+        .find_map(|suffix| stem.strip_suffix(suffix))
+        .unwrap_or(stem);
+    (!stem.is_empty()).then(|| stem.to_string())
 }
 
 // Helper functions
@@ -232,47 +1375,404 @@ fn trim_around_match(line: &str, symbol: &str) -> String {
     result
 }
 
-/// Classifies a reference line as definition/import/type_usage/usage.
-fn classify_reference(line: &str, symbol: &str) -> RefKind {
+/// Keywords that declare the name following them.
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "fn",
+    "fun",
+    "func",
+    "def",
+    "function",
+    "class",
+    "struct",
+    "enum",
+    "union",
+    "type",
+    "interface",
+    "trait",
+    "mod",
+    "module",
+    "namespace",
+    "object",
+    "record",
+    "const",
+    "static",
+    "let",
+    "var",
+    "val",
+    "macro_rules!",
+];
+
+/// Words allowed before a declaration keyword or the declared name.
+const DECLARATION_MODIFIERS: &[&str] = &[
+    "pub",
+    "export",
+    "default",
+    "async",
+    "unsafe",
+    "extern",
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "abstract",
+    "final",
+    "sealed",
+    "open",
+    "data",
+    "override",
+    "declare",
+    "readonly",
+    "mut",
+    "ref",
+    "inline",
+    "virtual",
+];
+
+/// Symbol kinds whose bodies declare fields or variants directly.
+const MEMBER_CONTAINER_KINDS: &[&str] = &["struct", "union", "enum", "class", "iface", "object"];
+
+/// Classifies a reference from the symbol index: a declaration of `symbol`
+/// on `line`, a type declared there that extends it, or a field or variant
+/// named `symbol` directly inside a type body.
+fn indexed_reference(
+    symbols: &[SymbolData],
+    line: usize,
+    content: &str,
+    symbol: &str,
+) -> Option<RefKind> {
+    let mut extends = false;
+    for sym in symbols.iter().filter(|s| s.line == line) {
+        if sym.kind != "impl" && sym.name == symbol {
+            return Some(RefKind::Definition);
+        }
+        extends |= sym.supertypes.iter().any(|t| t == symbol);
+    }
+    if extends {
+        return Some(RefKind::TraitImpl);
+    }
+    // Fields and variants are not indexed
+    let container = innermost_symbol(symbols, line, symbol)?;
+    (container.line < line
+        && MEMBER_CONTAINER_KINDS.contains(&container.kind.as_str())
+        && is_member_declaration(content, symbol, container.kind == "enum"))
+    .then_some(RefKind::Definition)
+}
+
+/// The innermost indexed symbol whose range contains `line`, other than a
+/// declaration of `symbol` or an `impl` header starting there.
+fn innermost_symbol<'a>(
+    symbols: &'a [SymbolData],
+    line: usize,
+    symbol: &str,
+) -> Option<&'a SymbolData> {
+    symbols
+        .iter()
+        .filter(|s| s.line <= line && line <= s.end_line.unwrap_or(s.line))
+        .filter(|s| s.line != line || (s.name != symbol && s.kind != "impl"))
+        .max_by_key(|s| (s.line, std::cmp::Reverse(s.end_line)))
+}
+
+/// Name of the innermost symbol around `line`, methods as `Type::method`.
+fn enclosing_symbol(symbols: &[SymbolData], line: usize, symbol: &str) -> Option<String> {
+    let sym = innermost_symbol(symbols, line, symbol)?;
+    Some(match &sym.parent {
+        Some(parent) => format!("{parent}::{}", sym.name),
+        None => sym.name.clone(),
+    })
+}
+
+/// Whether a line in a type body declares the field (`name: T`, `name = 1`,
+/// `name?: T`) or, in enums, the variant (`Name`, `Name(T)`, `Name { .. }`)
+/// called `symbol`.
+fn is_member_declaration(line: &str, symbol: &str, in_enum: bool) -> bool {
+    let mut rest = line.trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix("pub(") {
+            match after.split_once(')') {
+                Some((_, after)) => rest = after.trim_start(),
+                None => return false,
+            }
+            continue;
+        }
+        let word = leading_word(rest);
+        if word.is_empty() || !DECLARATION_MODIFIERS.contains(&word) {
+            break;
+        }
+        rest = rest[word.len()..].trim_start();
+    }
+    let Some(after) = rest.strip_prefix(symbol) else {
+        return false;
+    };
+    if after.starts_with(is_ident_char) {
+        return false;
+    }
+    let after = after.trim_start();
+    match after.chars().next() {
+        None | Some(',' | ';' | '?') => true,
+        Some(':') => !after.starts_with("::"),
+        Some('=') => !after.starts_with("=="),
+        Some('(' | '{') => in_enum,
+        Some(_) => false,
+    }
+}
+
+/// Classifies a reference line by per-language rules: comment or doc
+/// mention, import, definition, trait or interface implementation, call,
+/// field access or type usage, in that order. Lines matching none are
+/// plain usages.
+pub(crate) fn classify_reference(line: &str, symbol: &str, file_type: &str) -> RefKind {
     let trimmed = line.trim();
+    let code = &trimmed[..comment_start(trimmed, file_type).unwrap_or(trimmed.len())];
+    let occurrences: Vec<usize> = word_occurrences(code, symbol).collect();
+    if occurrences.is_empty() {
+        return if word_occurrences(trimmed, symbol).next().is_some() {
+            RefKind::Comment
+        } else {
+            RefKind::Usage
+        };
+    }
 
-    // Check for definitions
-    let is_definition_keyword = trimmed.starts_with("fn ")
-        || trimmed.starts_with("pub fn ")
-        || trimmed.starts_with("def ")
-        || trimmed.starts_with("function ")
-        || trimmed.starts_with("class ")
-        || trimmed.starts_with("struct ")
-        || trimmed.starts_with("enum ")
-        || trimmed.starts_with("type ")
-        || trimmed.starts_with("interface ");
-
-    if is_definition_keyword
-        && (trimmed.contains(&format!(" {symbol}"))
-            || trimmed.contains(&format!(" {symbol}("))
-            || trimmed.contains(&format!(" {symbol}<")))
-    {
-        return RefKind::Definition;
+    if is_import(code, file_type) {
+        return RefKind::Import;
+    }
+    if let Some((keyword, name)) = declared_name(code) {
+        if name == symbol {
+            return RefKind::Definition;
+        }
+        let is_type = matches!(
+            keyword,
+            "class" | "struct" | "interface" | "trait" | "enum" | "object" | "record"
+        );
+        if is_type
+            && symbols::supertypes(name, "class", code.trim_end_matches([' ', '{']), file_type)
+                .iter()
+                .any(|t| t == symbol)
+        {
+            return RefKind::TraitImpl;
+        }
+    }
+    // `impl Trait for Type` implements the trait; the implementing type
+    // and inherent `impl Type` blocks use the type
+    if let Some(header) = impl_header(code) {
+        let implemented = header
+            .split_once(" for ")
+            .map(|(implemented, _)| implemented);
+        if implemented.is_some_and(|t| word_occurrences(t, symbol).next().is_some()) {
+            return RefKind::TraitImpl;
+        }
+        return RefKind::TypeUsage;
     }
 
-    // Check for imports
-    if trimmed.starts_with("use ")
-        || trimmed.starts_with("import ")
-        || trimmed.starts_with("from ")
-        || trimmed.contains("require(")
+    occurrences
+        .into_iter()
+        .find_map(|at| occurrence_kind(code, at, symbol, file_type))
+        .unwrap_or(RefKind::Usage)
+}
+
+/// How a single occurrence is used, judged from its neighbours.
+fn occurrence_kind(line: &str, at: usize, symbol: &str, file_type: &str) -> Option<RefKind> {
+    let raw_before = &line[..at];
+    let after = line[at + symbol.len()..].trim_start();
+
+    // Macro bangs and turbofish come before the argument list
+    let mut args = after.strip_prefix('!').unwrap_or(after);
+    if let Some(generics) = args.strip_prefix("::<") {
+        args = after_generics(generics).unwrap_or("").trim_start();
+    }
+    if args.starts_with('(') {
+        return Some(RefKind::Call);
+    }
+    if raw_before.ends_with('.')
+        || (raw_before.ends_with("->")
+            && matches!(file_type, "c" | "h" | "cpp" | "cc" | "hpp" | "php"))
     {
-        return RefKind::Import;
+        return Some(RefKind::FieldAccess);
     }
 
-    // Check for type annotations
-    if trimmed.contains(&format!(": {symbol}"))
-        || trimmed.contains(&format!("-> {symbol}"))
-        || trimmed.contains(&format!("<{symbol}"))
+    // Reference and pointer sigils sit between a type and its position
+    let mut before = raw_before.trim_end();
+    loop {
+        let stripped = before
+            .trim_end_matches(['&', '*'])
+            .trim_end()
+            .trim_end_matches("mut")
+            .trim_end();
+        if stripped.len() == before.len() {
+            break;
+        }
+        before = stripped;
+    }
+    let after_keyword = [
+        "dyn",
+        "impl",
+        "as",
+        "is",
+        "instanceof",
+        "extends",
+        "implements",
+        "new",
+    ]
+    .iter()
+    .any(|kw| {
+        before
+            .strip_suffix(kw)
+            .is_some_and(|b| !b.ends_with(is_ident_char))
+    });
+    let type_position = (before.ends_with(':') && !before.ends_with("::"))
+        || before.ends_with("->")
+        || before.ends_with('<')
+        || after_keyword
+        || after.starts_with('>')
+        || (after.starts_with("::") && symbol.starts_with(|c: char| c.is_ascii_uppercase()));
+    type_position.then_some(RefKind::TypeUsage)
+}
+
+/// Byte offset where a comment starts on a trimmed line, skipping markers
+/// inside string literals. Block comment continuations (`* ...`) and
+/// Python docstrings count from the start of the line.
+fn comment_start(line: &str, file_type: &str) -> Option<usize> {
+    let hash = symbols::hash_comments(file_type);
+    if line.starts_with("* ")
+        || line == "*"
+        || line.starts_with("*/")
+        || (hash && (line.starts_with("\"\"\"") || line.starts_with("'''")))
     {
-        return RefKind::TypeUsage;
+        return Some(0);
+    }
+    // Rust lifetimes share the single quote, so only `"` opens strings there
+    let quotes: &[char] = if file_type == "rs" {
+        &['"']
+    } else {
+        &['"', '\'', '`']
+    };
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+        } else if quotes.contains(&c) {
+            quote = Some(c);
+        } else if (hash && c == '#')
+            || (!hash && c == '/' && matches!(line.as_bytes().get(i + 1), Some(b'/' | b'*')))
+        {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Byte offsets of `symbol` in `line` at identifier boundaries.
+fn word_occurrences<'a>(line: &'a str, symbol: &'a str) -> impl Iterator<Item = usize> + 'a {
+    line.match_indices(symbol)
+        .map(|(at, _)| at)
+        .filter(move |&at| {
+            !line[..at].ends_with(is_ident_char)
+                && !line[at + symbol.len()..].starts_with(is_ident_char)
+        })
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The identifier at the start of `text`, or `macro_rules!`.
+fn leading_word(text: &str) -> &str {
+    let end = text.find(|c: char| !is_ident_char(c)).unwrap_or(text.len());
+    if &text[..end] == "macro_rules" && text[end..].starts_with('!') {
+        return &text[..=end];
     }
+    &text[..end]
+}
 
-    RefKind::Usage
+/// Whether a line imports names: `use`, `import`, `from x import`,
+/// `require(...)`, `#include` or `using`, depending on the language.
+fn is_import(line: &str, file_type: &str) -> bool {
+    let line = match line.strip_prefix("pub(") {
+        Some(after) => after
+            .split_once(')')
+            .map_or(line, |(_, rest)| rest.trim_start()),
+        None => line.strip_prefix("pub ").unwrap_or(line),
+    };
+    match file_type {
+        "rs" => line.starts_with("use ") || line.starts_with("extern crate "),
+        "py" | "pyi" => {
+            line.starts_with("import ") || (line.starts_with("from ") && line.contains(" import "))
+        }
+        "c" | "h" | "cpp" | "cc" | "hpp" => line.starts_with("#include"),
+        "cs" => line.starts_with("using ") && !line.contains('('),
+        _ => {
+            line.starts_with("import ")
+                || line.starts_with("use ")
+                || line.contains("require(")
+                || ((line.starts_with("export ") || line.starts_with('}'))
+                    && line.contains(" from "))
+        }
+    }
+}
+
+/// The last declaration keyword of a line and the name it declares
+/// (`pub(crate) const fn x`, `export default class X`, `func (r *T) X`).
+fn declared_name(line: &str) -> Option<(&str, &str)> {
+    let mut rest = line.trim_start();
+    let mut keyword = None;
+    loop {
+        if let Some(after) = rest.strip_prefix("pub(") {
+            rest = after.split_once(')')?.1.trim_start();
+            continue;
+        }
+        // `extern "C" fn`
+        if let Some(after) = rest.strip_prefix('"') {
+            rest = after.split_once('"')?.1.trim_start();
+            continue;
+        }
+        let word = leading_word(rest);
+        if word.is_empty() {
+            // Go method receivers
+            if keyword.is_some() && rest.starts_with('(') {
+                rest = rest.split_once(')')?.1.trim_start();
+                continue;
+            }
+            return None;
+        }
+        if DECLARATION_KEYWORDS.contains(&word) {
+            keyword = Some(word);
+        } else if !DECLARATION_MODIFIERS.contains(&word) {
+            return keyword.map(|keyword| (keyword, word));
+        }
+        rest = rest[word.len()..].trim_start();
+    }
+}
+
+/// The header of a Rust `impl` line after its generics (`Display for Foo<T>`).
+fn impl_header(line: &str) -> Option<&str> {
+    let line = line.strip_prefix("unsafe ").unwrap_or(line);
+    let rest = line.strip_prefix("impl")?;
+    let rest = match rest.strip_prefix('<') {
+        Some(generics) => after_generics(generics)?,
+        None if rest.starts_with(char::is_whitespace) => rest,
+        None => return None,
+    };
+    Some(rest.trim())
+}
+
+/// The text after the `>` closing a generic list whose `<` precedes `text`.
+fn after_generics(text: &str) -> Option<&str> {
+    let mut depth = 1;
+    let end = text.char_indices().find_map(|(i, c)| {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            _ => {}
+        }
+        (depth == 0).then_some(i)
+    })?;
+    Some(&text[end + 1..])
 }
 
 fn format_bytes(bytes: u64) -> String {
@@ -290,3 +1790,274 @@ fn format_bytes(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_reference() {
+        use RefKind::*;
+        for (line, symbol, file_type, kind) in [
+            (
+                "pub(crate) fn parse(input: &str) {",
+                "parse",
+                "rs",
+                Definition,
+            ),
+            ("pub const LIMIT: usize = 10;", "LIMIT", "rs", Definition),
+            ("const fn size() -> usize {", "size", "rs", Definition),
+            ("export default class Store {", "Store", "ts", Definition),
+            (
+                "func (s *Server) Serve() error {",
+                "Serve",
+                "go",
+                Definition,
+            ),
+            ("let config: Config = load();", "Config", "rs", TypeUsage),
+            (
+                "fn run(cfg: &mut Config) -> Result<Config> {",
+                "Config",
+                "rs",
+                TypeUsage,
+            ),
+            ("let config = Config::load();", "Config", "rs", TypeUsage),
+            (
+                "pub(crate) use crate::db::Database;",
+                "Database",
+                "rs",
+                Import,
+            ),
+            ("from models import User", "User", "py", Import),
+            ("from_user = User(name)", "User", "py", Call),
+            ("impl fmt::Display for Mode {", "Display", "rs", TraitImpl),
+            ("impl<T: Clone> Store<T> {", "Store", "rs", TypeUsage),
+            ("class Dog(Animal):", "Animal", "py", TraitImpl),
+            (
+                "public class Worker extends Service implements Runnable {",
+                "Runnable",
+                "java",
+                TraitImpl,
+            ),
+            (
+                "let user = authenticate(&config)?;",
+                "authenticate",
+                "rs",
+                Call,
+            ),
+            ("println!(\"{}\", format!(\"x\"));", "format", "rs", Call),
+            ("let n = iter.collect::<Vec<_>>();", "collect", "rs", Call),
+            ("return self.username;", "username", "py", FieldAccess),
+            ("/// Returns the parsed Config.", "Config", "rs", Comment),
+            ("# uses Config for defaults", "Config", "py", Comment),
+            ("* @param config the Config", "Config", "java", Comment),
+            ("let url = \"http://x\"; // Config", "Config", "rs", Comment),
+            ("let url = \"http://Config\";", "Config", "rs", Usage),
+            ("handlers.push(parse);", "parse", "rs", Usage),
+        ] {
+            assert_eq!(classify_reference(line, symbol, file_type), kind, "{line}");
+        }
+    }
+
+    #[test]
+    fn test_indexed_reference_and_enclosing_symbol() {
+        let sym = |name: &str, kind: &str, line, end_line, parent: Option<&str>| SymbolData {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            line,
+            end_line: Some(end_line),
+            parent: parent.map(str::to_string),
+            supertypes: Vec::new(),
+        };
+        let mut display = sym("fmt::Display for User", "impl", 10, 14, None);
+        display.supertypes = vec!["Display".to_string()];
+        let symbols = vec![
+            sym("User", "struct", 1, 4, None),
+            sym("Kind", "enum", 5, 8, None),
+            display,
+            sym("fmt", "fn", 11, 13, Some("User")),
+        ];
+
+        assert_eq!(
+            indexed_reference(&symbols, 2, "pub(crate) name: String,", "name"),
+            Some(RefKind::Definition)
+        );
+        assert_eq!(
+            indexed_reference(&symbols, 6, "Admin(Level),", "Admin"),
+            Some(RefKind::Definition)
+        );
+        assert_eq!(
+            indexed_reference(&symbols, 6, "Admin(Level),", "Level"),
+            None
+        );
+        assert_eq!(
+            indexed_reference(&symbols, 10, "impl fmt::Display for User {", "Display"),
+            Some(RefKind::TraitImpl)
+        );
+        assert_eq!(
+            indexed_reference(&symbols, 11, "fn fmt(&self) {", "fmt"),
+            Some(RefKind::Definition)
+        );
+        assert_eq!(
+            indexed_reference(&symbols, 12, "self.name.fmt(f)", "name"),
+            None
+        );
+
+        assert_eq!(
+            enclosing_symbol(&symbols, 12, "name").as_deref(),
+            Some("User::fmt")
+        );
+        assert_eq!(
+            enclosing_symbol(&symbols, 11, "fmt").as_deref(),
+            Some("fmt::Display for User")
+        );
+        assert_eq!(
+            enclosing_symbol(&symbols, 2, "name").as_deref(),
+            Some("User")
+        );
+        assert_eq!(enclosing_symbol(&symbols, 9, "name"), None);
+        assert_eq!(enclosing_symbol(&symbols, 10, "Display"), None);
+    }
+
+    #[test]
+    fn test_ref_target_parse_and_qualifier_before() {
+        let target = RefTarget::parse("crate::db::Database::open");
+        assert_eq!((target.qualifier, target.name), (Some("Database"), "open"));
+        assert!(!target.member && !target.call);
+
+        let target = RefTarget::parse(".refresh_total_files(");
+        assert_eq!(
+            (target.qualifier, target.name),
+            (None, "refresh_total_files")
+        );
+        assert!(target.member && target.call);
+
+        let text = "let db = Database::open(&p); self.db.open(); f().open(); $db->open()";
+        let at = |needle: &str| text.find(needle).unwrap() + needle.len() - 4;
+        let before = qualifier_before(text, at("::open"), "rs").unwrap();
+        assert!(before.path && before.word == Some("Database"));
+        let before = qualifier_before(text, at(".db.open"), "rs").unwrap();
+        assert!(!before.path && before.word == Some("db"));
+        assert_eq!(
+            qualifier_before(text, at("().open"), "rs").unwrap().word,
+            None
+        );
+        assert!(qualifier_before(text, at("->open"), "rs").is_none());
+        assert_eq!(
+            qualifier_before(text, at("->open"), "php").unwrap().word,
+            Some("db")
+        );
+        assert_eq!(snake_case("SearchService"), "search_service");
+    }
+
+    #[test]
+    fn test_ref_target_fits_member_and_call_shapes() {
+        let fits = |symbol: &str, text: &str| {
+            let target = RefTarget::parse(symbol);
+            target.fits(text, text.find(target.name).unwrap(), "rs")
+        };
+
+        assert!(fits(".check(", "    store.check(\"b\");"));
+        assert!(fits(".check(", "other.check (\"c\");"));
+        assert!(!fits(".check(", "Self::check(path);"));
+        assert!(!fits(".check(", "fn check(path: &str) {}"));
+        assert!(!fits(".check(", "let f = store.check;"));
+        assert!(fits(
+            ".refresh_total_files(",
+            "search.refresh_total_files();"
+        ));
+
+        assert!(fits("check()", "Self::check(path);"));
+        assert!(fits("check()", "let n = check::<u8>(x);"));
+        assert!(fits("check()", "check!(x);"));
+        assert!(!fits("check()", "let check = 1;"));
+
+        assert!(fits(".check", "let f = store.check;"));
+        assert!(fits("check", "let check = 1;"));
+    }
+
+    #[test]
+    fn test_test_functions() {
+        let found = |src: &str, symbols: &[SymbolData], file_type: &str, in_test_file| {
+            let lines: Vec<&str> = src.lines().collect();
+            test_functions(&lines, symbols, file_type, in_test_file)
+                .into_iter()
+                .map(|t| (t.name, t.line, t.end))
+                .collect::<Vec<_>>()
+        };
+
+        // JS/TS tests run until the next one starts
+        let js =
+            "describe('slugify', () => {\n  it('lowercases', () => {\n    slugify('A');\n  });\n  \
+             test.skip(\"trims\", () => {});\n});\n";
+        assert_eq!(
+            found(js, &[], "ts", true),
+            vec![
+                ("lowercases".to_string(), 2, 4),
+                ("trims".to_string(), 5, 6)
+            ]
+        );
+
+        let sym = |name: &str, line| SymbolData {
+            name: name.to_string(),
+            kind: "fn".to_string(),
+            line,
+            end_line: Some(line),
+            parent: None,
+            supertypes: Vec::new(),
+        };
+        let rust = "fn helper() {}\n\n#[test]\nfn charges() {}\n\n#[tokio::test]\n\
+                    async fn waits() {}\n\nfn test_named() {}\n";
+        let symbols = [
+            sym("helper", 1),
+            sym("charges", 4),
+            sym("waits", 7),
+            sym("test_named", 9),
+        ];
+        let names = |in_test_file| {
+            found(rust, &symbols, "rs", in_test_file)
+                .into_iter()
+                .map(|(name, _, _)| name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(false), ["charges", "waits"]);
+        // Outside attributes, only test files go by the name
+        assert_eq!(names(true), ["charges", "waits", "test_named"]);
+
+        // Fixtures and test-only helpers are not tests
+        let python = "import pytest\n\n\n@pytest.fixture\ndef repo():\n    pass\n\n\
+                      @pytest.mark.slow\ndef saves():\n    pass\n\ndef test_total():\n    pass\n";
+        let symbols = [sym("repo", 5), sym("saves", 9), sym("test_total", 12)];
+        assert_eq!(
+            found(python, &symbols, "py", false),
+            vec![
+                ("saves".to_string(), 9, 9),
+                ("test_total".to_string(), 12, 12)
+            ]
+        );
+        let rust = "#[cfg(test)]\nfn helper() {}\n";
+        assert!(found(rust, &[sym("helper", 2)], "rs", false).is_empty());
+        let java = "@TestConfiguration\nvoid setup() {}\n@Test\nvoid saves() {}\n";
+        let symbols = [sym("setup", 2), sym("saves", 4)];
+        assert_eq!(
+            found(java, &symbols, "java", false),
+            vec![("saves".to_string(), 4, 4)]
+        );
+    }
+
+    #[test]
+    fn test_subject_stems() {
+        assert_eq!(subject_stem("src/db/mod.rs").as_deref(), Some("db"));
+        assert_eq!(subject_stem("src/auth.rs").as_deref(), Some("auth"));
+        for (rel, stem) in [
+            ("tests/auth.rs", "auth"),
+            ("tests/test_cart.py", "cart"),
+            ("pkg/cart_test.go", "cart"),
+            ("web/slug.test.ts", "slug"),
+            ("web/slug.spec.tsx", "slug"),
+            ("src/test/java/CartTest.java", "Cart"),
+        ] {
+            assert_eq!(test_subject_stem(rel).as_deref(), Some(stem), "{rel}");
+        }
+    }
+}
@@ -8,15 +8,28 @@
 //!
 //! See [`crate::security`] for details.
 
+use super::symbols::split_qualified;
+use crate::error::ServerError;
 use crate::security;
+use crate::services::fuzzy;
+use crate::services::symbols::{
+    annotate, detect_file_type, extract_symbols, has_extractor, hash_comments, is_doc_or_attribute,
+    parent_indices, parent_name,
+};
 use crate::services::SearchService;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write as _};
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::str::FromStr;
 use std::sync::Arc;
 
+pub use crate::services::symbols::Symbol;
+
 /// Wraps file content in boundary markers to help LLM consumers distinguish
 /// tool metadata from untrusted file content (prompt injection defense).
 fn mark_content_boundary(content: &str, path: &str) -> String {
@@ -92,11 +105,123 @@ pub fn execute_get(
     })
 }
 
+/// How much the outline reports per symbol.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutlineVerbosity {
+    /// Name, kind, line range and nesting level
+    #[default]
+    Names,
+    /// Adds the signature, visibility and enclosing parent
+    Signatures,
+    /// Adds the first doc-comment line
+    Full,
+}
+
+impl fmt::Display for OutlineVerbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Names => write!(f, "names"),
+            Self::Signatures => write!(f, "signatures"),
+            Self::Full => write!(f, "full"),
+        }
+    }
+}
+
+impl FromStr for OutlineVerbosity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "names" => Ok(Self::Names),
+            "signatures" => Ok(Self::Signatures),
+            "full" => Ok(Self::Full),
+            other => Err(format!(
+                "Invalid outline verbosity: '{}'. Valid values: names, signatures, full",
+                other
+            )),
+        }
+    }
+}
+
+/// Shape of the outline result.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutlineFormat {
+    /// Symbols in line order, nesting given by `level`
+    #[default]
+    Flat,
+    /// Nested `children` following line-range containment
+    Tree,
+    /// Compact indented text, one symbol per line
+    Text,
+}
+
+impl fmt::Display for OutlineFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flat => write!(f, "flat"),
+            Self::Tree => write!(f, "tree"),
+            Self::Text => write!(f, "text"),
+        }
+    }
+}
+
+impl FromStr for OutlineFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "flat" => Ok(Self::Flat),
+            "tree" => Ok(Self::Tree),
+            "text" => Ok(Self::Text),
+            other => Err(format!(
+                "Invalid outline format: '{}'. Valid values: flat, tree, text",
+                other
+            )),
+        }
+    }
+}
+
+/// Files outlined for a directory or glob path unless `limit` says otherwise.
+const DEFAULT_OUTLINE_FILES: usize = 30;
+
+/// Serialized size at which a multi-file outline stops adding files.
+const OUTLINE_BYTE_BUDGET: usize = 32 * 1024;
+
 /// Input for the outline tool (extracts file structure).
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct OutlineInput {
     /// File path (relative to root)
     pub path: String,
+    /// Detail per symbol: "names" (default), "signatures", or "full"
+    #[serde(default)]
+    pub verbosity: OutlineVerbosity,
+    /// Result shape: "flat" (default), "tree", or "text"
+    #[serde(default)]
+    pub format: OutlineFormat,
+    /// Most files outlined when `path` is a directory or glob (default: 30)
+    #[serde(default = "default_outline_files")]
+    pub limit: usize,
+    /// Include nested symbols (methods, inner types) when `path` is a
+    /// directory or glob; single files always include them (default: false)
+    #[serde(default)]
+    pub nested: bool,
+}
+
+const fn default_outline_files() -> usize {
+    DEFAULT_OUTLINE_FILES
+}
+
+/// A symbol with the symbols it contains.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct OutlineNode {
+    /// The symbol itself
+    #[serde(flatten)]
+    pub symbol: Symbol,
+    /// Symbols declared inside this one, in line order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OutlineNode>,
 }
 
 /// Output for the outline tool.
@@ -104,31 +229,26 @@ pub struct OutlineInput {
 pub struct OutlineOutput {
     /// File path
     pub path: String,
-    /// Extracted symbols/structure
+    /// Extracted symbols/structure (flat format; always serialized, so an
+    /// empty file still reports `symbols: []`)
     pub symbols: Vec<Symbol>,
-    /// File type detected
+    /// Top-level symbols with nested children (tree format)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tree: Vec<OutlineNode>,
+    /// Indented outline, one `kind name :start-end` per line (text format)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// File type detected ("directory" or "glob" for multi-file outlines)
     pub file_type: String,
-}
-
-/// A symbol extracted from the file.
-#[derive(Debug, Serialize, JsonSchema)]
-pub struct Symbol {
-    /// Symbol name
-    pub name: String,
-    /// Symbol kind (fn, class, struct, etc.)
-    pub kind: String,
-    /// Start line number
-    pub line: usize,
-    /// End line number (if detectable)
+    /// Per-file outlines when `path` is a directory or glob
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<OutlineOutput>,
+    /// Whether more files matched than were returned (limit or byte budget)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub has_more: bool,
+    /// Hint when files were left out or none matched
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub end_line: Option<usize>,
-    /// Indentation level
-    #[serde(skip_serializing_if = "is_zero")]
-    pub level: usize,
-}
-
-const fn is_zero(v: &usize) -> bool {
-    *v == 0
+    pub hint: Option<String>,
 }
 
 /// Executes the outline tool.
@@ -145,21 +265,393 @@ pub fn execute_outline(
     service: &Arc<SearchService>,
     input: OutlineInput,
 ) -> crate::error::Result<OutlineOutput> {
+    let root = service.root();
+    let glob = is_glob(root, &input.path);
+    if glob || security::validate_path(root, &input.path)?.is_dir() {
+        return outline_many(root, input, glob);
+    }
+
     // Security: validate path and check for sensitive files
-    let full_path = security::validate_read_access(service.root(), &input.path)?;
+    let full_path = security::validate_read_access(root, &input.path)?;
 
     let content = fs::read_to_string(&full_path)?;
 
-    let file_type = detect_file_type(&full_path);
-    let symbols = extract_symbols(&content, &file_type);
+    Ok(outline_file(
+        &full_path,
+        input.path.clone(),
+        &content,
+        &input,
+        false,
+    ))
+}
+
+/// Outlines one file; `summary` keeps only top-level symbols (unless
+/// `input.nested`) and drops test modules.
+fn outline_file(
+    full_path: &Path,
+    path: String,
+    content: &str,
+    input: &OutlineInput,
+    summary: bool,
+) -> OutlineOutput {
+    let file_type = detect_file_type(full_path);
+    let mut symbols = extract_symbols(content, &file_type);
+    if input.verbosity != OutlineVerbosity::Names {
+        let with_docs = input.verbosity == OutlineVerbosity::Full;
+        annotate(&mut symbols, content, &file_type, with_docs);
+    }
+    if summary {
+        symbols = drop_test_modules(symbols);
+        if !input.nested {
+            symbols.retain(|s| s.level == 0);
+        }
+    }
+
+    let (symbols, tree, text) = match input.format {
+        OutlineFormat::Flat => (symbols, Vec::new(), None),
+        OutlineFormat::Tree => (Vec::new(), build_tree(symbols), None),
+        OutlineFormat::Text => (Vec::new(), Vec::new(), Some(outline_text(&symbols))),
+    };
 
-    Ok(OutlineOutput {
-        path: input.path,
+    OutlineOutput {
+        path,
         symbols,
+        tree,
+        text,
         file_type,
+        files: Vec::new(),
+        has_more: false,
+        hint: None,
+    }
+}
+
+/// Outlines the files under a directory or matching a glob (relative to
+/// the root), in path order, within the file limit and byte budget.
+fn outline_many(
+    root: &Path,
+    input: OutlineInput,
+    glob: bool,
+) -> crate::error::Result<OutlineOutput> {
+    let (dir, overrides) = if glob {
+        let base = glob_base(&input.path);
+        let dir = security::validate_path(root, if base.is_empty() { "." } else { base })?;
+        let invalid =
+            |e: ignore::Error| ServerError::Tool(format!("Invalid glob '{}': {e}", input.path));
+        let mut builder = OverrideBuilder::new(root);
+        builder.add(&input.path).map_err(invalid)?;
+        (dir, Some(builder.build().map_err(invalid)?))
+    } else {
+        (security::validate_path(root, &input.path)?, None)
+    };
+
+    let mut walker = WalkBuilder::new(&dir);
+    walker
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .sort_by_file_path(|a, b| a.cmp(b));
+    if let Some(overrides) = overrides {
+        walker.overrides(overrides);
+    }
+
+    let mut files = Vec::new();
+    let mut bytes = 0;
+    let mut stopped_by = None;
+    for entry in walker.build().filter_map(Result::ok) {
+        let full_path = entry.path();
+        if !entry.file_type().is_some_and(|t| t.is_file())
+            || !has_extractor(&detect_file_type(full_path))
+            || security::is_sensitive_file(full_path).is_some()
+        {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(full_path) else {
+            continue; // binary or unreadable
+        };
+        let path = full_path
+            .strip_prefix(root)
+            .unwrap_or(full_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let outline = outline_file(full_path, path, &content, &input, true);
+        if outline.symbols.is_empty()
+            && outline.tree.is_empty()
+            && outline.text.as_deref().is_none_or(str::is_empty)
+        {
+            continue;
+        }
+
+        if files.len() == input.limit {
+            stopped_by = Some("file limit");
+            break;
+        }
+        let size = serde_json::to_string(&outline).map_or(0, |json| json.len());
+        if !files.is_empty() && bytes + size > OUTLINE_BYTE_BUDGET {
+            stopped_by = Some("response budget");
+            break;
+        }
+        bytes += size;
+        files.push(outline);
+    }
+
+    let hint = match stopped_by {
+        _ if files.is_empty() => Some(format!(
+            "No files with outlines under '{}'. Supported: source files, Markdown, TOML, YAML, JSON.",
+            input.path
+        )),
+        Some(reason) => Some(format!(
+            "Stopped after {} files ({reason}). Narrow the path or glob, or use format='text'.",
+            files.len()
+        )),
+        None => None,
+    };
+
+    Ok(OutlineOutput {
+        path: input.path,
+        symbols: Vec::new(),
+        tree: Vec::new(),
+        text: None,
+        file_type: if glob { "glob" } else { "directory" }.to_string(),
+        has_more: stopped_by.is_some(),
+        files,
+        hint,
     })
 }
 
+/// Whether an outline path is a glob rather than a file or directory.
+/// Paths naming an existing entry are never globs, even with wildcard
+/// characters in them (`pages/[id].tsx`, `routes/{slug}.ts`).
+fn is_glob(root: &Path, path: &str) -> bool {
+    path.contains(['*', '?', '[', '{'])
+        && !security::validate_path(root, path).is_ok_and(|p| p.exists())
+}
+
+/// Directory prefix of a glob before its first wildcard segment.
+fn glob_base(glob: &str) -> &str {
+    let wildcard = glob.find(['*', '?', '[', '{']).unwrap_or(glob.len());
+    glob[..wildcard]
+        .rfind('/')
+        .map_or("", |slash| &glob[..slash])
+}
+
+/// Removes `mod tests` blocks and everything inside them; every file has
+/// one, so they only repeat in multi-file outlines.
+fn drop_test_modules(symbols: Vec<Symbol>) -> Vec<Symbol> {
+    let mut skip_through = 0;
+    symbols
+        .into_iter()
+        .filter(|s| {
+            if s.line <= skip_through {
+                return false;
+            }
+            if s.kind == "mod" && matches!(s.name.as_str(), "tests" | "test") {
+                skip_through = s.end_line.unwrap_or(s.line);
+                return false;
+            }
+            true
+        })
+        .collect()
+}
+
+/// Nests symbols (in line order) under the symbols whose ranges contain
+/// them.
+fn build_tree(symbols: Vec<Symbol>) -> Vec<OutlineNode> {
+    let parents = parent_indices(&symbols);
+    let mut roots = Vec::new();
+    // Open ancestors of the current symbol: (index, node)
+    let mut stack: Vec<(usize, OutlineNode)> = Vec::new();
+
+    for (i, (symbol, parent)) in symbols.into_iter().zip(parents).enumerate() {
+        while stack.last().is_some_and(|&(top, _)| Some(top) != parent) {
+            close_node(&mut stack, &mut roots);
+        }
+        let node = OutlineNode {
+            symbol,
+            children: Vec::new(),
+        };
+        stack.push((i, node));
+    }
+    while !stack.is_empty() {
+        close_node(&mut stack, &mut roots);
+    }
+    roots
+}
+
+/// Pops the innermost open node into its parent (or the roots).
+fn close_node(stack: &mut Vec<(usize, OutlineNode)>, roots: &mut Vec<OutlineNode>) {
+    if let Some((_, node)) = stack.pop() {
+        match stack.last_mut() {
+            Some((_, parent)) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+}
+
+/// Renders symbols as indented lines (`kind name :start-end`), two spaces
+/// per nesting level.
+fn outline_text(symbols: &[Symbol]) -> String {
+    let mut text = String::new();
+    for sym in symbols {
+        let label = sym.signature.as_deref().unwrap_or(&sym.name);
+        let _ = write!(
+            text,
+            "{}{} {label} :{}",
+            "  ".repeat(sym.level),
+            sym.kind,
+            sym.line
+        );
+        if let Some(end) = sym.end_line.filter(|&end| end != sym.line) {
+            let _ = write!(text, "-{end}");
+        }
+        if let Some(doc) = &sym.doc {
+            let _ = write!(text, " // {doc}");
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Input for the symbol_body tool (extracts one symbol's source).
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SymbolBodyInput {
+    /// File path (relative to root)
+    pub path: String,
+    /// Symbol name, optionally qualified by its parent (`Type::method`)
+    pub symbol: String,
+    /// Include doc comments and attributes/decorators above the item (default: true)
+    #[serde(default = "default_include_docs")]
+    pub include_docs: bool,
+}
+
+const fn default_include_docs() -> bool {
+    true
+}
+
+/// Output for the symbol_body tool.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SymbolBodyOutput {
+    /// File path
+    pub path: String,
+    /// Resolved name, qualified by its parent for members (`Type::method`)
+    pub name: String,
+    /// Symbol kind
+    pub kind: String,
+    /// First line returned (doc comments included)
+    pub start_line: usize,
+    /// Last line returned
+    pub end_line: usize,
+    /// Item source
+    pub content: String,
+    /// Other symbols with the same name in this file (`name:line`), if ambiguous
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub other_matches: Vec<String>,
+}
+
+/// Executes the symbol_body tool.
+///
+/// Resolves the symbol with the outline extractor, preferring a
+/// declaration over an `impl` block of the same name, and returns lines
+/// from its doc comments (optional) through its `end_line`.
+///
+/// # Security
+///
+/// - Validates path stays within root directory
+/// - Blocks access to sensitive files (.env, credentials, keys)
+///
+/// # Errors
+///
+/// Returns a `ServerError` if path traversal is detected, file is sensitive, file cannot
+/// be read, or the symbol is not declared in the file.
+pub fn execute_symbol_body(
+    service: &Arc<SearchService>,
+    input: SymbolBodyInput,
+) -> crate::error::Result<SymbolBodyOutput> {
+    // Security: validate path and check for sensitive files
+    let full_path = security::validate_read_access(service.root(), &input.path)?;
+
+    let content = fs::read_to_string(&full_path)?;
+    let symbols = extract_symbols(&content, &detect_file_type(&full_path));
+    let parents = parent_indices(&symbols);
+    let (qualifier, name) = split_qualified(input.symbol.trim());
+    let qualified = |i: usize| match parents[i] {
+        Some(p) => format!("{}::{}", parent_name(&symbols[p]), symbols[i].name),
+        None => symbols[i].name.clone(),
+    };
+
+    let mut matches: Vec<usize> = (0..symbols.len())
+        .filter(|&i| {
+            symbols[i].name == name
+                && qualifier
+                    .is_none_or(|q| parents[i].is_some_and(|p| parent_name(&symbols[p]) == q))
+        })
+        .collect();
+    // `impl Config` shares its name with `struct Config`; prefer the declaration
+    matches.sort_by_key(|&i| symbols[i].kind == "impl");
+
+    let Some((&found, others)) = matches.split_first() else {
+        return Err(ServerError::Tool(symbol_not_found(
+            &input.symbol,
+            &input.path,
+            &symbols,
+        )));
+    };
+    let symbol = &symbols[found];
+
+    let lines: Vec<&str> = content.lines().collect();
+    let end_line = symbol.end_line.unwrap_or(symbol.line).min(lines.len());
+    let mut start_line = symbol.line;
+    if input.include_docs {
+        let hash = hash_comments(&detect_file_type(&full_path));
+        while start_line > 1 && is_doc_or_attribute(lines[start_line - 2], hash) {
+            start_line -= 1;
+        }
+    }
+    let body = lines[start_line - 1..end_line].join("\n");
+
+    Ok(SymbolBodyOutput {
+        content: mark_content_boundary(&body, &input.path),
+        path: input.path,
+        name: qualified(found),
+        kind: symbol.kind.clone(),
+        start_line,
+        end_line,
+        other_matches: others
+            .iter()
+            .map(|&i| format!("{}:{}", qualified(i), symbols[i].line))
+            .collect(),
+    })
+}
+
+/// Error message for a missing symbol, naming the closest declarations.
+fn symbol_not_found(symbol: &str, path: &str, symbols: &[Symbol]) -> String {
+    let (_, name) = split_qualified(symbol.trim());
+    let max = fuzzy::max_distance(name.len());
+    let mut close: Vec<(usize, &str)> = symbols
+        .iter()
+        .filter_map(|s| fuzzy::distance(name, &s.name, max).map(|d| (d, s.name.as_str())))
+        .collect();
+    close.sort_unstable();
+    close.dedup_by_key(|(_, n)| *n);
+
+    if close.is_empty() {
+        let names: Vec<&str> = symbols.iter().take(10).map(|s| s.name.as_str()).collect();
+        if names.is_empty() {
+            format!("Symbol '{symbol}' not found: no symbols detected in {path}")
+        } else {
+            format!(
+                "Symbol '{symbol}' not found in {path}. Declared: {}. Use 'outline' for the full list.",
+                names.join(", ")
+            )
+        }
+    } else {
+        let names: Vec<&str> = close.iter().take(5).map(|(_, n)| *n).collect();
+        format!(
+            "Symbol '{symbol}' not found in {path}. Did you mean: {}?",
+            names.join(", ")
+        )
+    }
+}
+
 /// Input for the toc tool (table of contents for directory).
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct TocInput {
@@ -464,379 +956,6 @@ fn read_context_full(
     Ok((selected, total, start, end))
 }
 
-fn detect_file_type(path: &Path) -> String {
-    path.extension()
-        .and_then(|e| e.to_str())
-        .map(|e| e.to_lowercase())
-        .unwrap_or_else(|| "unknown".to_string())
-}
-
-fn extract_symbols(content: &str, file_type: &str) -> Vec<Symbol> {
-    let lines: Vec<&str> = content.lines().collect();
-    let mut symbols = Vec::new();
-
-    for (line_num, line) in lines.iter().enumerate() {
-        let trimmed = line.trim();
-        let indent = line.len() - line.trim_start().len();
-        let level = indent / 4; // Assume 4-space indentation
-
-        let symbol = match file_type {
-            "rs" | "go" | "js" | "ts" | "jsx" | "tsx" => {
-                let raw = match file_type {
-                    "rs" => extract_rust_symbol(trimmed, line_num + 1, level),
-                    "go" => extract_go_symbol(trimmed, line_num + 1, level),
-                    _ => extract_js_symbol(trimmed, line_num + 1, level),
-                };
-                // Compute end_line via brace tracking for brace-delimited languages
-                raw.map(|mut s| {
-                    s.end_line = find_brace_end(&lines, line_num);
-                    s
-                })
-            }
-            "py" => {
-                let raw = extract_python_symbol(trimmed, line_num + 1, level);
-                // Compute end_line via indent tracking for Python
-                raw.map(|mut s| {
-                    s.end_line = find_indent_end(&lines, line_num, indent);
-                    s
-                })
-            }
-            _ => None,
-        };
-
-        if let Some(s) = symbol {
-            symbols.push(s);
-        }
-    }
-
-    symbols
-}
-
-/// Finds the closing brace for a symbol starting at `start_line` (0-indexed).
-/// Returns the 1-indexed line number of the closing brace, or None.
-fn find_brace_end(lines: &[&str], start_line: usize) -> Option<usize> {
-    let mut depth: i32 = 0;
-    let mut found_open = false;
-
-    for (i, line) in lines.iter().enumerate().skip(start_line) {
-        for ch in line.chars() {
-            if ch == '{' {
-                depth += 1;
-                found_open = true;
-            } else if ch == '}' {
-                depth -= 1;
-                if found_open && depth == 0 {
-                    return Some(i + 1); // 1-indexed
-                }
-            }
-        }
-        // If we found the opening brace but are past it and depth is back to 0
-        if found_open && depth == 0 {
-            return Some(i + 1);
-        }
-    }
-
-    None
-}
-
-/// Finds the end of an indentation block for Python (0-indexed start line).
-/// Returns the 1-indexed line number of the last line in the block.
-fn find_indent_end(lines: &[&str], start_line: usize, base_indent: usize) -> Option<usize> {
-    let mut last_content_line = start_line;
-
-    for (i, line) in lines.iter().enumerate().skip(start_line + 1) {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue; // Skip blank lines
-        }
-        let indent = line.len() - line.trim_start().len();
-        if indent <= base_indent {
-            // We've exited the block
-            return Some(last_content_line + 1); // 1-indexed
-        }
-        last_content_line = i;
-    }
-
-    // Block extends to end of file
-    Some(last_content_line + 1)
-}
-
-fn extract_rust_symbol(line: &str, line_num: usize, level: usize) -> Option<Symbol> {
-    if line.starts_with("fn ") || line.starts_with("pub fn ") || line.starts_with("async fn ") {
-        let name = line
-            .split('(')
-            .next()?
-            .split_whitespace()
-            .last()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "fn".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("struct ") || line.starts_with("pub struct ") {
-        let name = line
-            .split('{')
-            .next()?
-            .split('<')
-            .next()?
-            .split_whitespace()
-            .last()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "struct".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("enum ") || line.starts_with("pub enum ") {
-        let name = line
-            .split('{')
-            .next()?
-            .split_whitespace()
-            .last()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "enum".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("impl ") || line.starts_with("impl<") {
-        let name = line
-            .split('{')
-            .next()?
-            .trim()
-            .trim_start_matches("impl")
-            .trim_start_matches('<')
-            .split('>')
-            .next_back()?
-            .trim()
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "impl".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("trait ") || line.starts_with("pub trait ") {
-        let name = line
-            .split('{')
-            .next()?
-            .split('<')
-            .next()?
-            .split_whitespace()
-            .last()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "trait".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("mod ") || line.starts_with("pub mod ") {
-        let name = line
-            .split('{')
-            .next()?
-            .split(';')
-            .next()?
-            .split_whitespace()
-            .last()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "mod".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    None
-}
-
-fn extract_python_symbol(line: &str, line_num: usize, level: usize) -> Option<Symbol> {
-    if line.starts_with("def ") {
-        let name = line
-            .trim_start_matches("def ")
-            .split('(')
-            .next()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "fn".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("class ") {
-        let name = line
-            .trim_start_matches("class ")
-            .split('(')
-            .next()?
-            .split(':')
-            .next()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "class".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("async def ") {
-        let name = line
-            .trim_start_matches("async def ")
-            .split('(')
-            .next()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "fn".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    None
-}
-
-fn extract_js_symbol(line: &str, line_num: usize, level: usize) -> Option<Symbol> {
-    if line.starts_with("function ")
-        || line.starts_with("async function ")
-        || line.starts_with("export function ")
-        || line.starts_with("export async function ")
-    {
-        let name = line
-            .split('(')
-            .next()?
-            .split_whitespace()
-            .last()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "fn".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("class ") || line.starts_with("export class ") {
-        let name = line
-            .split('{')
-            .next()?
-            .split_whitespace()
-            .find(|w| *w != "class" && *w != "export" && *w != "extends")?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "class".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.contains("const ")
-        && line.contains(" = ")
-        && (line.contains("=>") || line.contains("function"))
-    {
-        let name = line
-            .split('=')
-            .next()?
-            .split_whitespace()
-            .last()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "fn".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    None
-}
-
-fn extract_go_symbol(line: &str, line_num: usize, level: usize) -> Option<Symbol> {
-    if line.starts_with("func ") {
-        let rest = line.trim_start_matches("func ");
-        // Handle method syntax: func (r *Receiver) MethodName()
-        let name = if rest.starts_with('(') {
-            rest.split(')')
-                .nth(1)?
-                .trim()
-                .split('(')
-                .next()?
-                .to_string()
-        } else {
-            rest.split('(').next()?.to_string()
-        };
-        return Some(Symbol {
-            name,
-            kind: "fn".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("type ") && line.contains(" struct") {
-        let name = line
-            .trim_start_matches("type ")
-            .split_whitespace()
-            .next()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "struct".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    if line.starts_with("type ") && line.contains(" interface") {
-        let name = line
-            .trim_start_matches("type ")
-            .split_whitespace()
-            .next()?
-            .to_string();
-        return Some(Symbol {
-            name,
-            kind: "iface".to_string(),
-            line: line_num,
-            end_line: None,
-            level,
-        });
-    }
-
-    None
-}
-
 /// Builds an indented text tree using `ignore::WalkBuilder` to respect `.gitignore`.
 fn build_toc_text(
     path: &Path,
@@ -846,8 +965,6 @@ fn build_toc_text(
     total_files: &mut usize,
     total_dirs: &mut usize,
 ) -> Result<(), String> {
-    use ignore::WalkBuilder;
-
     let walker = WalkBuilder::new(path)
         .git_ignore(true)
         .git_global(true)
@@ -0,0 +1,1429 @@
+//! Call graph, dependency graph and type implementation MCP tools.
+//!
+//! Function bodies come from the line ranges stored in the `symbols`
+//! table; call sites found in them are matched back to declarations by
+//! name, so the call graph covers workspace functions only. File
+//! dependencies come from the `imports` table, resolved at index time.
+//! Type hierarchies come from the supertypes stored with each type.
+//!
+//! # Security
+//!
+//! The deps path is validated against the root directory, and results
+//! from sensitive files are dropped.
+//!
+//! See [`crate::security`] for details.
+
+use super::analysis::{classify_reference, RefKind};
+use super::symbols::{is_test_path, prominence, relativize, split_qualified, CANDIDATE_POOL};
+use crate::db::{escape_like, Database, SymbolData};
+use crate::error::ServerError;
+use crate::security;
+use crate::services::symbols::{self, signature_at};
+use crate::services::SearchService;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// Deepest transitive expansion the calls tool performs.
+const MAX_CALL_DEPTH: usize = 5;
+
+/// Files grepped for a function's name when looking for its callers.
+const CALLER_FILE_POOL: usize = 500;
+
+/// Deepest transitive expansion the deps tool performs.
+const MAX_DEPS_DEPTH: usize = 5;
+
+/// Deepest transitive expansion the hierarchy tool performs.
+const MAX_HIERARCHY_DEPTH: usize = 10;
+
+/// Symbol kinds that declare a concrete type or an implementation.
+const IMPLEMENTOR_KINDS: &[&str] = &["impl", "class", "struct", "enum", "object"];
+
+/// Symbol kinds that can be implemented or extended.
+const SUPERTYPE_KINDS: &[&str] = &["trait", "iface", "class", "struct"];
+
+/// Words followed by `(` that are control flow or operators, not calls.
+const CALL_KEYWORDS: &[&str] = &[
+    "if", "elif", "else", "while", "for", "foreach", "loop", "match", "switch", "case", "return",
+    "catch", "except", "with", "and", "or", "not", "in", "is", "await", "yield", "sizeof",
+    "typeof", "alignof", "assert", "unless", "until", "when", "defined",
+];
+
+/// Words that, right before `name(`, make it a declaration or constructor
+/// expression rather than a function call.
+const DECL_KEYWORDS: &[&str] = &["fn", "def", "function", "func", "class", "struct", "new"];
+
+/// Receivers that name the caller's own type.
+const SELF_RECEIVERS: &[&str] = &["self", "Self", "this", "cls", "super"];
+
+/// Input for the calls tool (call hierarchy).
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CallsInput {
+    /// Function name, optionally qualified (`Type::method`)
+    pub symbol: String,
+    /// Levels of transitive expansion (1 = direct callers/callees only)
+    #[serde(default = "default_calls_depth")]
+    pub depth: usize,
+    /// Maximum callers and callees to return (each)
+    #[serde(default = "default_calls_limit")]
+    pub limit: usize,
+}
+
+const fn default_calls_depth() -> usize {
+    1
+}
+
+const fn default_calls_limit() -> usize {
+    30
+}
+
+/// Output for the calls tool.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CallsOutput {
+    /// The resolved function as `path:line name`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// Functions that call it, nearest first
+    pub callers: Vec<CallEdge>,
+    /// Workspace functions it calls, nearest first
+    pub callees: Vec<CallEdge>,
+    /// Whether either list was cut at the limit
+    pub has_more: bool,
+    /// Hint for unresolved or ambiguous names
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// One function reached in the call graph.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CallEdge {
+    /// Function name, qualified by its parent (`Type::method`)
+    pub name: String,
+    /// File path
+    pub path: String,
+    /// Declaration line
+    pub line: usize,
+    /// Lines of the call sites, in the calling function's file
+    pub call_lines: Vec<usize>,
+    /// Hops from the requested function (1 = direct)
+    pub depth: usize,
+    /// The function this one was reached through (depth > 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+}
+
+/// Executes the calls tool.
+///
+/// Callees are the identifiers invoked as `name(...)` inside the
+/// function's body range, resolved against the symbol index (receivers
+/// and `Type::` qualifiers pick between same-named methods). Callers are
+/// the enclosing functions of every usage-type reference to the name.
+/// With `depth > 1` both directions expand breadth-first; a function
+/// already reached is not expanded again, which also breaks cycles.
+///
+/// # Security
+///
+/// - Results are filtered to exclude sensitive files
+/// - Grep already constrains the caller search to the root directory
+///
+/// # Errors
+///
+/// Returns `ServerError::Tool` for an empty symbol, and
+/// `ServerError::Database` or a search error if a lookup fails.
+pub fn execute_calls(
+    service: &Arc<SearchService>,
+    input: CallsInput,
+) -> crate::error::Result<CallsOutput> {
+    let symbol = input.symbol.trim();
+    if split_qualified(symbol).1.is_empty() {
+        return Err(ServerError::Tool("calls requires a function name".into()));
+    }
+
+    let mut graph = CallGraph::new(service);
+    let targets = graph.resolve_target(symbol)?;
+    let Some(target) = targets.first() else {
+        let hint = if service.db().symbol_count().unwrap_or(0) == 0 {
+            "No symbols indexed. Run 'index' first.".to_string()
+        } else {
+            format!(
+                "No function named '{symbol}' in the symbol index. \
+                 Try 'symbols' with mode=fuzzy, or 'refs' for plain references."
+            )
+        };
+        return Ok(CallsOutput {
+            function: None,
+            callers: Vec::new(),
+            callees: Vec::new(),
+            has_more: false,
+            hint: Some(hint),
+        });
+    };
+
+    let depth = input.depth.clamp(1, MAX_CALL_DEPTH);
+    let (callers, more_callers) = graph.expand(target, Direction::Incoming, depth, input.limit)?;
+    let (callees, more_callees) = graph.expand(target, Direction::Outgoing, depth, input.limit)?;
+
+    let root = service.root();
+    let hint = (targets.len() > 1).then(|| {
+        let others: Vec<String> = targets[1..]
+            .iter()
+            .take(5)
+            .map(|f| format!("{}:{}", relativize(&f.path, root), f.sym.line))
+            .collect();
+        format!(
+            "'{symbol}' has {} definitions; also at {}. Qualify it (Type::name) to pick another.",
+            targets.len(),
+            others.join(", ")
+        )
+    });
+
+    Ok(CallsOutput {
+        function: Some(format!(
+            "{}:{} {}",
+            relativize(&target.path, root),
+            target.sym.line,
+            target.name()
+        )),
+        callers,
+        callees,
+        has_more: more_callers || more_callees,
+        hint,
+    })
+}
+
+/// Which way a graph is walked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    /// Callees, or imported files
+    Outgoing,
+    /// Callers, or importing files
+    Incoming,
+}
+
+/// A function declaration: its stored (absolute) path and symbol.
+#[derive(Debug, Clone)]
+struct FnNode {
+    path: String,
+    sym: SymbolData,
+}
+
+impl FnNode {
+    fn key(&self) -> (String, usize) {
+        (self.path.clone(), self.sym.line)
+    }
+
+    /// The name qualified by its parent (`Type::method`).
+    fn name(&self) -> String {
+        match &self.sym.parent {
+            Some(parent) => format!("{parent}::{}", self.sym.name),
+            None => self.sym.name.clone(),
+        }
+    }
+
+    /// Whether `line` falls inside this function's body range.
+    fn contains(&self, line: usize) -> bool {
+        self.sym
+            .end_line
+            .is_some_and(|end| self.sym.line <= line && line <= end)
+    }
+}
+
+/// A file's indexed content and symbols.
+struct SourceFile {
+    lines: Vec<String>,
+    functions: Vec<FnNode>,
+}
+
+/// Lazily loaded view of the symbol index for call and type graph walks.
+struct CallGraph<'a> {
+    service: &'a SearchService,
+    files: HashMap<String, Option<Arc<SourceFile>>>,
+    functions_named: HashMap<String, Vec<FnNode>>,
+}
+
+impl<'a> CallGraph<'a> {
+    fn new(service: &'a SearchService) -> Self {
+        Self {
+            service,
+            files: HashMap::new(),
+            functions_named: HashMap::new(),
+        }
+    }
+
+    /// Function declarations matching a (qualified) name, best first.
+    fn resolve_target(&mut self, symbol: &str) -> crate::error::Result<Vec<FnNode>> {
+        let (parent, name) = split_qualified(symbol);
+        let mut rows: Vec<FnNode> = self
+            .service
+            .db()
+            .find_symbols(&escape_like(name), Some("fn"), None, CANDIDATE_POOL)?
+            .into_iter()
+            .filter(|(path, sym)| {
+                parent.is_none_or(|p| {
+                    sym.parent
+                        .as_deref()
+                        .is_some_and(|sp| sp.eq_ignore_ascii_case(p))
+                }) && security::is_sensitive_file(Path::new(path)).is_none()
+            })
+            .map(|(path, sym)| FnNode { path, sym })
+            .collect();
+        if rows.iter().any(|f| f.sym.name == name) {
+            rows.retain(|f| f.sym.name == name);
+        }
+
+        let root = self.service.root();
+        rows.sort_by_cached_key(|f| {
+            (
+                is_test_path(&relativize(&f.path, root)),
+                prominence(&f.sym),
+                f.path.clone(),
+                f.sym.line,
+            )
+        });
+        Ok(rows)
+    }
+
+    /// Walks the graph breadth-first from `start`, up to `depth` hops and
+    /// `limit` functions. Returns the edges and whether the limit cut them.
+    fn expand(
+        &mut self,
+        start: &FnNode,
+        direction: Direction,
+        depth: usize,
+        limit: usize,
+    ) -> crate::error::Result<(Vec<CallEdge>, bool)> {
+        let root = self.service.root();
+        let mut visited: HashSet<(String, usize)> = HashSet::from([start.key()]);
+        let mut frontier = vec![start.clone()];
+        let mut edges = Vec::new();
+
+        for level in 1..=depth {
+            let mut next = Vec::new();
+            for node in &frontier {
+                let neighbours = match direction {
+                    Direction::Incoming => self.callers(node)?,
+                    Direction::Outgoing => self.callees(node),
+                };
+                for (function, call_lines) in neighbours {
+                    if !visited.insert(function.key()) {
+                        continue;
+                    }
+                    if edges.len() >= limit {
+                        return Ok((edges, true));
+                    }
+                    edges.push(CallEdge {
+                        name: function.name(),
+                        path: relativize(&function.path, root),
+                        line: function.sym.line,
+                        call_lines,
+                        depth: level,
+                        via: (level > 1).then(|| node.name()),
+                    });
+                    next.push(function);
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        Ok((edges, false))
+    }
+
+    /// Workspace functions called from `caller`'s body, in call order,
+    /// each with the lines calling it.
+    fn callees(&mut self, caller: &FnNode) -> Vec<(FnNode, Vec<usize>)> {
+        let Some(end) = caller.sym.end_line else {
+            return Vec::new();
+        };
+        let Some(file) = self.file(&caller.path) else {
+            return Vec::new();
+        };
+        // Bodies of nested functions belong to those functions
+        let nested: Vec<&FnNode> = file
+            .functions
+            .iter()
+            .filter(|f| {
+                f.sym.line > caller.sym.line && f.sym.line <= end && f.sym.end_line.is_some()
+            })
+            .collect();
+
+        let mut found: Vec<(FnNode, Vec<usize>)> = Vec::new();
+        let mut index: HashMap<(String, usize), usize> = HashMap::new();
+        for line_no in caller.sym.line..=end.min(file.lines.len()) {
+            if nested.iter().any(|f| f.contains(line_no)) {
+                continue;
+            }
+            for (receiver, name) in call_sites(&file.lines[line_no - 1]) {
+                if line_no == caller.sym.line && name == caller.sym.name {
+                    continue;
+                }
+                for callee in self.resolve_call(receiver, name, caller) {
+                    let key = callee.key();
+                    match index.get(&key) {
+                        Some(&i) => {
+                            if found[i].1.last() != Some(&line_no) {
+                                found[i].1.push(line_no);
+                            }
+                        }
+                        None => {
+                            index.insert(key, found.len());
+                            found.push((callee, vec![line_no]));
+                        }
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// Functions whose bodies reference `callee` as a usage, each with the
+    /// referencing lines, in path order.
+    fn callers(&mut self, callee: &FnNode) -> crate::error::Result<Vec<(FnNode, Vec<usize>)>> {
+        let name = &callee.sym.name;
+        let pattern = format!(r"\b{}\b", regex::escape(name));
+        let word = Regex::new(&pattern).map_err(|e| ServerError::Tool(e.to_string()))?;
+        let matches = self
+            .service
+            .search_grep_with_matches(&pattern, CALLER_FILE_POOL)?;
+        let mut paths: Vec<String> = matches
+            .keys()
+            .filter(|p| security::is_sensitive_file(p).is_none())
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        paths.sort();
+
+        let mut found = Vec::new();
+        for path in paths {
+            let Some(file) = self.file(&path) else {
+                continue;
+            };
+            let file_type = symbols::detect_file_type(Path::new(&path));
+            let mut by_caller: Vec<(&FnNode, Vec<usize>)> = Vec::new();
+            for (i, line) in file.lines.iter().enumerate() {
+                let line_no = i + 1;
+                if path == callee.path && line_no == callee.sym.line {
+                    continue;
+                }
+                if !word.is_match(line)
+                    || !references_member(line, name, callee.sym.parent.as_deref())
+                    || !matches!(
+                        classify_reference(line, name, &file_type),
+                        RefKind::Call | RefKind::FieldAccess | RefKind::Usage
+                    )
+                {
+                    continue;
+                }
+                // Innermost enclosing function; top-level code has none
+                let Some(caller) = file.functions.iter().rev().find(|f| f.contains(line_no)) else {
+                    continue;
+                };
+                if caller.key() == callee.key() {
+                    continue;
+                }
+                match by_caller.iter_mut().find(|(f, _)| f.key() == caller.key()) {
+                    Some((_, lines)) => lines.push(line_no),
+                    None => by_caller.push((caller, vec![line_no])),
+                }
+            }
+            found.extend(by_caller.into_iter().map(|(f, lines)| (f.clone(), lines)));
+        }
+        Ok(found)
+    }
+
+    /// Resolves a call site to declarations: the receiver's (or caller's
+    /// own) type first, then the caller's file, then a unique match.
+    fn resolve_call(&mut self, receiver: Option<&str>, name: &str, caller: &FnNode) -> Vec<FnNode> {
+        let candidates = self.functions_named(name);
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let owner = match receiver {
+            Some(r) if SELF_RECEIVERS.contains(&r) => caller.sym.parent.as_deref(),
+            other => other,
+        };
+        if let Some(owner) = owner {
+            let members: Vec<FnNode> = candidates
+                .iter()
+                .filter(|f| {
+                    f.sym
+                        .parent
+                        .as_deref()
+                        .is_some_and(|p| p.eq_ignore_ascii_case(owner))
+                })
+                .cloned()
+                .collect();
+            if !members.is_empty() {
+                return members;
+            }
+            // `Other::name(` on a type we don't index (String::new, Vec::with_capacity)
+            if owner.starts_with(|c: char| c.is_ascii_uppercase()) {
+                return Vec::new();
+            }
+        }
+
+        let same_file: Vec<FnNode> = candidates
+            .iter()
+            .filter(|f| f.path == caller.path)
+            .cloned()
+            .collect();
+        if !same_file.is_empty() {
+            return same_file;
+        }
+        if candidates.len() == 1 {
+            return candidates;
+        }
+        Vec::new()
+    }
+
+    /// All indexed functions named exactly `name`, outside sensitive files.
+    fn functions_named(&mut self, name: &str) -> Vec<FnNode> {
+        if let Some(found) = self.functions_named.get(name) {
+            return found.clone();
+        }
+        let found: Vec<FnNode> = self
+            .service
+            .db()
+            .find_symbols(&escape_like(name), Some("fn"), None, CANDIDATE_POOL)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(path, sym)| {
+                sym.name == name && security::is_sensitive_file(Path::new(path)).is_none()
+            })
+            .map(|(path, sym)| FnNode { path, sym })
+            .collect();
+        self.functions_named.insert(name.to_string(), found.clone());
+        found
+    }
+
+    /// The declaration header of a type, from the indexed content.
+    fn signature(&mut self, decl: &TypeDecl) -> String {
+        self.file(&decl.path)
+            .map(|file| {
+                let lines: Vec<&str> = file.lines.iter().map(String::as_str).collect();
+                signature_at(&lines, decl.sym.line)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Functions declared directly in a type's body, in line order.
+    fn methods(&mut self, decl: &TypeDecl) -> Vec<SymbolData> {
+        let Some(file) = self.file(&decl.path) else {
+            return Vec::new();
+        };
+        let end = decl.sym.end_line.unwrap_or(decl.sym.line);
+        file.functions
+            .iter()
+            .filter(|f| {
+                f.sym.line > decl.sym.line
+                    && f.sym.line <= end
+                    && f.sym.parent.as_deref() == Some(decl.name())
+            })
+            .map(|f| f.sym.clone())
+            .collect()
+    }
+
+    /// Loads a file's content and function symbols from the index.
+    fn file(&mut self, path: &str) -> Option<Arc<SourceFile>> {
+        if let Some(file) = self.files.get(path) {
+            return file.clone();
+        }
+        let db = self.service.db();
+        let file = db
+            .get_file_by_path(path)
+            .ok()
+            .flatten()
+            .map(|(id, content)| {
+                let functions = db
+                    .get_file_symbols(id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|sym| sym.kind == "fn")
+                    .map(|sym| FnNode {
+                        path: path.to_string(),
+                        sym,
+                    })
+                    .collect();
+                Arc::new(SourceFile {
+                    lines: content.lines().map(String::from).collect(),
+                    functions,
+                })
+            });
+        self.files.insert(path.to_string(), file.clone());
+        file
+    }
+}
+
+/// Finds `name(` call sites on a line, with the receiver or qualifier
+/// right before the name (`recv.name(`, `Type::name(`), if any.
+fn call_sites(line: &str) -> Vec<(Option<&str>, &str)> {
+    static CALL: OnceLock<Regex> = OnceLock::new();
+    let re = CALL.get_or_init(|| {
+        Regex::new(r"(?:([A-Za-z_]\w*)\s*(?:::|\.|->)\s*)?\b([A-Za-z_]\w*)\s*(?:::<[^()]*>\s*)?\(")
+            .expect("valid call regex")
+    });
+
+    re.captures_iter(line)
+        .filter_map(|caps| {
+            let whole = caps.get(0)?;
+            let name = caps.get(2)?.as_str();
+            let preceding = line[..whole.start()].split_whitespace().next_back();
+            let is_decl =
+                caps.get(1).is_none() && preceding.is_some_and(|w| DECL_KEYWORDS.contains(&w));
+            (!is_decl && !CALL_KEYWORDS.contains(&name))
+                .then(|| (caps.get(1).map(|m| m.as_str()), name))
+        })
+        .collect()
+}
+
+/// Whether `line` mentions `name` in a way that could refer to a member
+/// of `parent`: through `parent` (or `Self`) itself, or as a call through
+/// a value or unqualified. `Other::name` for another type, and field-like
+/// `value.name` without a call, do not count.
+fn references_member(line: &str, name: &str, parent: Option<&str>) -> bool {
+    let Some(parent) = parent else {
+        return true;
+    };
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    for (pos, _) in line.match_indices(name) {
+        let (before, after) = (&line[..pos], &line[pos + name.len()..]);
+        if before.ends_with(is_ident) || after.starts_with(is_ident) {
+            continue;
+        }
+        let before = before.trim_end();
+        let qualifier = before
+            .strip_suffix("::")
+            .or_else(|| before.strip_suffix('.'))
+            .map(|q| {
+                let start = q.rfind(|c: char| !is_ident(c)).map_or(0, |i| i + 1);
+                &q[start..]
+            });
+        let is_call = after.trim_start().starts_with('(');
+        let is_type = |q: &str| q.starts_with(|c: char| c.is_ascii_uppercase());
+        match qualifier {
+            Some(q) if is_type(q) && (q == parent || q == "Self") => return true,
+            Some(q) if is_type(q) => {}
+            _ if is_call => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Input for the deps tool (file dependency graph).
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DepsInput {
+    /// File path (relative to root)
+    pub path: String,
+    /// Levels of transitive expansion (1 = direct imports/importers only)
+    #[serde(default = "default_deps_depth")]
+    pub depth: usize,
+    /// Maximum files to return per direction
+    #[serde(default = "default_deps_limit")]
+    pub limit: usize,
+}
+
+const fn default_deps_depth() -> usize {
+    1
+}
+
+const fn default_deps_limit() -> usize {
+    50
+}
+
+/// Output for the deps tool.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DepsOutput {
+    /// The file, relative to root
+    pub path: String,
+    /// Workspace files it imports, nearest first
+    pub imports: Vec<DepEdge>,
+    /// Workspace files importing it, nearest first
+    pub imported_by: Vec<DepEdge>,
+    /// Packages it imports from outside the workspace
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external: Vec<String>,
+    /// Whether either list was cut at the limit
+    pub has_more: bool,
+    /// Hint when the file is not indexed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// One file reached in the dependency graph.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DepEdge {
+    /// File path
+    pub path: String,
+    /// Lines of the import statements, in the importing file
+    pub lines: Vec<usize>,
+    /// Hops from the requested file (1 = direct)
+    pub depth: usize,
+    /// The file this one was reached through (depth > 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+}
+
+/// Executes the deps tool.
+///
+/// Reads the import edges stored at index time: `imports` follows the
+/// file's own imports, `imported_by` the files importing it. With
+/// `depth > 1` both directions expand breadth-first, listing each file
+/// once (which also stops at import cycles).
+///
+/// # Security
+///
+/// - Validates the path stays within the root and is not sensitive
+/// - Results are filtered to exclude sensitive files
+///
+/// # Errors
+///
+/// Returns `ServerError::Security` for an invalid path, `ServerError::Tool`
+/// for a directory, and `ServerError::Database` if a lookup fails.
+pub fn execute_deps(
+    service: &Arc<SearchService>,
+    input: DepsInput,
+) -> crate::error::Result<DepsOutput> {
+    let root = service.root();
+    let resolved = security::validate_read_access(root, &input.path)?;
+    if resolved.is_dir() {
+        return Err(ServerError::Tool(
+            "deps expects a file path, not a directory".into(),
+        ));
+    }
+    let canonical_root = dunce::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let rel = resolved.strip_prefix(&canonical_root).unwrap_or(&resolved);
+    let path = root.join(rel).to_string_lossy().to_string();
+    let db = service.db();
+
+    let Some(file_id) = db.get_file_id(&path)? else {
+        return Ok(DepsOutput {
+            path: relativize(&path, root),
+            imports: Vec::new(),
+            imported_by: Vec::new(),
+            external: Vec::new(),
+            has_more: false,
+            hint: Some("File is not indexed. Run 'index' first.".to_string()),
+        });
+    };
+
+    let depth = input.depth.clamp(1, MAX_DEPS_DEPTH);
+    let (imports, more_imports) =
+        expand_deps(db, root, &path, Direction::Outgoing, depth, input.limit)?;
+    let (imported_by, more_importers) =
+        expand_deps(db, root, &path, Direction::Incoming, depth, input.limit)?;
+
+    let file_type = symbols::detect_file_type(Path::new(&path));
+    let mut external: Vec<String> = Vec::new();
+    for (import, target) in db.get_file_imports(file_id)? {
+        if target.is_some() {
+            continue;
+        }
+        if let Some(package) = package_name(&import.spec, &file_type) {
+            if !external.contains(&package) {
+                external.push(package);
+            }
+        }
+    }
+
+    Ok(DepsOutput {
+        path: relativize(&path, root),
+        imports,
+        imported_by,
+        external,
+        has_more: more_imports || more_importers,
+        hint: None,
+    })
+}
+
+/// Walks the import graph breadth-first from `start`, up to `depth` hops
+/// and `limit` files.
+fn expand_deps(
+    db: &Database,
+    root: &Path,
+    start: &str,
+    direction: Direction,
+    depth: usize,
+    limit: usize,
+) -> crate::error::Result<(Vec<DepEdge>, bool)> {
+    let mut visited: HashSet<String> = HashSet::from([start.to_string()]);
+    let mut frontier = vec![start.to_string()];
+    let mut edges = Vec::new();
+
+    for level in 1..=depth {
+        let mut next = Vec::new();
+        for node in &frontier {
+            let Some(file_id) = db.get_file_id(node)? else {
+                continue;
+            };
+            let links: Vec<(String, usize)> = match direction {
+                Direction::Outgoing => db
+                    .get_file_imports(file_id)?
+                    .into_iter()
+                    .filter_map(|(import, target)| Some((target?, import.line)))
+                    .collect(),
+                Direction::Incoming => db.get_importers(file_id)?,
+            };
+
+            let mut grouped: Vec<(String, Vec<usize>)> = Vec::new();
+            for (path, line) in links {
+                if security::is_sensitive_file(Path::new(&path)).is_some() {
+                    continue;
+                }
+                match grouped.iter_mut().find(|(p, _)| *p == path) {
+                    Some((_, lines)) if !lines.contains(&line) => lines.push(line),
+                    Some(_) => {}
+                    None => grouped.push((path, vec![line])),
+                }
+            }
+
+            for (path, lines) in grouped {
+                if !visited.insert(path.clone()) {
+                    continue;
+                }
+                if edges.len() >= limit {
+                    return Ok((edges, true));
+                }
+                edges.push(DepEdge {
+                    path: relativize(&path, root),
+                    lines,
+                    depth: level,
+                    via: (level > 1).then(|| relativize(node, root)),
+                });
+                next.push(path);
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    Ok((edges, false))
+}
+
+/// Input for the implementations tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ImplementationsInput {
+    /// Trait, interface or base class name (qualifiers are ignored)
+    pub name: String,
+    /// Maximum implementors to return
+    #[serde(default = "default_implementations_limit")]
+    pub limit: usize,
+}
+
+const fn default_implementations_limit() -> usize {
+    50
+}
+
+/// Output for the implementations tool.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ImplementationsOutput {
+    /// Where the trait, interface or class is declared, as `path:line name`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    /// Types implementing or extending it, non-test files first
+    pub implementors: Vec<Implementor>,
+    /// Whether the list was cut at the limit
+    pub has_more: bool,
+    /// Hint when nothing was found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// A type declaring that it implements or extends the requested one.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Implementor {
+    /// Implementing type, without generics
+    pub name: String,
+    /// Declaring symbol kind (`impl`, `class`, `struct`, ...)
+    pub kind: String,
+    /// File path
+    pub path: String,
+    /// Line of the `impl` block or type declaration
+    pub line: usize,
+    /// The declaration header
+    pub signature: String,
+}
+
+/// Executes the implementations tool.
+///
+/// Reads the supertypes stored with each type at index time: `impl Name
+/// for Type` blocks and class-like declarations listing the name
+/// (`extends`, `implements`, Python bases, `:` lists). Interfaces and
+/// traits that extend it are not implementors and are skipped;
+/// structurally satisfied interfaces (Go, Python protocols) have no
+/// declaration to find.
+///
+/// # Security
+///
+/// - Results are filtered to exclude sensitive files
+///
+/// # Errors
+///
+/// Returns `ServerError::Tool` for an empty name, and
+/// `ServerError::Database` if a lookup fails.
+pub fn execute_implementations(
+    service: &Arc<SearchService>,
+    input: ImplementationsInput,
+) -> crate::error::Result<ImplementationsOutput> {
+    let name = split_qualified(input.name.trim()).1;
+    if name.is_empty() {
+        return Err(ServerError::Tool(
+            "implementations requires a trait, interface or class name".into(),
+        ));
+    }
+    let root = service.root();
+    let db = service.db();
+
+    let definition = type_definitions(db, root, name)?.first().map(|t| {
+        format!(
+            "{}:{} {}",
+            relativize(&t.path, root),
+            t.sym.line,
+            t.sym.name
+        )
+    });
+
+    let mut graph = CallGraph::new(service);
+    let mut implementors = Vec::new();
+    for decl in subtypes(db, name)? {
+        if !IMPLEMENTOR_KINDS.contains(&decl.sym.kind.as_str()) {
+            continue;
+        }
+        implementors.push(Implementor {
+            name: decl.name().to_string(),
+            signature: graph.signature(&decl),
+            path: relativize(&decl.path, root),
+            line: decl.sym.line,
+            kind: decl.sym.kind,
+        });
+    }
+    implementors.sort_by_cached_key(|i| (is_test_path(&i.path), i.path.clone(), i.line));
+    let has_more = implementors.len() > input.limit;
+    implementors.truncate(input.limit);
+
+    let hint = implementors.is_empty().then(|| {
+        if db.symbol_count().unwrap_or(0) == 0 {
+            "No symbols indexed. Run 'index' first.".to_string()
+        } else {
+            format!(
+                "No declared implementors of '{name}'. Go interfaces and Python \
+                 protocols are satisfied implicitly; try 'refs' for other uses."
+            )
+        }
+    });
+
+    Ok(ImplementationsOutput {
+        definition,
+        implementors,
+        has_more,
+        hint,
+    })
+}
+
+/// Input for the hierarchy tool (type hierarchy).
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HierarchyInput {
+    /// Class or interface name (qualifiers are ignored)
+    pub name: String,
+    /// Levels to walk up and down (1 = direct supertypes/subtypes only)
+    #[serde(default = "default_hierarchy_depth")]
+    pub depth: usize,
+    /// Maximum types to return per direction
+    #[serde(default = "default_hierarchy_limit")]
+    pub limit: usize,
+}
+
+const fn default_hierarchy_depth() -> usize {
+    3
+}
+
+const fn default_hierarchy_limit() -> usize {
+    50
+}
+
+/// Output for the hierarchy tool.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HierarchyOutput {
+    /// The resolved type as `path:line name`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    /// Methods of the type that override an ancestor's
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<Override>,
+    /// Types it extends or implements, nearest first
+    pub ancestors: Vec<TypeEdge>,
+    /// Types extending or implementing it, nearest first
+    pub descendants: Vec<TypeEdge>,
+    /// Supertypes not declared in the workspace (`Exception`, `Serializable`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external: Vec<String>,
+    /// Whether either list was cut at the limit
+    pub has_more: bool,
+    /// Hint for unresolved or ambiguous names
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// One type reached in the type hierarchy.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TypeEdge {
+    /// Type name, without generics
+    pub name: String,
+    /// Symbol kind (`class`, `iface`, `impl`, ...)
+    pub kind: String,
+    /// File path
+    pub path: String,
+    /// Declaration line
+    pub line: usize,
+    /// Hops from the requested type (1 = direct)
+    pub depth: usize,
+    /// The type this one was reached through (depth > 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+    /// Methods it overrides from the types above it (descendants only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<Override>,
+}
+
+/// A method redefining one inherited from a supertype.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Override {
+    /// Method name
+    pub method: String,
+    /// Line of the overriding method
+    pub line: usize,
+    /// The overridden method as `path:line Type.method`
+    pub overrides: String,
+}
+
+/// Executes the hierarchy tool.
+///
+/// Walks the supertypes stored with each type at index time: up through
+/// the names a declaration extends or implements (resolved in its own
+/// file first, then workspace-wide), and down through the types naming
+/// it. Methods defined both by a type and by a type above it are reported
+/// as overrides, pointing at the nearest overridden definition.
+///
+/// # Security
+///
+/// - Results are filtered to exclude sensitive files
+///
+/// # Errors
+///
+/// Returns `ServerError::Tool` for an empty name, and
+/// `ServerError::Database` if a lookup fails.
+pub fn execute_hierarchy(
+    service: &Arc<SearchService>,
+    input: HierarchyInput,
+) -> crate::error::Result<HierarchyOutput> {
+    let name = split_qualified(input.name.trim()).1;
+    if name.is_empty() {
+        return Err(ServerError::Tool(
+            "hierarchy requires a class or interface name".into(),
+        ));
+    }
+    let root = service.root();
+    let db = service.db();
+
+    let targets = type_definitions(db, root, name)?;
+    let Some(target) = targets.first() else {
+        let hint = if db.symbol_count().unwrap_or(0) == 0 {
+            "No symbols indexed. Run 'index' first.".to_string()
+        } else {
+            format!(
+                "No class or interface named '{name}' in the symbol index. \
+                 Try 'symbols' with mode=fuzzy."
+            )
+        };
+        return Ok(HierarchyOutput {
+            class: None,
+            overrides: Vec::new(),
+            ancestors: Vec::new(),
+            descendants: Vec::new(),
+            external: Vec::new(),
+            has_more: false,
+            hint: Some(hint),
+        });
+    };
+
+    let depth = input.depth.clamp(1, MAX_HIERARCHY_DEPTH);
+    let mut graph = CallGraph::new(service);
+
+    // Up: supertypes by name, nearest first
+    let mut ancestors: Vec<(TypeDecl, usize, Option<String>)> = Vec::new();
+    let mut external: Vec<String> = Vec::new();
+    let mut visited: HashSet<(String, usize)> = HashSet::from([target.key()]);
+    let mut frontier = vec![target.clone()];
+    let mut more_ancestors = false;
+    'up: for level in 1..=depth {
+        let mut next = Vec::new();
+        for node in &frontier {
+            for supertype in &node.sym.supertypes {
+                let Some(decl) = resolve_supertype(db, root, supertype, node)? else {
+                    if !external.contains(supertype) {
+                        external.push(supertype.clone());
+                    }
+                    continue;
+                };
+                if !visited.insert(decl.key()) {
+                    continue;
+                }
+                if ancestors.len() >= input.limit {
+                    more_ancestors = true;
+                    break 'up;
+                }
+                let via = (level > 1).then(|| node.name().to_string());
+                ancestors.push((decl.clone(), level, via));
+                next.push(decl);
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    // The methods above the requested type, nearest first
+    let mut inherited: Vec<(TypeDecl, Vec<SymbolData>)> = Vec::new();
+    for (decl, _, _) in &ancestors {
+        inherited.push((decl.clone(), graph.methods(decl)));
+    }
+    let overrides = find_overrides(&graph.methods(target), &inherited, root);
+
+    // Down: types naming it as a supertype, each carrying the methods
+    // above it for override detection
+    let mut descendants: Vec<TypeEdge> = Vec::new();
+    let mut more_descendants = false;
+    let mut frontier = vec![(target.clone(), Arc::new(inherited))];
+    'down: for level in 1..=depth {
+        let mut next = Vec::new();
+        for (node, above) in &frontier {
+            let mut chain = vec![(node.clone(), graph.methods(node))];
+            chain.extend(above.iter().cloned());
+            let chain = Arc::new(chain);
+            for decl in subtypes(db, node.name())? {
+                if !visited.insert(decl.key()) {
+                    continue;
+                }
+                if descendants.len() >= input.limit {
+                    more_descendants = true;
+                    break 'down;
+                }
+                descendants.push(TypeEdge {
+                    name: decl.name().to_string(),
+                    kind: decl.sym.kind.clone(),
+                    path: relativize(&decl.path, root),
+                    line: decl.sym.line,
+                    depth: level,
+                    via: (level > 1).then(|| node.name().to_string()),
+                    overrides: find_overrides(&graph.methods(&decl), &chain, root),
+                });
+                next.push((decl, Arc::clone(&chain)));
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    let hint = (targets.len() > 1).then(|| {
+        let others: Vec<String> = targets[1..]
+            .iter()
+            .take(5)
+            .map(|t| format!("{}:{}", relativize(&t.path, root), t.sym.line))
+            .collect();
+        format!(
+            "'{name}' has {} definitions; also at {}. Subtypes are matched by name.",
+            targets.len(),
+            others.join(", ")
+        )
+    });
+
+    Ok(HierarchyOutput {
+        class: Some(format!(
+            "{}:{} {}",
+            relativize(&target.path, root),
+            target.sym.line,
+            target.name()
+        )),
+        overrides,
+        ancestors: ancestors
+            .into_iter()
+            .map(|(decl, depth, via)| TypeEdge {
+                name: decl.name().to_string(),
+                kind: decl.sym.kind,
+                path: relativize(&decl.path, root),
+                line: decl.sym.line,
+                depth,
+                via,
+                overrides: Vec::new(),
+            })
+            .collect(),
+        descendants,
+        external,
+        has_more: more_ancestors || more_descendants,
+        hint,
+    })
+}
+
+/// A type declaration (or Rust `impl` block): its stored (absolute) path
+/// and symbol.
+#[derive(Debug, Clone)]
+struct TypeDecl {
+    path: String,
+    sym: SymbolData,
+}
+
+impl TypeDecl {
+    fn key(&self) -> (String, usize) {
+        (self.path.clone(), self.sym.line)
+    }
+
+    /// The declared type, without generics; the implementing type for
+    /// `impl Trait for Type` blocks.
+    fn name(&self) -> &str {
+        let name = match self.sym.kind.as_str() {
+            "impl" => self
+                .sym
+                .name
+                .rsplit(" for ")
+                .next()
+                .unwrap_or(&self.sym.name),
+            _ => &self.sym.name,
+        };
+        name.split('<').next().unwrap_or(name).trim()
+    }
+}
+
+/// Declarations of the type `name` (exact case), best first.
+fn type_definitions(db: &Database, root: &Path, name: &str) -> crate::error::Result<Vec<TypeDecl>> {
+    let mut found: Vec<TypeDecl> = db
+        .find_symbols(&escape_like(name), None, None, CANDIDATE_POOL)?
+        .into_iter()
+        .filter(|(path, sym)| {
+            sym.name == name
+                && SUPERTYPE_KINDS.contains(&sym.kind.as_str())
+                && security::is_sensitive_file(Path::new(path)).is_none()
+        })
+        .map(|(path, sym)| TypeDecl { path, sym })
+        .collect();
+    found.sort_by_cached_key(|t| {
+        (
+            is_test_path(&relativize(&t.path, root)),
+            prominence(&t.sym),
+            t.path.clone(),
+            t.sym.line,
+        )
+    });
+    Ok(found)
+}
+
+/// Resolves a supertype named by `from`: a declaration in the same file
+/// first, then the best one in the workspace.
+fn resolve_supertype(
+    db: &Database,
+    root: &Path,
+    name: &str,
+    from: &TypeDecl,
+) -> crate::error::Result<Option<TypeDecl>> {
+    let mut found = type_definitions(db, root, name)?;
+    let local = found.iter().position(|t| t.path == from.path);
+    Ok(match local {
+        Some(i) => Some(found.swap_remove(i)),
+        None => found.into_iter().next(),
+    })
+}
+
+/// Declarations naming `name` as a supertype, outside sensitive files.
+fn subtypes(db: &Database, name: &str) -> crate::error::Result<Vec<TypeDecl>> {
+    Ok(db
+        .find_subtypes(name, CANDIDATE_POOL)?
+        .into_iter()
+        .filter(|(path, _)| security::is_sensitive_file(Path::new(path)).is_none())
+        .map(|(path, sym)| TypeDecl { path, sym })
+        .collect())
+}
+
+/// Methods in `methods` that redefine one of the types in `above`
+/// (nearest first), each pointing at the nearest redefined method.
+fn find_overrides(
+    methods: &[SymbolData],
+    above: &[(TypeDecl, Vec<SymbolData>)],
+    root: &Path,
+) -> Vec<Override> {
+    methods
+        .iter()
+        .filter_map(|method| {
+            above.iter().find_map(|(decl, inherited)| {
+                let base = inherited.iter().find(|m| m.name == method.name)?;
+                Some(Override {
+                    method: method.name.clone(),
+                    line: method.line,
+                    overrides: format!(
+                        "{}:{} {}.{}",
+                        relativize(&decl.path, root),
+                        base.line,
+                        decl.name(),
+                        base.name
+                    ),
+                })
+            })
+        })
+        .collect()
+}
+
+/// The package an unresolved import belongs to (`std` for
+/// `std::collections::HashMap`, `@scope/pkg` for `@scope/pkg/sub`).
+/// Relative specs that failed to resolve name no package.
+fn package_name(spec: &str, file_type: &str) -> Option<String> {
+    let package = match file_type {
+        "rs" => {
+            let first = spec.split("::").next()?;
+            if matches!(first, "crate" | "self" | "super") {
+                return None;
+            }
+            first.to_string()
+        }
+        "py" | "pyi" => {
+            if spec.starts_with('.') {
+                return None;
+            }
+            spec.split('.').next()?.to_string()
+        }
+        "go" => spec.to_string(),
+        _ => {
+            if spec.starts_with('.') || spec.starts_with('/') {
+                return None;
+            }
+            let take = if spec.starts_with('@') { 2 } else { 1 };
+            spec.split('/').take(take).collect::<Vec<_>>().join("/")
+        }
+    };
+    (!package.is_empty()).then_some(package)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_sites() {
+        assert_eq!(
+            call_sites("    let user = authenticate(&config).unwrap();"),
+            vec![(None, "authenticate"), (None, "unwrap")]
+        );
+        assert_eq!(
+            call_sites("let c = Config::load(); self.run::<T>(x)"),
+            vec![(Some("Config"), "load"), (Some("self"), "run")]
+        );
+    }
+
+    #[test]
+    fn test_call_sites_skip_keywords_and_declarations() {
+        assert!(call_sites("if (ready) { return (x); }").is_empty());
+        assert!(call_sites("pub fn load() -> Self {").is_empty());
+        assert!(call_sites("def handler(event):").is_empty());
+        assert!(call_sites("println!(\"{}\", x);").is_empty());
+    }
+
+    #[test]
+    fn test_package_name() {
+        assert_eq!(package_name("std::fmt", "rs").as_deref(), Some("std"));
+        assert_eq!(package_name("crate::db", "rs"), None);
+        assert_eq!(package_name("os.path", "py").as_deref(), Some("os"));
+        assert_eq!(package_name("..models", "py"), None);
+        assert_eq!(
+            package_name("@scope/pkg/sub", "ts").as_deref(),
+            Some("@scope/pkg")
+        );
+        assert_eq!(package_name("./missing", "ts"), None);
+        assert_eq!(package_name("net/http", "go").as_deref(), Some("net/http"));
+    }
+
+    #[test]
+    fn test_references_member() {
+        assert!(references_member(
+            "let u = User::new(x);",
+            "new",
+            Some("User")
+        ));
+        assert!(references_member("Self::new(x)", "new", Some("User")));
+        assert!(references_member(
+            "let u = factory.new(x);",
+            "new",
+            Some("User")
+        ));
+        assert!(!references_member(
+            "let s = String::new();",
+            "new",
+            Some("User")
+        ));
+        assert!(references_member("let s = String::new();", "new", None));
+    }
+
+    #[test]
+    fn test_find_overrides() {
+        let sym = |name: &str, kind: &str, line| SymbolData {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            line,
+            end_line: None,
+            parent: None,
+            supertypes: Vec::new(),
+        };
+        let decl = |name: &str, kind: &str, line| TypeDecl {
+            path: "/repo/animals.py".to_string(),
+            sym: sym(name, kind, line),
+        };
+        // Nearest supertype first: Dog, then Animal
+        let above = vec![
+            (decl("Dog", "class", 9), vec![sym("speak", "fn", 10)]),
+            (
+                decl("Animal", "class", 1),
+                vec![sym("speak", "fn", 2), sym("move", "fn", 5)],
+            ),
+        ];
+        let methods = [
+            sym("speak", "fn", 15),
+            sym("move", "fn", 18),
+            sym("fetch", "fn", 21),
+        ];
+
+        let found: Vec<(String, usize, String)> =
+            find_overrides(&methods, &above, Path::new("/repo"))
+                .into_iter()
+                .map(|o| (o.method, o.line, o.overrides))
+                .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "speak".to_string(),
+                    15,
+                    "animals.py:10 Dog.speak".to_string()
+                ),
+                (
+                    "move".to_string(),
+                    18,
+                    "animals.py:5 Animal.move".to_string()
+                ),
+            ]
+        );
+
+        // An impl block is named after its implementing type
+        let above = vec![(
+            decl("Shape for Circle", "impl", 3),
+            vec![sym("area", "fn", 4)],
+        )];
+        let found = find_overrides(&[sym("area", "fn", 12)], &above, Path::new("/repo"));
+        assert_eq!(found[0].overrides, "animals.py:4 Circle.area");
+    }
+}
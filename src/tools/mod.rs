@@ -2,19 +2,30 @@
 
 mod analysis;
 mod content;
+mod graph;
 mod index;
 mod search;
+mod symbols;
 
 // analysis
 pub use analysis::{
-    execute_refs, execute_stats, IndexSize, Reference, RefsInput, RefsOutput, StatsInput,
-    StatsOutput,
+    execute_refs, execute_stats, execute_tests_for, IndexSize, Reference, RefsFileSummary,
+    RefsGroup, RefsInput, RefsOutput, StatsInput, StatsOutput, TestHit, TestsForInput,
+    TestsForOutput,
 };
 
 // content
 pub use content::{
-    execute_context, execute_get, execute_outline, execute_toc, ContextInput, ContextOutput,
-    GetInput, GetOutput, OutlineInput, OutlineOutput, Symbol, TocInput, TocOutput,
+    execute_context, execute_get, execute_outline, execute_symbol_body, execute_toc, ContextInput,
+    ContextOutput, GetInput, GetOutput, OutlineFormat, OutlineInput, OutlineNode, OutlineOutput,
+    OutlineVerbosity, Symbol, SymbolBodyInput, SymbolBodyOutput, TocInput, TocOutput,
+};
+
+// graph
+pub use graph::{
+    execute_calls, execute_deps, execute_hierarchy, execute_implementations, CallEdge, CallsInput,
+    CallsOutput, DepEdge, DepsInput, DepsOutput, HierarchyInput, HierarchyOutput,
+    ImplementationsInput, ImplementationsOutput, Implementor, Override, TypeEdge,
 };
 
 // index
@@ -25,5 +36,12 @@ pub use index::{
 
 // search
 pub use search::{
-    execute_search, MatchSnippetOutput, SearchInput, SearchMode, SearchOutput, SearchResultItem,
+    execute_search, AggregateBucket, AggregateBy, MatchSnippetOutput, RegionFilter, SearchInput,
+    SearchMode, SearchOutput, SearchResultItem,
+};
+
+// symbols
+pub use symbols::{
+    execute_definition, execute_symbols, Definition, DefinitionInput, DefinitionOutput,
+    SymbolMatch, SymbolsInput, SymbolsOutput,
 };
@@ -1,12 +1,39 @@
 //! Search-related MCP tools.
 
 use crate::security;
-use crate::services::SearchService;
+use crate::services::grep::MAX_SPANS_PER_LINE;
+use crate::services::lexer::{self, Region};
+use crate::services::symbols;
+use crate::services::{diversify, phrase};
+use crate::services::{FuzzyCandidate, MatchSnippet, SearchHit, SearchService, MAX_SNIPPETS};
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 
+/// Maximum grep matches counted for an aggregate search.
+const AGGREGATE_MATCH_BUDGET: usize = 10_000;
+
+/// "Did you mean" candidates listed in a hint.
+const FUZZY_HINT_CANDIDATES: usize = 3;
+
+/// Source marker for fuzzy results, which come from an edit-distance scan
+/// of identifiers rather than any of the search backends.
+const FUZZY_SOURCE: &str = "e";
+
+/// Files named per candidate in a "did you mean" hint.
+const FUZZY_HINT_FILES: usize = 2;
+
+/// Candidate pool multiplier for diversified searches: results are picked
+/// from `limit * N` ranked hits so other directories have a chance.
+const DIVERSIFY_OVERCOLLECT: usize = 3;
+
+/// Candidate pool multiplier for region-filtered searches, which drop the
+/// files whose matches all fall in other regions.
+const REGION_OVERCOLLECT: usize = 3;
+
 /// Relativizes a path against the workspace root.
 fn relativize_path(path: &Path, root: &Path) -> String {
     path.strip_prefix(root)
@@ -22,8 +49,7 @@ fn map_snippets(snippets: &[crate::services::MatchSnippet]) -> Vec<MatchSnippetO
         .map(|s| MatchSnippetOutput {
             line: s.line_number,
             text: s.line_content.clone(),
-            highlight_start: s.match_start,
-            highlight_end: s.match_end,
+            highlights: s.spans.clone(),
         })
         .collect()
 }
@@ -39,6 +65,10 @@ pub enum SearchMode {
     Fts,
     /// Grep regex search only (best for patterns)
     Grep,
+    /// Exact phrase search: FTS5 phrase match verified by grep
+    Phrase,
+    /// Typo-tolerant identifier search: near-miss names and their files
+    Fuzzy,
 }
 
 impl std::fmt::Display for SearchMode {
@@ -47,6 +77,8 @@ impl std::fmt::Display for SearchMode {
             Self::Combined => write!(f, "combined"),
             Self::Fts => write!(f, "fts"),
             Self::Grep => write!(f, "grep"),
+            Self::Phrase => write!(f, "phrase"),
+            Self::Fuzzy => write!(f, "fuzzy"),
         }
     }
 }
@@ -59,25 +91,154 @@ impl std::str::FromStr for SearchMode {
             "combined" => Ok(Self::Combined),
             "fts" => Ok(Self::Fts),
             "grep" => Ok(Self::Grep),
+            "phrase" => Ok(Self::Phrase),
+            "fuzzy" => Ok(Self::Fuzzy),
+            other => Err(format!(
+                "Invalid search mode: '{}'. Valid modes: combined, fts, grep, phrase, fuzzy",
+                other
+            )),
+        }
+    }
+}
+
+/// Grouping key for aggregate searches.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateBy {
+    /// Parent directory of each matching file
+    Directory,
+    /// File extension (files without one group under "(none)")
+    Extension,
+    /// First path component: top-level directory or package
+    Package,
+}
+
+impl std::fmt::Display for AggregateBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Directory => write!(f, "directory"),
+            Self::Extension => write!(f, "extension"),
+            Self::Package => write!(f, "package"),
+        }
+    }
+}
+
+impl std::str::FromStr for AggregateBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "directory" | "dir" => Ok(Self::Directory),
+            "extension" | "ext" => Ok(Self::Extension),
+            "package" | "pkg" => Ok(Self::Package),
+            other => Err(format!(
+                "Invalid aggregate: '{}'. Valid values: directory, extension, package",
+                other
+            )),
+        }
+    }
+}
+
+impl AggregateBy {
+    /// Returns the group key for a root-relative path.
+    fn key(self, relative: &str) -> String {
+        let path = Path::new(relative);
+        match self {
+            Self::Directory => match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => {
+                    parent.to_string_lossy().to_string()
+                }
+                _ => ".".to_string(),
+            },
+            Self::Extension => path
+                .extension()
+                .map_or_else(|| "(none)".to_string(), |e| e.to_string_lossy().to_string()),
+            Self::Package => match relative.split_once('/') {
+                Some((first, _)) => first.to_string(),
+                None => ".".to_string(),
+            },
+        }
+    }
+}
+
+/// Which source regions matches may fall in, as told by the per-language
+/// lexer. Files in languages it does not know are all code.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionFilter {
+    /// Matches anywhere
+    #[default]
+    All,
+    /// Matches outside comments and string literals
+    CodeOnly,
+    /// Matches in comments and docs (including Python docstrings)
+    CommentsOnly,
+    /// Matches in string literals
+    StringsOnly,
+}
+
+impl std::fmt::Display for RegionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::CodeOnly => write!(f, "code_only"),
+            Self::CommentsOnly => write!(f, "comments_only"),
+            Self::StringsOnly => write!(f, "strings_only"),
+        }
+    }
+}
+
+impl std::str::FromStr for RegionFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "all" => Ok(Self::All),
+            "code_only" | "code" => Ok(Self::CodeOnly),
+            "comments_only" | "comments" => Ok(Self::CommentsOnly),
+            "strings_only" | "strings" => Ok(Self::StringsOnly),
             other => Err(format!(
-                "Invalid search mode: '{}'. Valid modes: combined, fts, grep",
+                "Invalid filter: '{}'. Valid values: all, code_only, comments_only, strings_only",
                 other
             )),
         }
     }
 }
 
+impl RegionFilter {
+    /// Whether a match in `region` passes the filter.
+    pub(crate) fn allows(self, region: Region) -> bool {
+        match self {
+            Self::All => true,
+            Self::CodeOnly => region == Region::Code,
+            Self::CommentsOnly => region == Region::Comment,
+            Self::StringsOnly => region == Region::String,
+        }
+    }
+}
+
 /// Input for the search tool.
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct SearchInput {
-    /// Search query (supports regex)
+    /// Search query (supports regex, `"quoted phrases"` and `NEAR(a b, n)`)
     pub query: String,
     /// Maximum results to return (default: 20)
     #[serde(default = "default_limit")]
     pub limit: usize,
-    /// Search mode: "combined", "fts", or "grep"
+    /// Search mode: "combined", "fts", "grep", "phrase", or "fuzzy"
     #[serde(default)]
     pub mode: SearchMode,
+    /// Return match counts grouped by "directory", "extension" or "package"
+    /// instead of a file list
+    #[serde(default)]
+    pub aggregate: Option<AggregateBy>,
+    /// Diversify results across directories, keeping at most this many
+    /// files per directory (default: off)
+    #[serde(default)]
+    pub max_per_directory: Option<usize>,
+    /// Keep only matches in code, comments or string literals
+    #[serde(default)]
+    pub filter: RegionFilter,
 }
 
 const fn default_limit() -> usize {
@@ -91,16 +252,9 @@ pub struct MatchSnippetOutput {
     pub line: u64,
     /// Content of the matching line (trimmed)
     pub text: String,
-    /// Byte offset where the match starts within the line
-    #[serde(skip_serializing_if = "is_zero")]
-    pub highlight_start: usize,
-    /// Byte offset where the match ends within the line
-    #[serde(skip_serializing_if = "is_zero")]
-    pub highlight_end: usize,
-}
-
-const fn is_zero(v: &usize) -> bool {
-    *v == 0
+    /// Byte ranges `[start, end)` of each match within `text`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<(usize, usize)>,
 }
 
 fn round2(v: f64) -> f64 {
@@ -114,11 +268,37 @@ pub struct SearchOutput {
     pub results: Vec<SearchResultItem>,
     /// Whether more results exist beyond the limit
     pub has_more: bool,
+    /// Match counts per group, most matches first (aggregate searches only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aggregates: Vec<AggregateBucket>,
+    /// Directories that hit `max_per_directory`, with how many results were held back
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub collapsed: Vec<CollapsedArea>,
     /// Agent guidance when results are empty or may be incomplete
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hint: Option<String>,
 }
 
+/// A directory whose results were capped by diversification.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CollapsedArea {
+    /// Directory relative to root ("." for the root itself)
+    pub directory: String,
+    /// Ranked results from this directory that were left out
+    pub hidden: usize,
+}
+
+/// Match counts for one group of an aggregate search.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AggregateBucket {
+    /// Directory, extension or top-level package
+    pub key: String,
+    /// Files in the group with at least one match
+    pub files: usize,
+    /// Matching lines across those files
+    pub matches: usize,
+}
+
 /// A single search result.
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct SearchResultItem {
@@ -126,13 +306,21 @@ pub struct SearchResultItem {
     pub path: String,
     /// Relevance score (0.0 - 1.0)
     pub score: f64,
-    /// Search sources that matched (f=fts, g=grep, t=trigram)
+    /// Search sources that matched (f=fts, g=grep, t=trigram; e=edit
+    /// distance, for fuzzy mode)
     pub sources: String,
+    /// Number of matching lines in the file (omitted when unknown)
+    #[serde(skip_serializing_if = "is_zero")]
+    pub match_count: usize,
     /// Matching line snippets (up to 3) showing why this file matched
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub snippets: Vec<MatchSnippetOutput>,
 }
 
+const fn is_zero(v: &usize) -> bool {
+    *v == 0
+}
+
 /// Executes the search tool.
 ///
 /// # Errors
@@ -150,17 +338,79 @@ pub fn execute_search(
         ));
     }
 
-    // Overcollect by 1 to detect if more results exist
-    let request_limit = input.limit + 1;
+    if let Some(by) = input.aggregate {
+        return execute_aggregate(service, &input, by);
+    }
+
+    // Overcollect by 1 to detect if more results exist; diversification
+    // and region filtering additionally need a deeper pool to pick from
+    let max_per_directory = input.max_per_directory.filter(|&n| n > 0);
+    let mut request_limit = match max_per_directory {
+        Some(_) => (input.limit + 1) * DIVERSIFY_OVERCOLLECT,
+        None => input.limit + 1,
+    };
+    let region_pattern = match input.filter {
+        RegionFilter::All => None,
+        _ => {
+            request_limit *= REGION_OVERCOLLECT;
+            Some(line_pattern(&input, "Filtering by region", "filter")?)
+        }
+    };
 
-    let results = match input.mode {
-        SearchMode::Fts => service.search_fts(&input.query, request_limit)?,
-        SearchMode::Grep => service.search_grep(&input.query, request_limit)?,
-        SearchMode::Combined => service.search(&input.query, request_limit)?,
+    // Each mode's search, and what to suggest when it finds nothing
+    let (results, suggestion) = match input.mode {
+        SearchMode::Fts => (
+            service.search_fts(&input.query, request_limit)?,
+            "Try mode=grep for exact regex or mode=combined for broader matching.",
+        ),
+        SearchMode::Grep => (
+            service.search_grep(&input.query, request_limit)?,
+            "Try mode=fts for natural language or mode=combined for broader matching.",
+        ),
+        SearchMode::Combined => (
+            service.search(&input.query, request_limit)?,
+            "Try a broader query, different keywords, or check 'stats' to verify index coverage.",
+        ),
+        SearchMode::Phrase => (
+            service.search_phrase(&input.query, request_limit)?,
+            "Try fewer words, a NEAR(a b, n) query, or mode=combined for looser matching.",
+        ),
+        SearchMode::Fuzzy => return execute_fuzzy(service, &input),
+    };
+    let results = match &region_pattern {
+        Some(pattern) => filter_regions(results, pattern, input.filter)?,
+        None => results,
     };
 
-    let has_more = results.len() > input.limit;
     let root = service.root();
+    let (results, collapsed, has_more) = match max_per_directory {
+        Some(cap) => {
+            let diversify::Diversified {
+                picked: mut kept,
+                collapsed,
+                has_more,
+            } = diversify::diversify(results, root, input.limit, cap);
+            // Picks come in MMR order; present them by score
+            kept.sort_by(|a, b| b.score.as_f64().total_cmp(&a.score.as_f64()));
+            let collapsed = collapsed
+                .into_iter()
+                .map(|(dir, hidden)| CollapsedArea {
+                    directory: if dir.as_os_str().is_empty() {
+                        ".".to_string()
+                    } else {
+                        dir.to_string_lossy().to_string()
+                    },
+                    hidden,
+                })
+                .collect();
+            (kept, collapsed, has_more)
+        }
+        None => {
+            let has_more = results.len() > input.limit;
+            (results, Vec::new(), has_more)
+        }
+    };
+
     let items: Vec<_> = results
         .iter()
         .take(input.limit)
@@ -169,17 +419,23 @@ pub fn execute_search(
             path: relativize_path(&r.path, root),
             score: round2(r.score.as_f64()),
             sources: r.sources.to_compact(),
+            match_count: r.match_count,
             snippets: map_snippets(&r.snippets),
         })
         .collect();
 
     let hint = if items.is_empty() {
-        let suggestion = match input.mode {
-            SearchMode::Grep => "Try mode=fts for natural language or mode=combined for broader matching.",
-            SearchMode::Fts => "Try mode=grep for exact regex or mode=combined for broader matching.",
-            SearchMode::Combined => "Try a broader query, different keywords, or check 'stats' to verify index coverage.",
+        // A misremembered name finds nothing anywhere; offer near misses.
+        // Regexes and prose are not names, so skip the vocabulary scan.
+        let candidates = if input.mode != SearchMode::Grep && is_identifier(&input.query) {
+            service.fuzzy_candidates(&input.query, FUZZY_HINT_CANDIDATES)
+        } else {
+            Vec::new()
         };
-        Some(format!("No results found. {suggestion}"))
+        match did_you_mean(&candidates, root) {
+            Some(dym) => Some(format!("No results found. {dym} {suggestion}")),
+            None => Some(format!("No results found. {suggestion}")),
+        }
     } else {
         None
     };
@@ -187,6 +443,262 @@ pub fn execute_search(
     Ok(SearchOutput {
         results: items,
         has_more,
+        aggregates: Vec::new(),
+        collapsed,
+        hint,
+    })
+}
+
+/// Finds identifiers close to the query and lists the files using them.
+///
+/// Results are the candidates' files, closest candidate first, with the
+/// first occurrence as snippet; the hint names the candidates.
+fn execute_fuzzy(
+    service: &Arc<SearchService>,
+    input: &SearchInput,
+) -> crate::error::Result<SearchOutput> {
+    let root = service.root();
+    let candidates = service.fuzzy_candidates(&input.query, input.limit.max(1));
+
+    let mut seen = std::collections::HashSet::new();
+    let mut items = Vec::new();
+    for candidate in &candidates {
+        let max = crate::services::fuzzy::max_distance(candidate.identifier.len());
+        let score = 1.0 - candidate.distance as f64 / (max + 1) as f64;
+        for (path, snippet) in &candidate.occurrences {
+            if security::is_sensitive_file(path).is_some() || !seen.insert(path.clone()) {
+                continue;
+            }
+            items.push(SearchResultItem {
+                path: relativize_path(path, root),
+                score: round2(score),
+                sources: FUZZY_SOURCE.to_string(),
+                match_count: 0,
+                snippets: map_snippets(std::slice::from_ref(snippet)),
+            });
+        }
+    }
+
+    let has_more = items.len() > input.limit;
+    items.truncate(input.limit);
+
+    let hint = did_you_mean(
+        &candidates[..candidates.len().min(FUZZY_HINT_CANDIDATES)],
+        root,
+    )
+    .or_else(|| {
+        Some(
+            "No similar identifiers found. Check spelling, try mode=fts, or run 'index' \
+                 to refresh."
+                .to_string(),
+        )
+    });
+
+    Ok(SearchOutput {
+        results: items,
+        has_more,
+        aggregates: Vec::new(),
+        collapsed: Vec::new(),
+        hint,
+    })
+}
+
+/// Whether `query` is a single identifier (`execute_serach`,
+/// `SearchService::new`), the only kind of query a did-you-mean hint fits.
+fn is_identifier(query: &str) -> bool {
+    let query = query.trim();
+    query.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && query
+            .split("::")
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'))
+}
+
+/// Formats "did you mean" candidates with a few of their files, e.g.
+/// `Did you mean: SearchService (src/search.rs, src/main.rs +3 more)?`
+fn did_you_mean(candidates: &[FuzzyCandidate], root: &Path) -> Option<String> {
+    let parts: Vec<String> = candidates
+        .iter()
+        .filter_map(|c| {
+            let files: Vec<String> = c
+                .occurrences
+                .iter()
+                .filter(|(p, _)| security::is_sensitive_file(p).is_none())
+                .map(|(p, _)| relativize_path(p, root))
+                .collect();
+            if files.is_empty() {
+                return None;
+            }
+            let shown = files[..files.len().min(FUZZY_HINT_FILES)].join(", ");
+            let more = files.len().saturating_sub(FUZZY_HINT_FILES);
+            Some(if more > 0 {
+                format!("{} ({shown} +{more} more)", c.identifier)
+            } else {
+                format!("{} ({shown})", c.identifier)
+            })
+        })
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(format!("Did you mean: {}?", parts.join(", ")))
+    }
+}
+
+/// The grep pattern whose line matches `feature` works on: the query
+/// itself, or the phrase or `NEAR` pattern for phrase searches and for
+/// combined searches using that syntax.
+fn line_pattern(input: &SearchInput, feature: &str, verb: &str) -> crate::error::Result<String> {
+    let pattern = match input.mode {
+        SearchMode::Fts | SearchMode::Fuzzy => {
+            return Err(crate::error::ServerError::Tool(format!(
+                "{feature} needs line matches, which mode={} does not produce. \
+                 Use mode=combined, grep or phrase.",
+                input.mode
+            )))
+        }
+        SearchMode::Phrase => {
+            let parsed = phrase::parse(&input.query);
+            if parsed.has_structure() {
+                parsed.grep_pattern()
+            } else {
+                phrase::phrase_pattern(&input.query.replace('"', " "))
+            }
+        }
+        // Combined search greps phrase and NEAR queries by their parts
+        SearchMode::Combined if phrase::has_syntax(&input.query) => {
+            let parsed = phrase::parse(&input.query);
+            if parsed.has_structure() {
+                parsed.grep_pattern()
+            } else {
+                Some(input.query.clone())
+            }
+        }
+        SearchMode::Combined | SearchMode::Grep => Some(input.query.clone()),
+    };
+    pattern.filter(|p| !p.trim().is_empty()).ok_or_else(|| {
+        crate::error::ServerError::Tool(format!("Empty query. Provide a pattern to {verb}."))
+    })
+}
+
+/// Keeps the results with a line match of `pattern` in the regions
+/// `filter` allows, replacing their snippets and match counts with those
+/// matches. Files are re-read, since grep only keeps a few snippets each.
+fn filter_regions(
+    results: Vec<SearchHit>,
+    pattern: &str,
+    filter: RegionFilter,
+) -> crate::error::Result<Vec<SearchHit>> {
+    let re = Regex::new(pattern).map_err(|e| crate::error::ServerError::Tool(e.to_string()))?;
+    let mut kept = Vec::with_capacity(results.len());
+    for mut result in results {
+        let Ok(content) = std::fs::read_to_string(&result.path) else {
+            continue;
+        };
+        let regions = lexer::lex(&content, &symbols::detect_file_type(&result.path));
+        let mut snippets = Vec::new();
+        let mut count = 0;
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim_end();
+            let spans: Vec<(usize, usize)> = re
+                .find_iter(line)
+                .map(|m| (m.start(), m.end()))
+                .filter(|&(start, _)| filter.allows(regions.at_line(i + 1, start)))
+                .take(MAX_SPANS_PER_LINE)
+                .collect();
+            if spans.is_empty() {
+                continue;
+            }
+            count += 1;
+            if snippets.len() < MAX_SNIPPETS {
+                let snippet = MatchSnippet {
+                    line_number: i as u64 + 1,
+                    line_content: line.to_string(),
+                    spans,
+                };
+                snippets.push(snippet.clipped());
+            }
+        }
+        if count > 0 {
+            result.match_count = count;
+            result.snippets = snippets;
+            kept.push(result);
+        }
+    }
+    Ok(kept)
+}
+
+/// Counts grep matches for the query and groups them by `by`.
+///
+/// Phrase mode counts the whitespace-tolerant phrase pattern; grep mode
+/// counts the query as a regex, and combined mode does too unless it
+/// holds phrase or `NEAR` syntax. FTS and fuzzy modes have no
+/// per-line matches to count and are rejected.
+fn execute_aggregate(
+    service: &Arc<SearchService>,
+    input: &SearchInput,
+    by: AggregateBy,
+) -> crate::error::Result<SearchOutput> {
+    let pattern = line_pattern(input, "Aggregating", "aggregate")?;
+
+    let (counts, exhausted) = service.count_matches(&pattern, AGGREGATE_MATCH_BUDGET)?;
+
+    let root = service.root();
+    let mut groups: HashMap<String, (usize, usize)> = HashMap::new();
+    for (path, count) in &counts {
+        if security::is_sensitive_file(path).is_some() {
+            continue;
+        }
+        let group = groups
+            .entry(by.key(&relativize_path(path, root)))
+            .or_insert((0, 0));
+        group.0 += 1;
+        group.1 += count;
+    }
+
+    let mut aggregates: Vec<AggregateBucket> = groups
+        .into_iter()
+        .map(|(key, (files, matches))| AggregateBucket {
+            key,
+            files,
+            matches,
+        })
+        .collect();
+    aggregates.sort_by(|a, b| b.matches.cmp(&a.matches).then_with(|| a.key.cmp(&b.key)));
+    let has_more = aggregates.len() > input.limit;
+    aggregates.truncate(input.limit);
+
+    let hint = if aggregates.is_empty() {
+        Some("No matches found. Try a broader pattern or a different mode.".to_string())
+    } else if exhausted {
+        Some(format!(
+            "Counting stopped after {AGGREGATE_MATCH_BUDGET} matches; totals are lower bounds. \
+             Narrow the pattern for exact counts."
+        ))
+    } else {
+        None
+    };
+
+    Ok(SearchOutput {
+        results: Vec::new(),
+        has_more,
+        aggregates,
+        collapsed: Vec::new(),
         hint,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_identifier() {
+        assert!(is_identifier("execute_serach"));
+        assert!(is_identifier(" SearchService::new "));
+        assert!(!is_identifier("fn\\s+main"));
+        assert!(!is_identifier("user session"));
+        assert!(!is_identifier("::new"));
+        assert!(!is_identifier("9lives"));
+    }
+}
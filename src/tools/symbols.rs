@@ -0,0 +1,563 @@
+//! Symbol index MCP tools.
+//!
+//! These tools query the `symbols` table written at index time, so they
+//! answer "where is X declared" without reading or grepping files.
+//!
+//! # Security
+//!
+//! Path filters are validated against the root directory, and results
+//! from sensitive files are dropped.
+//!
+//! See [`crate::security`] for details.
+
+use crate::db::{escape_like, SymbolData};
+use crate::error::ServerError;
+use crate::security;
+use crate::services::symbols::signature_at;
+use crate::services::{fuzzy, SearchService};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Rows fetched per query before ranking (ranking needs more than `limit`).
+pub(crate) const CANDIDATE_POOL: usize = 1000;
+
+/// Distinct fuzzy-matched names whose symbols are fetched.
+const FUZZY_MAX_NAMES: usize = 20;
+
+/// How the symbol query is matched against names (case-insensitive).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolMatch {
+    /// Whole name equals the query
+    Exact,
+    /// Name starts with the query (exact matches rank first)
+    #[default]
+    Prefix,
+    /// Name contains the query anywhere
+    Contains,
+    /// Name is within a few typos of the query
+    Fuzzy,
+}
+
+impl fmt::Display for SymbolMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact => write!(f, "exact"),
+            Self::Prefix => write!(f, "prefix"),
+            Self::Contains => write!(f, "contains"),
+            Self::Fuzzy => write!(f, "fuzzy"),
+        }
+    }
+}
+
+impl FromStr for SymbolMatch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "exact" => Ok(Self::Exact),
+            "prefix" => Ok(Self::Prefix),
+            "contains" => Ok(Self::Contains),
+            "fuzzy" => Ok(Self::Fuzzy),
+            other => Err(format!(
+                "Invalid symbol match: '{}'. Valid values: exact, prefix, contains, fuzzy",
+                other
+            )),
+        }
+    }
+}
+
+/// Input for the symbols tool (workspace symbol search).
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SymbolsInput {
+    /// Symbol name or name fragment
+    pub query: String,
+    /// How the query is matched: "exact", "prefix", "contains", or "fuzzy"
+    #[serde(default)]
+    pub mode: SymbolMatch,
+    /// Only symbols of this kind (fn, struct, enum, trait, class, impl, mod, iface,
+    /// const, static, type, macro)
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// Only symbols in files under this path prefix (relative to root)
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Maximum symbols to return
+    #[serde(default = "default_symbols_limit")]
+    pub limit: usize,
+}
+
+const fn default_symbols_limit() -> usize {
+    30
+}
+
+/// Output for the symbols tool.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SymbolsOutput {
+    /// Matches as `path:line kind name`, best first. Methods are qualified
+    /// by their parent (`Type::method`).
+    pub symbols: Vec<String>,
+    /// Whether more matches exist beyond the limit
+    pub has_more: bool,
+    /// Hint when nothing matched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// Executes the symbols tool.
+///
+/// Ranking: match quality first (exact case > exact > prefix > contains >
+/// fuzzy by distance), then prominence (top-level types, then top-level
+/// functions, then members, then `impl` blocks), then non-test paths.
+///
+/// # Security
+///
+/// - Validates the path filter stays within the root directory
+/// - Results are filtered to exclude sensitive files
+///
+/// # Errors
+///
+/// Returns `ServerError::Tool` for an empty query or an invalid path, and
+/// `ServerError::Database` if the symbol query fails.
+pub fn execute_symbols(
+    service: &Arc<SearchService>,
+    input: SymbolsInput,
+) -> crate::error::Result<SymbolsOutput> {
+    let query = input.query.trim();
+    if query.is_empty() {
+        return Err(ServerError::Tool(
+            "symbols requires a non-empty query".into(),
+        ));
+    }
+
+    let root = service.root();
+    let kind = input.kind.as_deref().map(normalize_kind);
+    let path_like = path_filter(root, input.path.as_deref())?;
+    let db = service.db();
+
+    let rows = match input.mode {
+        SymbolMatch::Exact => db.find_symbols(
+            &escape_like(query),
+            kind,
+            path_like.as_deref(),
+            CANDIDATE_POOL,
+        )?,
+        SymbolMatch::Prefix => db.find_symbols(
+            &format!("{}%", escape_like(query)),
+            kind,
+            path_like.as_deref(),
+            CANDIDATE_POOL,
+        )?,
+        SymbolMatch::Contains => db.find_symbols(
+            &format!("%{}%", escape_like(query)),
+            kind,
+            path_like.as_deref(),
+            CANDIDATE_POOL,
+        )?,
+        SymbolMatch::Fuzzy => {
+            let max = fuzzy::max_distance(query.len());
+            let mut names: Vec<(usize, String)> = db
+                .symbol_names(query.len().saturating_sub(max), query.len() + max)?
+                .into_iter()
+                .filter_map(|name| fuzzy::distance(query, &name, max).map(|d| (d, name)))
+                .collect();
+            names.sort_unstable();
+            let mut rows = Vec::new();
+            for (_, name) in names.into_iter().take(FUZZY_MAX_NAMES) {
+                rows.extend(db.find_symbols(
+                    &escape_like(&name),
+                    kind,
+                    path_like.as_deref(),
+                    CANDIDATE_POOL,
+                )?);
+            }
+            rows
+        }
+    };
+
+    let mut ranked: Vec<(SymbolRank, String, SymbolData)> = rows
+        .into_iter()
+        .filter(|(path, _)| security::is_sensitive_file(Path::new(path)).is_none())
+        .filter_map(|(path, sym)| {
+            let quality = match_quality(query, &sym.name)?;
+            let rel = relativize(&path, root);
+            let rank = SymbolRank {
+                quality,
+                prominence: prominence(&sym),
+                test_path: is_test_path(&rel),
+            };
+            Some((rank, rel, sym))
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.cmp(&b.1))
+            .then_with(|| a.2.line.cmp(&b.2.line))
+    });
+
+    let has_more = ranked.len() > input.limit;
+    let symbols: Vec<String> = ranked
+        .into_iter()
+        .take(input.limit)
+        .map(|(_, path, sym)| format_symbol(&path, &sym))
+        .collect();
+
+    let hint = if symbols.is_empty() {
+        Some(empty_hint(service, &input))
+    } else {
+        None
+    };
+
+    Ok(SymbolsOutput {
+        symbols,
+        has_more,
+        hint,
+    })
+}
+
+/// Input for the definition tool (go to definition).
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DefinitionInput {
+    /// Symbol name, optionally qualified by its parent
+    /// (`merge_results`, `SearchService::merge_results`, `Service.run`)
+    pub symbol: String,
+    /// Only definitions of this kind
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// Maximum definitions to return
+    #[serde(default = "default_definition_limit")]
+    pub limit: usize,
+}
+
+const fn default_definition_limit() -> usize {
+    10
+}
+
+/// Output for the definition tool.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DefinitionOutput {
+    /// Definition sites, most prominent first
+    pub definitions: Vec<Definition>,
+    /// Hint when nothing was found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// A definition site.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Definition {
+    /// File path
+    pub path: String,
+    /// First line of the definition
+    pub line: usize,
+    /// Last line of the body (if detectable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    /// Symbol kind (fn, struct, const, ...)
+    pub kind: String,
+    /// Name, qualified by its parent for members (`Type::method`)
+    pub name: String,
+    /// Declaration up to its body, whitespace-collapsed
+    pub signature: String,
+}
+
+/// Executes the definition tool.
+///
+/// Looks the name up in the symbol index (case-sensitive when any
+/// case-sensitive match exists), skipping `impl` blocks unless asked for.
+///
+/// # Security
+///
+/// - Results are filtered to exclude sensitive files
+///
+/// # Errors
+///
+/// Returns `ServerError::Tool` for an empty symbol, and
+/// `ServerError::Database` if the symbol query fails.
+pub fn execute_definition(
+    service: &Arc<SearchService>,
+    input: DefinitionInput,
+) -> crate::error::Result<DefinitionOutput> {
+    let (parent, name) = split_qualified(input.symbol.trim());
+    if name.is_empty() {
+        return Err(ServerError::Tool(
+            "definition requires a symbol name".into(),
+        ));
+    }
+
+    let root = service.root();
+    let db = service.db();
+    let kind = input.kind.as_deref().map(normalize_kind);
+
+    let mut rows: Vec<(String, SymbolData)> = db
+        .find_symbols(&escape_like(name), kind, None, CANDIDATE_POOL)?
+        .into_iter()
+        .filter(|(path, sym)| {
+            (kind == Some("impl") || sym.kind != "impl")
+                && parent.is_none_or(|p| {
+                    sym.parent
+                        .as_deref()
+                        .is_some_and(|sp| sp.eq_ignore_ascii_case(p))
+                })
+                && security::is_sensitive_file(Path::new(path)).is_none()
+        })
+        .collect();
+    if rows.iter().any(|(_, sym)| sym.name == name) {
+        rows.retain(|(_, sym)| sym.name == name);
+    }
+
+    let mut ranked: Vec<((u8, bool), String, SymbolData)> = rows
+        .into_iter()
+        .map(|(path, sym)| {
+            let rel = relativize(&path, root);
+            ((prominence(&sym), is_test_path(&rel)), path, sym)
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.cmp(&b.1))
+            .then_with(|| a.2.line.cmp(&b.2.line))
+    });
+
+    let mut contents: HashMap<String, Option<String>> = HashMap::new();
+    let definitions: Vec<Definition> = ranked
+        .into_iter()
+        .take(input.limit)
+        .map(|(_, path, sym)| {
+            let content = contents
+                .entry(path.clone())
+                .or_insert_with(|| db.get_file_by_path(&path).ok().flatten().map(|(_, c)| c));
+            let signature = content
+                .as_deref()
+                .map(|c| signature_at(&c.lines().collect::<Vec<_>>(), sym.line))
+                .unwrap_or_default();
+            let name = match &sym.parent {
+                Some(parent) => format!("{parent}::{}", sym.name),
+                None => sym.name.clone(),
+            };
+            Definition {
+                path: relativize(&path, root),
+                line: sym.line,
+                end_line: sym.end_line,
+                kind: sym.kind,
+                name,
+                signature,
+            }
+        })
+        .collect();
+
+    let hint = if definitions.is_empty() {
+        if db.symbol_count().unwrap_or(0) == 0 {
+            Some("No symbols indexed. Run 'index' first.".to_string())
+        } else {
+            Some(format!(
+                "No definition of '{}' in the symbol index. Try 'symbols' with mode=fuzzy, \
+                 or 'refs' to find it by text search.",
+                input.symbol.trim()
+            ))
+        }
+    } else {
+        None
+    };
+
+    Ok(DefinitionOutput { definitions, hint })
+}
+
+/// Splits `Parent::name` or `Parent.name` into `(Some(parent), name)`.
+pub(crate) fn split_qualified(symbol: &str) -> (Option<&str>, &str) {
+    let (qualifier, name) = match symbol.rsplit_once("::") {
+        Some(split) => split,
+        None => match symbol.rsplit_once('.') {
+            Some(split) => split,
+            None => return (None, symbol),
+        },
+    };
+    // Only the innermost qualifier is stored as the parent
+    let parent = qualifier.rsplit(['.', ':']).find(|s| !s.is_empty());
+    (parent, name)
+}
+
+/// Sort key for symbol matches; lower is better.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct SymbolRank {
+    quality: usize,
+    prominence: u8,
+    test_path: bool,
+}
+
+/// Scores how well `name` matches `query`: 0 = exact (case-sensitive),
+/// 1 = exact ignoring case, 2 = prefix, 3 = substring, 4+ = typo distance.
+fn match_quality(query: &str, name: &str) -> Option<usize> {
+    if name == query {
+        return Some(0);
+    }
+    let (q, n) = (query.to_lowercase(), name.to_lowercase());
+    if n == q {
+        Some(1)
+    } else if n.starts_with(&q) {
+        Some(2)
+    } else if n.contains(&q) {
+        Some(3)
+    } else {
+        fuzzy::distance(query, name, fuzzy::max_distance(query.len())).map(|d| 3 + d)
+    }
+}
+
+/// Definition prominence; lower is more prominent.
+pub(crate) fn prominence(sym: &SymbolData) -> u8 {
+    let is_type = matches!(
+        sym.kind.as_str(),
+        "struct" | "enum" | "union" | "trait" | "class" | "iface" | "mod" | "type"
+    );
+    match (sym.kind.as_str(), sym.parent.is_some()) {
+        ("impl", _) => 3,
+        (_, false) if is_type => 0,
+        (_, false) => 1,
+        (_, true) => 2,
+    }
+}
+
+/// Whether a relative path looks like test code: under a test directory
+/// or named like a test file (`foo_test.go`, `test_foo.py`,
+/// `foo.spec.ts`, `FooTest.java`).
+pub(crate) fn is_test_path(rel: &str) -> bool {
+    Path::new(rel).components().any(|c| {
+        let c = c.as_os_str().to_string_lossy();
+        c == "tests"
+            || c == "test"
+            || c == "__tests__"
+            || c == "spec"
+            || c.contains("_test.")
+            || c.contains(".test.")
+            || c.contains(".spec.")
+            || (c.starts_with("test_") && c.contains('.'))
+            || c.contains("Test.")
+            || c.contains("Tests.")
+    })
+}
+
+/// Maps common kind spellings onto the stored kinds.
+fn normalize_kind(kind: &str) -> &str {
+    match kind.trim() {
+        "function" | "func" | "def" | "method" => "fn",
+        "interface" | "protocol" => "iface",
+        "module" | "namespace" => "mod",
+        other => other,
+    }
+}
+
+/// Builds a `LIKE` pattern matching stored paths under `path`.
+fn path_filter(root: &Path, path: Option<&str>) -> crate::error::Result<Option<String>> {
+    let Some(path) = path.map(str::trim).filter(|p| !p.is_empty() && *p != ".") else {
+        return Ok(None);
+    };
+    let resolved = security::validate_path(root, path)?;
+    let canonical_root = dunce::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let rel = resolved.strip_prefix(&canonical_root).unwrap_or(&resolved);
+    let prefix = root.join(rel);
+    Ok(Some(format!("{}%", escape_like(&prefix.to_string_lossy()))))
+}
+
+pub(crate) fn relativize(path: &str, root: &Path) -> String {
+    Path::new(path)
+        .strip_prefix(root)
+        .unwrap_or(Path::new(path))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Formats a match as `path:line kind name`, qualifying members with
+/// their parent (`Type::method`).
+fn format_symbol(path: &str, sym: &SymbolData) -> String {
+    match &sym.parent {
+        Some(parent) if sym.kind != "impl" => {
+            format!("{path}:{} {} {parent}::{}", sym.line, sym.kind, sym.name)
+        }
+        _ => format!("{path}:{} {} {}", sym.line, sym.kind, sym.name),
+    }
+}
+
+fn empty_hint(service: &Arc<SearchService>, input: &SymbolsInput) -> String {
+    if service.db().symbol_count().unwrap_or(0) == 0 {
+        return "No symbols indexed. Run 'index' first.".to_string();
+    }
+    match input.mode {
+        SymbolMatch::Fuzzy => {
+            "No similar symbol names. Try mode=contains or 'search' for non-declarations."
+                .to_string()
+        }
+        _ => "No symbols found. Try mode=contains or mode=fuzzy, or drop the kind/path filters."
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sym(name: &str, kind: &str, parent: Option<&str>) -> SymbolData {
+        SymbolData {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            line: 3,
+            end_line: None,
+            parent: parent.map(String::from),
+            supertypes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_match_quality_order() {
+        assert_eq!(match_quality("Config", "Config"), Some(0));
+        assert_eq!(match_quality("config", "Config"), Some(1));
+        assert_eq!(match_quality("Conf", "ConfigLoader"), Some(2));
+        assert_eq!(match_quality("Config", "AppConfig"), Some(3));
+        assert_eq!(match_quality("Confgi", "Config"), Some(4));
+        assert_eq!(match_quality("Config", "Widget"), None);
+    }
+
+    #[test]
+    fn test_prominence_prefers_top_level_types() {
+        assert!(prominence(&sym("A", "struct", None)) < prominence(&sym("a", "fn", None)));
+        assert!(prominence(&sym("a", "fn", None)) < prominence(&sym("a", "fn", Some("A"))));
+        assert!(prominence(&sym("a", "fn", Some("A"))) < prominence(&sym("A", "impl", None)));
+    }
+
+    #[test]
+    fn test_format_symbol_qualifies_members() {
+        assert_eq!(
+            format_symbol("src/a.rs", &sym("load", "fn", Some("Config"))),
+            "src/a.rs:3 fn Config::load"
+        );
+        assert_eq!(
+            format_symbol("src/a.rs", &sym("Config", "impl", Some("mod_a"))),
+            "src/a.rs:3 impl Config"
+        );
+    }
+
+    #[test]
+    fn test_split_qualified() {
+        assert_eq!(split_qualified("merge"), (None, "merge"));
+        assert_eq!(
+            split_qualified("crate::SearchService::merge"),
+            (Some("SearchService"), "merge")
+        );
+        assert_eq!(split_qualified("Service.run"), (Some("Service"), "run"));
+    }
+
+    #[test]
+    fn test_is_test_path() {
+        assert!(is_test_path("tests/tools.rs"));
+        assert!(is_test_path("src/config_test.go"));
+        assert!(!is_test_path("src/testing_utils.rs"));
+        assert!(is_test_path("pkg/test_models.py"));
+        assert!(is_test_path("web/api.spec.ts"));
+        assert!(is_test_path("src/main/java/AppTest.java"));
+        assert!(!is_test_path("src/Testing.java"));
+    }
+}
@@ -488,6 +488,9 @@ fn test_concurrent_tool_execution() {
                                 query: "function".to_string(),
                                 limit: 10,
                                 mode: SearchMode::Combined,
+                                aggregate: None,
+                                max_per_directory: None,
+                                filter: RegionFilter::All,
                             };
                             let _ = execute_search(&search, input);
                         }
@@ -496,6 +499,9 @@ fn test_concurrent_tool_execution() {
                                 query: "tool".to_string(),
                                 limit: 5,
                                 mode: SearchMode::Fts,
+                                aggregate: None,
+                                max_per_directory: None,
+                                filter: RegionFilter::All,
                             };
                             let _ = execute_search(&search, input);
                         }
@@ -88,6 +88,10 @@ fn test_outline_nonexistent_file() {
 
     let input = OutlineInput {
         path: "ghost.rs".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 30,
+        nested: false,
     };
 
     let result = execute_outline(&search, input);
@@ -127,6 +131,10 @@ fn test_outline_binary_file() {
 
     let input = OutlineInput {
         path: "binary.bin".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 30,
+        nested: false,
     };
 
     // Should handle gracefully - either return empty symbols or error
@@ -175,11 +183,17 @@ fn test_outline_empty_file() {
 
     let input = OutlineInput {
         path: "empty.rs".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 30,
+        nested: false,
     };
 
     let result = execute_outline(&search, input).unwrap();
     assert!(result.symbols.is_empty());
     assert_eq!(result.file_type, "rs");
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["symbols"], serde_json::json!([]));
 }
 
 #[test]
@@ -111,11 +111,55 @@ fn test_outline_tool_blocks_path_traversal() {
         &service,
         OutlineInput {
             path: "../../etc/passwd".to_string(),
+            verbosity: OutlineVerbosity::Names,
+            format: OutlineFormat::Flat,
+            limit: 30,
+            nested: false,
         },
     );
     assert!(result.is_err(), "Should block path traversal");
 }
 
+#[test]
+fn test_outline_tool_glob_blocks_path_traversal() {
+    let (_dir, service, _indexer) = setup_test_services();
+
+    let result = execute_outline(
+        &service,
+        OutlineInput {
+            path: "../**/*.rs".to_string(),
+            verbosity: OutlineVerbosity::Names,
+            format: OutlineFormat::Flat,
+            limit: 30,
+            nested: false,
+        },
+    );
+    assert!(result.is_err(), "Should block glob escaping the root");
+}
+
+#[test]
+fn test_outline_tool_directory_skips_sensitive_files() {
+    let (dir, service, _indexer) = setup_test_services();
+    fs::write(dir.path().join("secrets.json"), "{\"api_key\": \"abc\"}\n").unwrap();
+
+    let result = execute_outline(
+        &service,
+        OutlineInput {
+            path: ".".to_string(),
+            verbosity: OutlineVerbosity::Names,
+            format: OutlineFormat::Flat,
+            limit: 30,
+            nested: false,
+        },
+    )
+    .unwrap();
+    assert!(!result.files.is_empty());
+    assert!(
+        result.files.iter().all(|f| f.path != "secrets.json"),
+        "Should not outline sensitive files"
+    );
+}
+
 #[test]
 fn test_context_tool_blocks_path_traversal() {
     let (_dir, service, _indexer) = setup_test_services();
@@ -145,6 +189,59 @@ fn test_toc_tool_blocks_path_traversal() {
     assert!(result.is_err(), "Should block path traversal");
 }
 
+#[test]
+fn test_deps_tool_blocks_path_traversal_and_sensitive_files() {
+    let (_dir, service, _indexer) = setup_test_services();
+
+    for path in ["../../../etc/passwd", ".env"] {
+        let result = execute_deps(
+            &service,
+            DepsInput {
+                path: path.to_string(),
+                depth: 1,
+                limit: 50,
+            },
+        );
+        assert!(result.is_err(), "Should block {path}");
+    }
+}
+
+#[test]
+fn test_tests_for_tool_blocks_path_traversal_and_sensitive_files() {
+    let (_dir, service, _indexer) = setup_test_services();
+
+    for target in ["../../../etc/passwd", ".env"] {
+        let result = execute_tests_for(
+            &service,
+            TestsForInput {
+                target: target.to_string(),
+                limit: 20,
+            },
+        );
+        assert!(result.is_err(), "Should block {target}");
+    }
+}
+
+#[test]
+fn test_refs_tool_path_blocks_traversal_and_sensitive_files() {
+    let (_dir, service, _indexer) = setup_test_services();
+
+    for path in ["../../../etc/passwd", ".env"] {
+        let result = execute_refs(
+            &service,
+            RefsInput {
+                symbol: "API_KEY".to_string(),
+                symbols: Vec::new(),
+                limit: 50,
+                filter: RegionFilter::All,
+                summary: false,
+                path: Some(path.to_string()),
+            },
+        );
+        assert!(result.is_err(), "Should block {path}");
+    }
+}
+
 #[test]
 fn test_diff_tool_blocks_path_traversal() {
     let (_dir, service, _indexer) = setup_test_services();
@@ -228,6 +325,10 @@ fn test_outline_tool_blocks_sensitive_files() {
         &service,
         OutlineInput {
             path: ".env".to_string(),
+            verbosity: OutlineVerbosity::Names,
+            format: OutlineFormat::Flat,
+            limit: 30,
+            nested: false,
         },
     );
     assert!(result.is_err(), "Should block .env file");
@@ -446,6 +547,9 @@ fn test_search_tool_blocks_redos_patterns() {
             query: "(a+)+".to_string(),
             limit: 10,
             mode: SearchMode::Grep,
+            aggregate: None,
+            max_per_directory: None,
+            filter: RegionFilter::All,
         },
     );
     assert!(result.is_err(), "Should block (a+)+ pattern");
@@ -456,6 +560,9 @@ fn test_search_tool_blocks_redos_patterns() {
             query: "(.*)*".to_string(),
             limit: 10,
             mode: SearchMode::Grep,
+            aggregate: None,
+            max_per_directory: None,
+            filter: RegionFilter::All,
         },
     );
     assert!(result.is_err(), "Should block (.*)* pattern");
@@ -466,6 +573,9 @@ fn test_search_tool_blocks_redos_patterns() {
             query: "(.+)+".to_string(),
             limit: 10,
             mode: SearchMode::Grep,
+            aggregate: None,
+            max_per_directory: None,
+            filter: RegionFilter::All,
         },
     );
     assert!(result.is_err(), "Should block (.+)+ pattern");
@@ -482,6 +592,9 @@ fn test_search_tool_allows_safe_patterns() {
             query: "fn\\s+\\w+".to_string(),
             limit: 10,
             mode: SearchMode::Grep,
+            aggregate: None,
+            max_per_directory: None,
+            filter: RegionFilter::All,
         },
     );
     assert!(result.is_ok(), "Should allow fn\\s+\\w+ pattern");
@@ -492,6 +605,9 @@ fn test_search_tool_allows_safe_patterns() {
             query: "hello.*world".to_string(),
             limit: 10,
             mode: SearchMode::Grep,
+            aggregate: None,
+            max_per_directory: None,
+            filter: RegionFilter::All,
         },
     );
     assert!(result.is_ok(), "Should allow hello.*world pattern");
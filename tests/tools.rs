@@ -155,6 +155,19 @@ impl std::error::Error for AuthError {}
     (dir, search, indexer)
 }
 
+/// Sets up the test services with `files` (relative path, content) written
+/// next to the standard fixtures, then indexes everything.
+fn setup_indexed(files: &[(&str, &str)]) -> (TempDir, Arc<SearchService>) {
+    let (dir, search, indexer) = setup_test_services();
+    for (name, content) in files {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    indexer.index(None, false).unwrap();
+    (dir, search)
+}
+
 // ============================================================================
 // Search Tool Tests
 // ============================================================================
@@ -167,6 +180,9 @@ fn test_search_tool_happy_path() {
         query: "authenticate".to_string(),
         limit: 10,
         mode: SearchMode::Combined,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
     };
 
     let result = execute_search(&search, input).unwrap();
@@ -190,6 +206,9 @@ fn test_search_tool_fts_mode() {
         query: "Config".to_string(),
         limit: 10,
         mode: SearchMode::Fts,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
     };
 
     let result = execute_search(&search, input).unwrap();
@@ -204,6 +223,37 @@ fn test_search_tool_fts_mode() {
     }
 }
 
+#[test]
+fn test_search_tool_fts_mode_has_highlighted_snippets() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SearchInput {
+        query: "credentials".to_string(),
+        limit: 10,
+        mode: SearchMode::Fts,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
+    };
+
+    let result = execute_search(&search, input).unwrap();
+
+    let auth = result
+        .results
+        .iter()
+        .find(|r| r.path == "auth.rs")
+        .expect("auth.rs should match");
+    assert!(!auth.snippets.is_empty(), "FTS-only results get snippets");
+    for snippet in &auth.snippets {
+        assert!(!snippet.highlights.is_empty());
+        for &(start, end) in &snippet.highlights {
+            assert!(snippet.text[start..end]
+                .to_lowercase()
+                .starts_with("credential"));
+        }
+    }
+}
+
 #[test]
 fn test_search_tool_grep_mode() {
     let (_dir, search, _indexer) = setup_test_services();
@@ -212,6 +262,9 @@ fn test_search_tool_grep_mode() {
         query: "pub fn".to_string(),
         limit: 10,
         mode: SearchMode::Grep,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
     };
 
     let result = execute_search(&search, input).unwrap();
@@ -226,6 +279,84 @@ fn test_search_tool_grep_mode() {
     }
 }
 
+#[test]
+fn test_search_tool_phrase_mode() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SearchInput {
+        query: "Invalid credentials".to_string(),
+        limit: 10,
+        mode: SearchMode::Phrase,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
+    };
+
+    let result = execute_search(&search, input).unwrap();
+
+    assert_eq!(result.results.len(), 1);
+    assert_eq!(result.results[0].path, "error.rs");
+    assert_eq!(result.results[0].sources, "fg");
+}
+
+#[test]
+fn test_search_tool_phrase_ignores_case() {
+    let (_dir, search) = setup_indexed(&[
+        ("notes.md", "Error handling matters.\n"),
+        (
+            "strings.rs",
+            "// Error handling in comments\nlet s = \"error handling\";\n",
+        ),
+    ]);
+
+    for (query, mode) in [
+        ("error handling", SearchMode::Phrase),
+        ("\"Error handling\"", SearchMode::Combined),
+    ] {
+        let input = SearchInput {
+            query: query.to_string(),
+            limit: 10,
+            mode,
+            aggregate: None,
+            max_per_directory: None,
+            filter: RegionFilter::All,
+        };
+        let result = execute_search(&search, input).unwrap();
+
+        let mut paths: Vec<&str> = result.results.iter().map(|r| r.path.as_str()).collect();
+        paths.sort_unstable();
+        assert_eq!(paths, ["notes.md", "strings.rs"], "{query}");
+        let strings = result
+            .results
+            .iter()
+            .find(|r| r.path == "strings.rs")
+            .unwrap();
+        assert_eq!(strings.match_count, 2, "{query}");
+    }
+}
+
+#[test]
+fn test_search_tool_quoted_phrase_in_combined_query() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SearchInput {
+        query: "\"pub fn authenticate\" config".to_string(),
+        limit: 10,
+        mode: SearchMode::Combined,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
+    };
+
+    let result = execute_search(&search, input).unwrap();
+
+    assert_eq!(result.results.len(), 1);
+    assert_eq!(result.results[0].path, "auth.rs");
+    assert!(result.results[0].snippets[0]
+        .text
+        .contains("pub fn authenticate"));
+}
+
 #[test]
 fn test_search_tool_no_matches() {
     let (_dir, search, _indexer) = setup_test_services();
@@ -234,6 +365,9 @@ fn test_search_tool_no_matches() {
         query: "xyznonexistent123456".to_string(),
         limit: 10,
         mode: SearchMode::Combined,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
     };
 
     let result = execute_search(&search, input).unwrap();
@@ -249,6 +383,9 @@ fn test_search_tool_respects_limit() {
         query: "fn".to_string(), // Should match many things
         limit: 2,
         mode: SearchMode::Combined,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
     };
 
     let result = execute_search(&search, input).unwrap();
@@ -256,6 +393,285 @@ fn test_search_tool_respects_limit() {
     assert!(result.results.len() <= 2);
 }
 
+#[test]
+fn test_search_tool_reports_match_count() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SearchInput {
+        query: "AuthError".to_string(),
+        limit: 10,
+        mode: SearchMode::Grep,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
+    };
+
+    let result = execute_search(&search, input).unwrap();
+
+    let auth = result.results.iter().find(|r| r.path == "auth.rs").unwrap();
+    assert_eq!(auth.match_count, 4);
+}
+
+#[test]
+fn test_search_tool_aggregate_by_package() {
+    let (dir, search, _indexer) = setup_test_services();
+    fs::write(
+        dir.path().join("src/utils/more.rs"),
+        "// Config helpers\nfn config_path() -> Config {}\n",
+    )
+    .unwrap();
+
+    let input = SearchInput {
+        query: "Config".to_string(),
+        limit: 10,
+        mode: SearchMode::Grep,
+        aggregate: Some(AggregateBy::Package),
+        max_per_directory: None,
+        filter: RegionFilter::All,
+    };
+
+    let result = execute_search(&search, input).unwrap();
+
+    assert!(result.results.is_empty(), "aggregate replaces file list");
+    let root = result.aggregates.iter().find(|b| b.key == ".").unwrap();
+    let src = result.aggregates.iter().find(|b| b.key == "src").unwrap();
+    assert_eq!(root.files, 3); // main.rs, auth.rs, config.rs
+    assert_eq!(src.files, 1);
+    assert_eq!(src.matches, 2);
+    assert!(result.aggregates[0].matches >= result.aggregates[1].matches);
+}
+
+#[test]
+fn test_search_tool_aggregate_by_extension() {
+    let (dir, search, _indexer) = setup_test_services();
+    fs::write(dir.path().join("notes.md"), "Invalid credentials again\n").unwrap();
+
+    let input = SearchInput {
+        query: "Invalid credentials".to_string(),
+        limit: 10,
+        mode: SearchMode::Phrase,
+        aggregate: Some(AggregateBy::Extension),
+        max_per_directory: None,
+        filter: RegionFilter::All,
+    };
+
+    let result = execute_search(&search, input).unwrap();
+
+    let keys: Vec<&str> = result.aggregates.iter().map(|b| b.key.as_str()).collect();
+    assert_eq!(keys, vec!["md", "rs"]);
+}
+
+#[test]
+fn test_search_tool_max_per_directory_collapses_areas() {
+    let (dir, search, _indexer) = setup_test_services();
+    fs::create_dir_all(dir.path().join("tests")).unwrap();
+    for i in 0..4 {
+        fs::write(
+            dir.path().join(format!("tests/widget_{i}.rs")),
+            "widget widget widget\n",
+        )
+        .unwrap();
+    }
+    fs::write(dir.path().join("src/utils/widget.rs"), "widget\n").unwrap();
+
+    let input = SearchInput {
+        query: "widget".to_string(),
+        limit: 3,
+        mode: SearchMode::Grep,
+        aggregate: None,
+        max_per_directory: Some(1),
+        filter: RegionFilter::All,
+    };
+
+    let result = execute_search(&search, input).unwrap();
+
+    let paths: Vec<&str> = result.results.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(paths.len(), 2, "one file per directory: {paths:?}");
+    assert!(paths.contains(&"src/utils/widget.rs"));
+    assert!(result.results.windows(2).all(|w| w[0].score >= w[1].score));
+    assert_eq!(result.collapsed.len(), 1);
+    assert_eq!(result.collapsed[0].directory, "tests");
+    assert_eq!(result.collapsed[0].hidden, 3);
+}
+
+#[test]
+fn test_search_tool_aggregate_combined_phrase_query() {
+    let (dir, search, indexer) = setup_test_services();
+    fs::write(
+        dir.path().join("session.rs"),
+        "// the user session expires\nfn user() {}\n",
+    )
+    .unwrap();
+    indexer.index(None, false).unwrap();
+
+    let input = SearchInput {
+        query: "\"user session\"".to_string(),
+        limit: 10,
+        mode: SearchMode::Combined,
+        aggregate: Some(AggregateBy::Extension),
+        max_per_directory: None,
+        filter: RegionFilter::All,
+    };
+
+    let result = execute_search(&search, input).unwrap();
+
+    // The quotes delimit a phrase; they are not searched for
+    assert_eq!(result.aggregates.len(), 1);
+    assert_eq!(result.aggregates[0].key, "rs");
+    assert_eq!(result.aggregates[0].matches, 1);
+}
+
+#[test]
+fn test_search_tool_aggregate_rejects_fts_mode() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SearchInput {
+        query: "Config".to_string(),
+        limit: 10,
+        mode: SearchMode::Fts,
+        aggregate: Some(AggregateBy::Directory),
+        max_per_directory: None,
+        filter: RegionFilter::All,
+    };
+
+    let err = execute_search(&search, input).unwrap_err().to_string();
+    assert!(
+        err.contains("Aggregating needs line matches, which mode=fts does not produce."),
+        "{err}"
+    );
+}
+
+/// A file mentioning `retry_budget` in a comment, in code and in a string.
+const REGION_FIXTURE: &[(&str, &str)] = &[(
+    "budget.rs",
+    r#"// retry_budget is read once at startup
+fn configure() {
+    let retry_budget = 3;
+    log("retry_budget set");
+}
+"#,
+)];
+
+#[test]
+fn test_search_tool_filters_by_region() {
+    let (_dir, search) = setup_indexed(REGION_FIXTURE);
+
+    let search_in = |filter: RegionFilter| {
+        let input = SearchInput {
+            query: "retry_budget".to_string(),
+            limit: 10,
+            mode: SearchMode::Grep,
+            aggregate: None,
+            max_per_directory: None,
+            filter,
+        };
+        execute_search(&search, input).unwrap().results
+    };
+
+    let all = search_in(RegionFilter::All);
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].match_count, 3);
+
+    for (filter, line) in [
+        (RegionFilter::CodeOnly, 3),
+        (RegionFilter::CommentsOnly, 1),
+        (RegionFilter::StringsOnly, 4),
+    ] {
+        let results = search_in(filter);
+        assert_eq!(results.len(), 1, "{filter}");
+        assert_eq!(results[0].match_count, 1, "{filter}");
+        let lines: Vec<u64> = results[0].snippets.iter().map(|s| s.line).collect();
+        assert_eq!(lines, vec![line], "{filter}");
+    }
+}
+
+#[test]
+fn test_search_tool_filter_combined_phrase_query() {
+    let (_dir, search) = setup_indexed(REGION_FIXTURE);
+
+    let input = SearchInput {
+        query: "\"read once\"".to_string(),
+        limit: 10,
+        mode: SearchMode::Combined,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::CommentsOnly,
+    };
+
+    let output = execute_search(&search, input).unwrap();
+    assert_eq!(output.results.len(), 1, "{:?}", output.hint);
+    assert_eq!(output.results[0].path, "budget.rs");
+    assert_eq!(output.results[0].snippets[0].line, 1);
+}
+
+#[test]
+fn test_search_tool_filter_rejects_fts_mode() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SearchInput {
+        query: "Config".to_string(),
+        limit: 10,
+        mode: SearchMode::Fts,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::CodeOnly,
+    };
+
+    assert!(execute_search(&search, input).is_err());
+}
+
+#[test]
+fn test_search_tool_fuzzy_mode_suggests_identifier() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SearchInput {
+        query: "validate_credentails".to_string(),
+        limit: 10,
+        mode: SearchMode::Fuzzy,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
+    };
+
+    let output = execute_search(&search, input).unwrap();
+    assert!(
+        output.results.iter().any(|r| r.path.contains("auth.rs")),
+        "fuzzy mode should find auth.rs: {:?}",
+        output.results
+    );
+    assert!(output.results.iter().all(|r| r.sources == "e"));
+    let hint = output.hint.expect("fuzzy mode always sets a hint");
+    assert!(
+        hint.contains("Did you mean: validate_credentials (auth.rs"),
+        "hint: {hint}"
+    );
+}
+
+#[test]
+fn test_search_tool_empty_results_offer_did_you_mean() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let search_in = |mode: SearchMode| {
+        let input = SearchInput {
+            query: "authenticat_user".to_string(),
+            limit: 10,
+            mode,
+            aggregate: None,
+            max_per_directory: None,
+            filter: RegionFilter::All,
+        };
+        let output = execute_search(&search, input).unwrap();
+        assert!(output.results.is_empty());
+        output.hint.unwrap()
+    };
+
+    let hint = search_in(SearchMode::Combined);
+    assert!(hint.contains("authenticated_user"), "hint: {hint}");
+    // Grep queries are regexes, not names
+    let hint = search_in(SearchMode::Grep);
+    assert!(!hint.contains("Did you mean"), "hint: {hint}");
+}
+
 // ============================================================================
 // Get Tool Tests
 // ============================================================================
@@ -346,6 +762,10 @@ fn test_outline_tool_rust_file() {
 
     let input = OutlineInput {
         path: "auth.rs".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 30,
+        nested: false,
     };
 
     let result = execute_outline(&search, input).unwrap();
@@ -377,6 +797,10 @@ fn test_outline_tool_extracts_impl_blocks() {
 
     let input = OutlineInput {
         path: "auth.rs".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 30,
+        nested: false,
     };
 
     let result = execute_outline(&search, input).unwrap();
@@ -392,6 +816,10 @@ fn test_outline_tool_includes_line_numbers() {
 
     let input = OutlineInput {
         path: "auth.rs".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 30,
+        nested: false,
     };
 
     let result = execute_outline(&search, input).unwrap();
@@ -401,109 +829,351 @@ fn test_outline_tool_includes_line_numbers() {
     }
 }
 
-// ============================================================================
-// TOC Tool Tests
-// ============================================================================
-
 #[test]
-fn test_toc_tool_happy_path() {
+fn test_outline_tool_full_verbosity_adds_signatures_and_docs() {
     let (_dir, search, _indexer) = setup_test_services();
 
-    let input = TocInput {
-        path: ".".to_string(),
-        depth: 3,
+    let input = OutlineInput {
+        path: "config.rs".to_string(),
+        verbosity: OutlineVerbosity::Full,
+        format: OutlineFormat::Flat,
+        limit: 30,
+        nested: false,
     };
 
-    let result = execute_toc(&search, input).unwrap();
+    let result = execute_outline(&search, input).unwrap();
 
-    assert!(!result.tree.is_empty());
-    assert!(result.total_files > 0);
+    let config = &result.symbols[0];
+    assert_eq!(config.name, "Config");
+    assert_eq!(config.doc.as_deref(), Some("Application configuration."));
+    assert_eq!(config.visibility.as_deref(), Some("pub"));
 
-    // Should find our test files
-    assert!(result.tree.contains("main.rs"), "Should include main.rs");
+    let load = result.symbols.iter().find(|s| s.name == "load").unwrap();
+    assert_eq!(load.signature.as_deref(), Some("pub fn load() -> Self"));
+    assert_eq!(load.visibility.as_deref(), Some("pub"));
+    assert_eq!(load.parent.as_deref(), Some("Config"));
 }
 
 #[test]
-fn test_toc_tool_respects_depth() {
+fn test_outline_tool_tree_nests_methods_under_impl() {
     let (_dir, search, _indexer) = setup_test_services();
 
-    // Depth 1 should only show top-level items
-    let input = TocInput {
-        path: ".".to_string(),
-        depth: 1,
+    let input = OutlineInput {
+        path: "config.rs".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Tree,
+        limit: 30,
+        nested: false,
     };
 
-    let result = execute_toc(&search, input).unwrap();
+    let result = execute_outline(&search, input).unwrap();
 
-    // At depth 1, directories should be listed but no children shown
-    // The tree string should contain top-level items but no indented entries
-    assert!(!result.tree.is_empty());
-    // No lines should have 2-space indentation (depth 1 means only top-level)
-    assert!(
-        !result.tree.lines().any(|l| l.starts_with("  ")),
-        "At depth 1, directories should have no children listed"
-    );
+    assert!(result.symbols.is_empty());
+    let imp = result
+        .tree
+        .iter()
+        .find(|n| n.symbol.kind == "impl" && n.symbol.name == "Config")
+        .expect("impl Config should be a root");
+    let methods: Vec<&str> = imp
+        .children
+        .iter()
+        .map(|c| c.symbol.name.as_str())
+        .collect();
+    assert!(methods.contains(&"load"), "methods: {methods:?}");
+    assert!(result.tree.iter().all(|n| n.symbol.name != "load"));
 }
 
 #[test]
-fn test_toc_tool_nested_directory() {
+fn test_outline_tool_text_format_is_indented() {
     let (_dir, search, _indexer) = setup_test_services();
 
-    let input = TocInput {
-        path: "src".to_string(),
-        depth: 3,
+    let input = OutlineInput {
+        path: "config.rs".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Text,
+        limit: 30,
+        nested: false,
     };
 
-    let result = execute_toc(&search, input).unwrap();
+    let result = execute_outline(&search, input).unwrap();
 
-    assert!(!result.tree.is_empty());
-    // Should find utils subdirectory (listed with trailing slash)
-    assert!(
-        result.tree.contains("utils/"),
-        "Should find utils subdirectory"
-    );
+    let text = result.text.expect("text format should fill text");
+    assert!(text.starts_with("struct Config :2-5\n"), "text: {text}");
+    assert!(text.contains("\nimpl Config :7-"), "text: {text}");
+    assert!(text.contains("\n  fn load :8-13\n"), "text: {text}");
 }
 
-// ============================================================================
-// Context Tool Tests
-// ============================================================================
-
 #[test]
-fn test_context_tool_happy_path() {
-    let (_dir, search, _indexer) = setup_test_services();
+fn test_outline_tool_directory_lists_top_level_symbols_per_file() {
+    let (dir, search, _indexer) = setup_test_services();
+    fs::write(
+        dir.path().join("src/utils/parse.rs"),
+        "pub struct Parser;\n\nimpl Parser {\n    pub fn run(&self) {}\n}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn parses() {}\n}\n",
+    )
+    .unwrap();
 
-    let input = ContextInput {
-        path: "auth.rs".to_string(),
-        line: 5,
-        context_lines: 3,
+    let input = OutlineInput {
+        path: "src/utils".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 30,
+        nested: false,
     };
 
-    let result = execute_context(&search, input).unwrap();
-
-    assert!(!result.content.is_empty());
-    assert_eq!(result.center_line, 5);
+    let result = execute_outline(&search, input).unwrap();
 
-    // Should include line numbers in output
-    assert!(
-        result.content.contains("|"),
-        "Context should include line number formatting"
-    );
+    assert_eq!(result.file_type, "directory");
+    let paths: Vec<&str> = result.files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, vec!["src/utils/helpers.rs", "src/utils/parse.rs"]);
 
-    // Should mark the center line
-    assert!(
-        result.content.contains(">"),
-        "Should mark the center line with >"
-    );
+    let parse: Vec<&str> = result.files[1]
+        .symbols
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(parse, vec!["Parser", "Parser"], "no methods or test module");
 }
 
 #[test]
-fn test_context_tool_respects_context_size() {
+fn test_outline_tool_glob_respects_file_limit() {
     let (_dir, search, _indexer) = setup_test_services();
 
-    let input = ContextInput {
-        path: "auth.rs".to_string(),
-        line: 10,
-        context_lines: 2,
+    let input = OutlineInput {
+        path: "*.rs".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 2,
+        nested: false,
+    };
+
+    let result = execute_outline(&search, input).unwrap();
+
+    assert_eq!(result.file_type, "glob");
+    assert_eq!(result.files.len(), 2);
+    assert_eq!(result.files[0].path, "auth.rs");
+    assert!(result.has_more);
+    assert!(result.hint.unwrap().contains("file limit"));
+}
+
+#[test]
+fn test_outline_tool_bracketed_filename_is_not_a_glob() {
+    let (dir, search, _indexer) = setup_test_services();
+    fs::create_dir_all(dir.path().join("pages")).unwrap();
+    fs::write(
+        dir.path().join("pages/[id].tsx"),
+        "export function Page() {\n  return null;\n}\n",
+    )
+    .unwrap();
+
+    let input = OutlineInput {
+        path: "pages/[id].tsx".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 20,
+        nested: false,
+    };
+
+    let result = execute_outline(&search, input).unwrap();
+
+    assert_eq!(result.file_type, "tsx");
+    assert_eq!(result.symbols.len(), 1);
+    assert_eq!(result.symbols[0].name, "Page");
+}
+
+#[test]
+fn test_outline_tool_markdown_headings() {
+    let (dir, search, _indexer) = setup_test_services();
+    std::fs::write(
+        dir.path().join("GUIDE.md"),
+        "# Guide\n\n## Setup\n\nSteps.\n\n## Usage\n\nRun it.\n",
+    )
+    .unwrap();
+
+    let input = OutlineInput {
+        path: "GUIDE.md".to_string(),
+        verbosity: OutlineVerbosity::Names,
+        format: OutlineFormat::Flat,
+        limit: 30,
+        nested: false,
+    };
+
+    let result = execute_outline(&search, input).unwrap();
+
+    let headings: Vec<_> = result
+        .symbols
+        .iter()
+        .map(|s| (s.kind.as_str(), s.name.as_str(), s.end_line))
+        .collect();
+    assert_eq!(
+        headings,
+        vec![
+            ("h1", "Guide", Some(9)),
+            ("h2", "Setup", Some(5)),
+            ("h2", "Usage", Some(9)),
+        ]
+    );
+}
+
+// ============================================================================
+// Symbol Body Tool Tests
+// ============================================================================
+
+#[test]
+fn test_symbol_body_tool_includes_docs_and_prefers_declaration() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SymbolBodyInput {
+        path: "config.rs".to_string(),
+        symbol: "Config".to_string(),
+        include_docs: true,
+    };
+    let result = execute_symbol_body(&search, input).unwrap();
+
+    assert_eq!(result.kind, "struct");
+    assert_eq!((result.start_line, result.end_line), (1, 5));
+    assert!(result
+        .content
+        .starts_with("--- BEGIN FILE CONTENT: config.rs ---"));
+    assert!(result.content.contains("/// Application configuration."));
+    assert!(result.content.contains("timeout: u64,"));
+    assert!(!result.content.contains("impl Config"));
+    assert_eq!(result.other_matches, vec!["Config:7"]);
+}
+
+#[test]
+fn test_symbol_body_tool_qualified_method() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SymbolBodyInput {
+        path: "config.rs".to_string(),
+        symbol: "Config::load".to_string(),
+        include_docs: false,
+    };
+    let result = execute_symbol_body(&search, input).unwrap();
+
+    assert_eq!(result.name, "Config::load");
+    assert_eq!((result.start_line, result.end_line), (8, 13));
+    assert!(result.content.contains("pub fn load() -> Self {"));
+    assert!(!result.content.contains("pub fn credentials"));
+}
+
+#[test]
+fn test_symbol_body_tool_unknown_symbol_suggests_names() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = SymbolBodyInput {
+        path: "config.rs".to_string(),
+        symbol: "Confg".to_string(),
+        include_docs: true,
+    };
+    let err = execute_symbol_body(&search, input).unwrap_err();
+
+    assert!(err.to_string().contains("Did you mean: Config"), "{err}");
+}
+
+// ============================================================================
+// TOC Tool Tests
+// ============================================================================
+
+#[test]
+fn test_toc_tool_happy_path() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = TocInput {
+        path: ".".to_string(),
+        depth: 3,
+    };
+
+    let result = execute_toc(&search, input).unwrap();
+
+    assert!(!result.tree.is_empty());
+    assert!(result.total_files > 0);
+
+    // Should find our test files
+    assert!(result.tree.contains("main.rs"), "Should include main.rs");
+}
+
+#[test]
+fn test_toc_tool_respects_depth() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    // Depth 1 should only show top-level items
+    let input = TocInput {
+        path: ".".to_string(),
+        depth: 1,
+    };
+
+    let result = execute_toc(&search, input).unwrap();
+
+    // At depth 1, directories should be listed but no children shown
+    // The tree string should contain top-level items but no indented entries
+    assert!(!result.tree.is_empty());
+    // No lines should have 2-space indentation (depth 1 means only top-level)
+    assert!(
+        !result.tree.lines().any(|l| l.starts_with("  ")),
+        "At depth 1, directories should have no children listed"
+    );
+}
+
+#[test]
+fn test_toc_tool_nested_directory() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = TocInput {
+        path: "src".to_string(),
+        depth: 3,
+    };
+
+    let result = execute_toc(&search, input).unwrap();
+
+    assert!(!result.tree.is_empty());
+    // Should find utils subdirectory (listed with trailing slash)
+    assert!(
+        result.tree.contains("utils/"),
+        "Should find utils subdirectory"
+    );
+}
+
+// ============================================================================
+// Context Tool Tests
+// ============================================================================
+
+#[test]
+fn test_context_tool_happy_path() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = ContextInput {
+        path: "auth.rs".to_string(),
+        line: 5,
+        context_lines: 3,
+    };
+
+    let result = execute_context(&search, input).unwrap();
+
+    assert!(!result.content.is_empty());
+    assert_eq!(result.center_line, 5);
+
+    // Should include line numbers in output
+    assert!(
+        result.content.contains("|"),
+        "Context should include line number formatting"
+    );
+
+    // Should mark the center line
+    assert!(
+        result.content.contains(">"),
+        "Should mark the center line with >"
+    );
+}
+
+#[test]
+fn test_context_tool_respects_context_size() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = ContextInput {
+        path: "auth.rs".to_string(),
+        line: 10,
+        context_lines: 2,
     };
 
     let result = execute_context(&search, input).unwrap();
@@ -574,7 +1244,11 @@ fn test_refs_tool_finds_usages() {
 
     let input = RefsInput {
         symbol: "authenticate".to_string(),
+        symbols: Vec::new(),
         limit: 50,
+        filter: RegionFilter::All,
+        summary: false,
+        path: None,
     };
 
     let result = execute_refs(&search, input).unwrap();
@@ -598,7 +1272,11 @@ fn test_refs_tool_respects_limit() {
 
     let input = RefsInput {
         symbol: "fn".to_string(), // Common keyword
+        symbols: Vec::new(),
         limit: 3,
+        filter: RegionFilter::All,
+        summary: false,
+        path: None,
     };
 
     let result = execute_refs(&search, input).unwrap();
@@ -612,12 +1290,1258 @@ fn test_refs_tool_no_matches() {
 
     let input = RefsInput {
         symbol: "xyznonexistent123".to_string(),
+        symbols: Vec::new(),
         limit: 50,
+        filter: RegionFilter::All,
+        summary: false,
+        path: None,
+    };
+
+    let result = execute_refs(&search, input).unwrap();
+
+    assert!(result.references.is_empty());
+}
+
+#[test]
+fn test_refs_tool_classifies_by_scope() {
+    let (_dir, search, indexer) = setup_test_services();
+    indexer.index(None, false).unwrap();
+
+    let refs = |symbol: &str| {
+        let input = RefsInput {
+            symbol: symbol.to_string(),
+            symbols: Vec::new(),
+            limit: 50,
+            filter: RegionFilter::All,
+            summary: false,
+            path: None,
+        };
+        execute_refs(&search, input).unwrap().references
+    };
+    let find = |refs: &[Reference], path: &str, line: usize| {
+        refs.iter()
+            .find(|r| r.path == path && r.line == line)
+            .map(|r| (r.ref_type.clone(), r.enclosing.clone()))
+            .unwrap_or_else(|| panic!("no reference at {path}:{line}"))
+    };
+
+    let api_key = refs("api_key");
+    // Struct fields are definitions, struct literals and member reads are not
+    assert_eq!(
+        find(&api_key, "config.rs", 3),
+        ("definition".to_string(), Some("Config".to_string()))
+    );
+    assert_eq!(
+        find(&api_key, "config.rs", 10),
+        ("usage".to_string(), Some("Config::load".to_string()))
+    );
+    assert_eq!(
+        find(&api_key, "config.rs", 17),
+        (
+            "field_access".to_string(),
+            Some("Config::credentials".to_string())
+        )
+    );
+
+    let authenticate = refs("authenticate");
+    assert_eq!(
+        find(&authenticate, "main.rs", 3),
+        ("call".to_string(), Some("main".to_string()))
+    );
+    assert_eq!(find(&authenticate, "auth.rs", 5).0, "definition");
+
+    let display = refs("Display");
+    assert_eq!(find(&display, "error.rs", 10).0, "trait_impl");
+
+    let config = refs("Config");
+    assert_eq!(find(&config, "auth.rs", 1).0, "import");
+    assert_eq!(
+        find(&config, "auth.rs", 5),
+        ("type_usage".to_string(), Some("authenticate".to_string()))
+    );
+}
+
+#[test]
+fn test_refs_tool_tags_and_filters_regions() {
+    let (_dir, search) = setup_indexed(REGION_FIXTURE);
+
+    let refs = |filter: RegionFilter| {
+        let input = RefsInput {
+            symbol: "retry_budget".to_string(),
+            symbols: Vec::new(),
+            limit: 50,
+            filter,
+            summary: false,
+            path: None,
+        };
+        execute_refs(&search, input)
+            .unwrap()
+            .references
+            .into_iter()
+            .map(|r| (r.line, r.region, r.ref_type))
+            .collect::<Vec<_>>()
     };
 
+    assert_eq!(
+        refs(RegionFilter::All),
+        vec![
+            (1, "comment".to_string(), "comment".to_string()),
+            (3, "code".to_string(), "definition".to_string()),
+            (4, "string".to_string(), "usage".to_string()),
+        ]
+    );
+    assert_eq!(
+        refs(RegionFilter::CodeOnly),
+        vec![(3, "code".to_string(), "definition".to_string())]
+    );
+    assert_eq!(refs(RegionFilter::CommentsOnly)[0].0, 1);
+    assert_eq!(refs(RegionFilter::StringsOnly)[0].0, 4);
+}
+
+#[test]
+fn test_refs_tool_ranks_defining_file_first() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let result = execute_refs(
+        &search,
+        RefsInput {
+            symbol: "Config".to_string(),
+            symbols: Vec::new(),
+            limit: 2,
+            filter: RegionFilter::All,
+            summary: false,
+            path: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(result.references.len(), 2);
+    assert!(result.references.iter().all(|r| r.path == "config.rs"));
+    assert!(result.total > 2);
+    assert!(result.has_more);
+    assert!(result.hint.unwrap().contains("summary"));
+}
+
+#[test]
+fn test_refs_tool_summary_counts_per_file() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let input = RefsInput {
+        symbol: "Config".to_string(),
+        symbols: Vec::new(),
+        limit: 50,
+        filter: RegionFilter::All,
+        summary: true,
+        path: None,
+    };
     let result = execute_refs(&search, input).unwrap();
 
     assert!(result.references.is_empty());
+    assert_eq!(result.files[0].path, "config.rs");
+    assert_eq!(
+        result.files.iter().map(|f| f.total).sum::<usize>(),
+        result.total
+    );
+    let auth = result.files.iter().find(|f| f.path == "auth.rs").unwrap();
+    assert_eq!(auth.counts.get("import"), Some(&1));
+    assert_eq!(auth.counts.values().sum::<usize>(), auth.total);
+    let sites: Vec<(&str, usize)> = result
+        .definitions
+        .iter()
+        .map(|d| (d.path.as_str(), d.line))
+        .collect();
+    assert_eq!(sites, vec![("config.rs", 2)]);
+    assert!(result.hint.unwrap().contains("config.rs"));
+
+    let input = RefsInput {
+        symbol: "Config".to_string(),
+        symbols: Vec::new(),
+        limit: 1,
+        filter: RegionFilter::All,
+        summary: true,
+        path: None,
+    };
+    let result = execute_refs(&search, input).unwrap();
+    assert_eq!(result.files.len(), 1);
+    assert!(result.has_more);
+}
+
+#[test]
+fn test_refs_tool_path_drills_into_one_file() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let input = RefsInput {
+        symbol: "Config".to_string(),
+        symbols: Vec::new(),
+        limit: 50,
+        filter: RegionFilter::All,
+        summary: false,
+        path: Some("auth.rs".to_string()),
+    };
+    let result = execute_refs(&search, input).unwrap();
+
+    assert!(!result.references.is_empty());
+    assert!(result.references.iter().all(|r| r.path == "auth.rs"));
+    assert!(!result.has_more);
+
+    let input = RefsInput {
+        symbol: "Config".to_string(),
+        symbols: Vec::new(),
+        limit: 50,
+        filter: RegionFilter::All,
+        summary: false,
+        path: Some("src".to_string()),
+    };
+    assert!(execute_refs(&search, input).is_err());
+}
+
+/// Two types with an `open` method, and calls through paths and receivers.
+const QUALIFIED_FIXTURE: &[(&str, &str)] = &[(
+    "store.rs",
+    r#"pub struct Store;
+
+impl Store {
+    pub fn open(path: &str) -> Store {
+        Self::check(path);
+        Store
+    }
+
+    fn check(path: &str) {}
+}
+
+pub struct Cache;
+
+impl Cache {
+    pub fn open() -> Cache {
+        Cache
+    }
+}
+
+fn run(store: &Store, other: Thing) {
+    let open = Store::open("a");
+    let c = Cache::open();
+    store.check("b");
+    other.check("c");
+    let file = File::open("x");
+}
+
+pub struct Thing;
+
+impl Thing {
+    fn check(&self, path: &str) {}
+}
+"#,
+)];
+
+#[test]
+fn test_refs_tool_qualified_symbol_separates_types() {
+    let (_dir, search) = setup_indexed(QUALIFIED_FIXTURE);
+
+    let result = execute_refs(
+        &search,
+        RefsInput {
+            symbol: "Store::open".to_string(),
+            symbols: Vec::new(),
+            limit: 50,
+            filter: RegionFilter::All,
+            summary: false,
+            path: None,
+        },
+    )
+    .unwrap();
+    let lines: Vec<_> = result
+        .references
+        .iter()
+        .map(|r| (r.line, r.via.as_deref()))
+        .collect();
+    assert_eq!(lines, [(4, Some("definition")), (21, Some("qualified"))]);
+    assert_eq!(result.references[0].ref_type, "definition");
+    // The `let open` binding on the same line is not the qualified use
+    assert_eq!(result.references[1].ref_type, "call");
+
+    let result = execute_refs(
+        &search,
+        RefsInput {
+            symbol: "Cache::open".to_string(),
+            symbols: Vec::new(),
+            limit: 50,
+            filter: RegionFilter::All,
+            summary: false,
+            path: None,
+        },
+    )
+    .unwrap();
+    let lines: Vec<_> = result.references.iter().map(|r| r.line).collect();
+    assert_eq!(lines, [15, 22]);
+}
+
+#[test]
+fn test_refs_tool_qualified_symbol_uses_receiver_types() {
+    let (_dir, search) = setup_indexed(QUALIFIED_FIXTURE);
+
+    let result = execute_refs(
+        &search,
+        RefsInput {
+            symbol: "Store::check".to_string(),
+            symbols: Vec::new(),
+            limit: 50,
+            filter: RegionFilter::All,
+            summary: false,
+            path: None,
+        },
+    )
+    .unwrap();
+    let lines: Vec<_> = result
+        .references
+        .iter()
+        .map(|r| (r.line, r.via.as_deref()))
+        .collect();
+    // `other` has an unrelated type, so line 24 is left out and reported
+    assert_eq!(
+        lines,
+        [
+            (5, Some("self")),
+            (9, Some("definition")),
+            (23, Some("receiver_type"))
+        ]
+    );
+    assert!(!result.has_more);
+    assert!(result.hint.unwrap().contains("Skipped 1"));
+}
+
+#[test]
+fn test_refs_tool_batch_groups_per_symbol() {
+    let (_dir, search) = setup_indexed(QUALIFIED_FIXTURE);
+
+    let input = RefsInput {
+        symbol: "Store::open".to_string(),
+        symbols: vec![
+            "Cache::open".to_string(),
+            "Config".to_string(),
+            "Store::open".to_string(),
+            "xyznonexistent123".to_string(),
+        ],
+        limit: 1,
+        filter: RegionFilter::All,
+        summary: false,
+        path: None,
+    };
+    let result = execute_refs(&search, input).unwrap();
+
+    let symbols: Vec<&str> = result.groups.iter().map(|g| g.symbol.as_str()).collect();
+    assert_eq!(
+        symbols,
+        ["Store::open", "Cache::open", "Config", "xyznonexistent123"]
+    );
+    assert!(result.references.is_empty());
+    // The limit applies per symbol
+    for group in &result.groups[..3] {
+        assert_eq!(group.refs.references.len(), 1);
+    }
+    assert_eq!(result.groups[0].refs.references[0].line, 4);
+    assert_eq!(result.groups[1].refs.references[0].line, 15);
+    assert_eq!(result.groups[2].refs.references[0].path, "config.rs");
+    assert_eq!(result.groups[3].refs.total, 0);
+    assert_eq!(
+        result.total,
+        result.groups.iter().map(|g| g.refs.total).sum::<usize>()
+    );
+    assert!(result.has_more);
+}
+
+#[test]
+fn test_refs_tool_batch_rejects_empty_and_oversized() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    for symbol in ["", "Store::"] {
+        let input = RefsInput {
+            symbol: symbol.to_string(),
+            symbols: Vec::new(),
+            limit: 10,
+            filter: RegionFilter::All,
+            summary: false,
+            path: None,
+        };
+        assert!(execute_refs(&search, input).is_err(), "{symbol}");
+    }
+
+    let input = RefsInput {
+        symbol: "Config".to_string(),
+        symbols: (0..25).map(|i| format!("sym{i}")).collect(),
+        limit: 10,
+        filter: RegionFilter::All,
+        summary: false,
+        path: None,
+    };
+    assert!(execute_refs(&search, input).is_err());
+}
+
+// ============================================================================
+// Symbols Tool Tests
+// ============================================================================
+
+#[test]
+fn test_symbols_tool_prefix_ranks_exact_type_first() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let result = execute_symbols(
+        &search,
+        SymbolsInput {
+            query: "Config".to_string(),
+            mode: SymbolMatch::Prefix,
+            kind: None,
+            path: None,
+            limit: 30,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(result.symbols[0], "config.rs:2 struct Config");
+    // The impl block shares the name but is not the declaration
+    assert_eq!(result.symbols[1], "config.rs:7 impl Config");
+    assert!(result.hint.is_none());
+}
+
+#[test]
+fn test_symbols_tool_kind_and_path_filters() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let input = SymbolsInput {
+        query: "e".to_string(),
+        mode: SymbolMatch::Contains,
+        kind: Some("function".to_string()),
+        path: Some("src".to_string()),
+        limit: 30,
+    };
+    let result = execute_symbols(&search, input).unwrap();
+
+    assert_eq!(
+        result.symbols,
+        vec!["src/utils/helpers.rs:1 fn helper_function"]
+    );
+}
+
+#[test]
+fn test_symbols_tool_fuzzy_tolerates_typos() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let result = execute_symbols(
+        &search,
+        SymbolsInput {
+            query: "validate_credentails".to_string(),
+            mode: SymbolMatch::Fuzzy,
+            kind: None,
+            path: None,
+            limit: 30,
+        },
+    )
+    .unwrap();
+
+    assert!(
+        result.symbols[0].starts_with("auth.rs:")
+            && result.symbols[0].ends_with("fn validate_credentials"),
+        "got {:?}",
+        result.symbols
+    );
+}
+
+#[test]
+fn test_symbols_tool_hints_when_not_indexed() {
+    let (_dir, search, _indexer) = setup_test_services();
+
+    let result = execute_symbols(
+        &search,
+        SymbolsInput {
+            query: "Config".to_string(),
+            mode: SymbolMatch::Exact,
+            kind: None,
+            path: None,
+            limit: 30,
+        },
+    )
+    .unwrap();
+
+    assert!(result.symbols.is_empty());
+    assert!(result.hint.unwrap().contains("Run 'index'"));
+}
+
+#[test]
+fn test_symbols_tool_rejects_path_traversal() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let input = SymbolsInput {
+        query: "Config".to_string(),
+        mode: SymbolMatch::Prefix,
+        kind: None,
+        path: Some("../".to_string()),
+        limit: 30,
+    };
+
+    assert!(execute_symbols(&search, input).is_err());
+}
+
+#[test]
+fn test_definition_tool_qualified_name() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let input = DefinitionInput {
+        symbol: "Config::load".to_string(),
+        kind: None,
+        limit: 10,
+    };
+    let result = execute_definition(&search, input).unwrap();
+
+    assert_eq!(result.definitions.len(), 1);
+    let def = &result.definitions[0];
+    assert_eq!(def.path, "config.rs");
+    assert_eq!((def.line, def.end_line), (8, Some(13)));
+    assert_eq!(def.name, "Config::load");
+    assert_eq!(def.signature, "pub fn load() -> Self");
+}
+
+#[test]
+fn test_definition_tool_skips_impl_blocks_and_finds_consts() {
+    let (_dir, search) = setup_indexed(&[(
+        "limits.rs",
+        "pub(crate) const MAX_RETRIES: u32 = 3;\npub(crate) async fn retry() {}\n",
+    )]);
+
+    let config = execute_definition(
+        &search,
+        DefinitionInput {
+            symbol: "Config".to_string(),
+            kind: None,
+            limit: 10,
+        },
+    )
+    .unwrap();
+    assert_eq!(config.definitions.len(), 1);
+    assert_eq!(config.definitions[0].kind, "struct");
+
+    let retries = execute_definition(
+        &search,
+        DefinitionInput {
+            symbol: "MAX_RETRIES".to_string(),
+            kind: None,
+            limit: 10,
+        },
+    )
+    .unwrap();
+    assert_eq!(retries.definitions[0].kind, "const");
+    assert_eq!(
+        retries.definitions[0].signature,
+        "pub(crate) const MAX_RETRIES: u32 = 3;"
+    );
+
+    let retry = execute_definition(
+        &search,
+        DefinitionInput {
+            symbol: "retry".to_string(),
+            kind: Some("fn".to_string()),
+            limit: 10,
+        },
+    )
+    .unwrap();
+    assert_eq!(retry.definitions[0].path, "limits.rs");
+}
+
+#[test]
+fn test_definition_tool_unknown_symbol_hints() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let input = DefinitionInput {
+        symbol: "NoSuchThing".to_string(),
+        kind: None,
+        limit: 10,
+    };
+    let result = execute_definition(&search, input).unwrap();
+
+    assert!(result.definitions.is_empty());
+    assert!(result.hint.unwrap().contains("mode=fuzzy"));
+}
+
+// ============================================================================
+// Calls Tool Tests
+// ============================================================================
+
+#[test]
+fn test_calls_tool_direct_callers_and_callees() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let result = execute_calls(
+        &search,
+        CallsInput {
+            symbol: "authenticate".to_string(),
+            depth: 1,
+            limit: 30,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(result.function.as_deref(), Some("auth.rs:5 authenticate"));
+    assert_eq!(result.callers.len(), 1);
+    assert_eq!(result.callers[0].name, "main");
+    assert_eq!(result.callers[0].call_lines, vec![3]);
+
+    let callees: Vec<(&str, &str, Vec<usize>)> = result
+        .callees
+        .iter()
+        .map(|e| (e.name.as_str(), e.path.as_str(), e.call_lines.clone()))
+        .collect();
+    assert_eq!(
+        callees,
+        vec![
+            ("Config::credentials", "config.rs", vec![6]),
+            ("validate_credentials", "auth.rs", vec![7]),
+            ("User::new", "auth.rs", vec![8]),
+        ]
+    );
+}
+
+#[test]
+fn test_calls_tool_expands_transitively() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let result = execute_calls(
+        &search,
+        CallsInput {
+            symbol: "main".to_string(),
+            depth: 2,
+            limit: 30,
+        },
+    )
+    .unwrap();
+
+    let direct: Vec<&str> = result
+        .callees
+        .iter()
+        .filter(|e| e.depth == 1)
+        .map(|e| e.name.as_str())
+        .collect();
+    assert_eq!(direct, vec!["Config::load", "authenticate", "User::name"]);
+
+    let hop = result
+        .callees
+        .iter()
+        .find(|e| e.name == "validate_credentials")
+        .expect("reached through authenticate");
+    assert_eq!(hop.depth, 2);
+    assert_eq!(hop.via.as_deref(), Some("authenticate"));
+    // Three levels down, outside the requested depth
+    assert!(!result
+        .callees
+        .iter()
+        .any(|e| e.name == "Credentials::is_valid"));
+}
+
+#[test]
+fn test_calls_tool_stops_at_cycles() {
+    let (_dir, search) = setup_indexed(&[(
+        "pingpong.rs",
+        "fn ping(n: u32) {\n    if n > 0 {\n        pong(n - 1);\n    }\n}\n\n\
+         fn pong(n: u32) {\n    ping(n);\n}\n",
+    )]);
+
+    let result = execute_calls(
+        &search,
+        CallsInput {
+            symbol: "ping".to_string(),
+            depth: 5,
+            limit: 30,
+        },
+    )
+    .unwrap();
+
+    let callees: Vec<(&str, usize)> = result
+        .callees
+        .iter()
+        .map(|e| (e.name.as_str(), e.depth))
+        .collect();
+    assert_eq!(callees, vec![("pong", 1)]);
+    let callers: Vec<&str> = result.callers.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(callers, vec!["pong"]);
+}
+
+#[test]
+fn test_calls_tool_unknown_function_hints() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let result = execute_calls(
+        &search,
+        CallsInput {
+            symbol: "does_not_exist".to_string(),
+            depth: 1,
+            limit: 30,
+        },
+    )
+    .unwrap();
+
+    assert!(result.function.is_none());
+    assert!(result.callers.is_empty() && result.callees.is_empty());
+    assert!(result.hint.unwrap().contains("No function named"));
+}
+
+// ============================================================================
+// Deps Tool Tests
+// ============================================================================
+
+#[test]
+fn test_deps_tool_imports_and_importers() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let auth = execute_deps(
+        &search,
+        DepsInput {
+            path: "auth.rs".to_string(),
+            depth: 1,
+            limit: 50,
+        },
+    )
+    .unwrap();
+    let imports: Vec<(&str, Vec<usize>)> = auth
+        .imports
+        .iter()
+        .map(|e| (e.path.as_str(), e.lines.clone()))
+        .collect();
+    assert_eq!(imports, vec![("config.rs", vec![1]), ("error.rs", vec![2])]);
+
+    let config = execute_deps(
+        &search,
+        DepsInput {
+            path: "config.rs".to_string(),
+            depth: 1,
+            limit: 50,
+        },
+    )
+    .unwrap();
+    assert!(config.imports.is_empty());
+    let importers: Vec<&str> = config.imported_by.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(importers, vec!["auth.rs"]);
+
+    let error = execute_deps(
+        &search,
+        DepsInput {
+            path: "error.rs".to_string(),
+            depth: 1,
+            limit: 50,
+        },
+    )
+    .unwrap();
+    assert_eq!(error.external, vec!["std"]);
+}
+
+#[test]
+fn test_deps_tool_expands_transitively() {
+    let (_dir, search) = setup_indexed(&[(
+        "app.rs",
+        "use crate::auth::authenticate;\n\npub fn run() {}\n",
+    )]);
+
+    let result = execute_deps(
+        &search,
+        DepsInput {
+            path: "config.rs".to_string(),
+            depth: 2,
+            limit: 50,
+        },
+    )
+    .unwrap();
+
+    let importers: Vec<(&str, usize, Option<&str>)> = result
+        .imported_by
+        .iter()
+        .map(|e| (e.path.as_str(), e.depth, e.via.as_deref()))
+        .collect();
+    assert_eq!(
+        importers,
+        vec![("auth.rs", 1, None), ("app.rs", 2, Some("auth.rs"))]
+    );
+}
+
+#[test]
+fn test_deps_tool_resolves_imports_of_files_added_later() {
+    let (dir, search, indexer) = setup_test_services();
+    fs::write(dir.path().join("app.rs"), "use crate::session::Session;\n").unwrap();
+    indexer.index(None, false).unwrap();
+    let app_imports = || {
+        let input = DepsInput {
+            path: "app.rs".to_string(),
+            depth: 1,
+            limit: 50,
+        };
+        execute_deps(&search, input).unwrap().imports
+    };
+    assert!(app_imports().is_empty());
+
+    fs::write(dir.path().join("session.rs"), "pub struct Session;\n").unwrap();
+    indexer.index(None, false).unwrap();
+
+    let imports = app_imports();
+    assert_eq!(imports.len(), 1);
+    assert_eq!(imports[0].path, "session.rs");
+}
+
+#[test]
+fn test_deps_tool_unindexed_file_hints() {
+    let (dir, search, _indexer) = setup_test_services();
+    fs::write(dir.path().join("fresh.rs"), "use crate::config::Config;\n").unwrap();
+
+    let result = execute_deps(
+        &search,
+        DepsInput {
+            path: "fresh.rs".to_string(),
+            depth: 1,
+            limit: 50,
+        },
+    )
+    .unwrap();
+
+    assert!(result.imports.is_empty() && result.imported_by.is_empty());
+    assert!(result.hint.unwrap().contains("not indexed"));
+}
+
+// ============================================================================
+// Implementations Tool Tests
+// ============================================================================
+
+/// Rust, TypeScript and Python types implementing or extending `Shape`.
+const SHAPE_FIXTURES: &[(&str, &str)] = &[
+    (
+        "shape.rs",
+        r#"pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub trait Solid: Shape {}
+
+pub struct Circle(f64);
+
+impl Shape for Circle {
+    fn area(&self) -> f64 { self.0 }
+}
+
+impl<T: Shape> crate::shape::Shape for Box<T> {
+    fn area(&self) -> f64 { 0.0 }
+}
+
+pub fn total(shapes: &[&dyn Shape]) -> f64 {
+    shapes.iter().map(|s| s.area()).sum()
+}
+"#,
+    ),
+    (
+        "store.ts",
+        r#"export interface Shape {
+  area(): number;
+}
+
+export class Square implements Shape {
+  area() { return 1; }
+}
+
+export class Cube<T extends Shape> extends Square implements Shape, Printable {
+}
+"#,
+    ),
+    (
+        "models.py",
+        "class Shape:\n    pass\n\n\nclass Triangle(base.Shape, metaclass=Meta):\n    pass\n",
+    ),
+];
+
+#[test]
+fn test_implementations_tool_across_languages() {
+    let (_dir, search) = setup_indexed(SHAPE_FIXTURES);
+
+    let result = execute_implementations(
+        &search,
+        ImplementationsInput {
+            name: "Shape".to_string(),
+            limit: 50,
+        },
+    )
+    .unwrap();
+
+    let found: Vec<(&str, &str, usize, &str)> = result
+        .implementors
+        .iter()
+        .map(|i| (i.path.as_str(), i.name.as_str(), i.line, i.kind.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("models.py", "Triangle", 5, "class"),
+            ("shape.rs", "Circle", 9, "impl"),
+            ("shape.rs", "Box", 13, "impl"),
+            ("store.ts", "Square", 5, "class"),
+            ("store.ts", "Cube", 9, "class"),
+        ]
+    );
+    assert!(result.definition.is_some());
+    assert!(!result.has_more);
+}
+
+#[test]
+fn test_implementations_tool_follows_extends() {
+    let (_dir, search) = setup_indexed(SHAPE_FIXTURES);
+
+    let result = execute_implementations(
+        &search,
+        ImplementationsInput {
+            name: "Square".to_string(),
+            limit: 50,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(result.implementors.len(), 1);
+    assert_eq!(result.implementors[0].name, "Cube");
+    assert_eq!(result.definition.as_deref(), Some("store.ts:5 Square"));
+}
+
+#[test]
+fn test_implementations_tool_hints_when_none_found() {
+    let (_dir, search) = setup_indexed(SHAPE_FIXTURES);
+
+    let result = execute_implementations(
+        &search,
+        ImplementationsInput {
+            name: "Config".to_string(),
+            limit: 50,
+        },
+    )
+    .unwrap();
+
+    assert!(result.implementors.is_empty());
+    assert!(result.hint.unwrap().contains("No declared implementors"));
+}
+
+// ============================================================================
+// Hierarchy Tool Tests
+// ============================================================================
+
+/// A Python class chain with overrides and Java interface implementors.
+const CLASS_FIXTURES: &[(&str, &str)] = &[
+    (
+        "animals.py",
+        r#"class Animal:
+    def speak(self):
+        pass
+
+    def move(self):
+        pass
+
+
+class Dog(Animal):
+    def speak(self):
+        return "woof"
+
+
+class Puppy(Dog):
+    def speak(self):
+        return "yip"
+
+    def move(self):
+        pass
+
+
+class Robot(errors.Fault, Exception):
+    pass
+"#,
+    ),
+    (
+        "Service.java",
+        "public interface Service {\n  void run();\n}\n",
+    ),
+    (
+        "Worker.java",
+        "public class Worker implements Service {\n  public void run() {}\n  void idle() {}\n}\n",
+    ),
+];
+
+#[test]
+fn test_hierarchy_tool_ancestors_descendants_and_overrides() {
+    let (_dir, search) = setup_indexed(CLASS_FIXTURES);
+
+    let result = execute_hierarchy(
+        &search,
+        HierarchyInput {
+            name: "Dog".to_string(),
+            depth: 3,
+            limit: 50,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(result.class.as_deref(), Some("animals.py:9 Dog"));
+    let overrides: Vec<(&str, usize, &str)> = result
+        .overrides
+        .iter()
+        .map(|o| (o.method.as_str(), o.line, o.overrides.as_str()))
+        .collect();
+    assert_eq!(overrides, vec![("speak", 10, "animals.py:2 Animal.speak")]);
+
+    assert_eq!(result.ancestors.len(), 1);
+    assert_eq!(result.ancestors[0].name, "Animal");
+    assert_eq!(result.descendants.len(), 1);
+    let puppy = &result.descendants[0];
+    assert_eq!((puppy.name.as_str(), puppy.line), ("Puppy", 14));
+    let overrides: Vec<(&str, &str)> = puppy
+        .overrides
+        .iter()
+        .map(|o| (o.method.as_str(), o.overrides.as_str()))
+        .collect();
+    // The nearest overridden definition wins
+    assert_eq!(
+        overrides,
+        vec![
+            ("speak", "animals.py:10 Dog.speak"),
+            ("move", "animals.py:5 Animal.move"),
+        ]
+    );
+}
+
+#[test]
+fn test_hierarchy_tool_expands_transitively() {
+    let (_dir, search) = setup_indexed(CLASS_FIXTURES);
+
+    let result = execute_hierarchy(
+        &search,
+        HierarchyInput {
+            name: "Animal".to_string(),
+            depth: 2,
+            limit: 50,
+        },
+    )
+    .unwrap();
+
+    let descendants: Vec<(&str, usize, Option<&str>)> = result
+        .descendants
+        .iter()
+        .map(|e| (e.name.as_str(), e.depth, e.via.as_deref()))
+        .collect();
+    assert_eq!(
+        descendants,
+        vec![("Dog", 1, None), ("Puppy", 2, Some("Dog"))]
+    );
+    assert!(result.ancestors.is_empty());
+    assert!(
+        execute_hierarchy(
+            &search,
+            HierarchyInput {
+                name: "Animal".to_string(),
+                depth: 1,
+                limit: 50,
+            },
+        )
+        .unwrap()
+        .descendants
+        .len()
+            == 1
+    );
+}
+
+#[test]
+fn test_hierarchy_tool_interfaces_and_external_supertypes() {
+    let (_dir, search) = setup_indexed(CLASS_FIXTURES);
+
+    let service = execute_hierarchy(
+        &search,
+        HierarchyInput {
+            name: "Service".to_string(),
+            depth: 1,
+            limit: 50,
+        },
+    )
+    .unwrap();
+    assert_eq!(service.descendants.len(), 1);
+    assert_eq!(service.descendants[0].path, "Worker.java");
+    assert_eq!(service.descendants[0].overrides.len(), 1);
+    assert_eq!(
+        service.descendants[0].overrides[0].overrides,
+        "Service.java:2 Service.run"
+    );
+
+    let robot = execute_hierarchy(
+        &search,
+        HierarchyInput {
+            name: "Robot".to_string(),
+            depth: 1,
+            limit: 50,
+        },
+    )
+    .unwrap();
+    assert!(robot.ancestors.is_empty());
+    assert_eq!(robot.external, vec!["Fault", "Exception"]);
+}
+
+#[test]
+fn test_hierarchy_tool_unknown_class_hints() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let result = execute_hierarchy(
+        &search,
+        HierarchyInput {
+            name: "Nonexistent".to_string(),
+            depth: 1,
+            limit: 50,
+        },
+    )
+    .unwrap();
+
+    assert!(result.class.is_none());
+    assert!(result.hint.unwrap().contains("No class or interface"));
+}
+
+// ============================================================================
+// Tests For Tool Tests
+// ============================================================================
+
+/// Rust, Python and TypeScript sources with tests in the same file and by naming convention.
+const TEST_FIXTURES: &[(&str, &str)] = &[
+    (
+        "billing.rs",
+        r#"pub fn charge(amount: u32) -> u32 {
+    amount
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charge() {
+        assert_eq!(charge(3), 3);
+    }
+
+    #[test]
+    fn test_other() {}
+}
+"#,
+    ),
+    (
+        "tests/billing.rs",
+        r#"fn setup() -> u32 {
+    billing::charge(1)
+}
+
+#[test]
+fn charges_customer() {
+    billing::charge(2);
+}
+
+#[test]
+fn uses_setup() {
+    setup();
+}
+"#,
+    ),
+    ("cart.py", "def total(items):\n    return sum(items)\n"),
+    (
+        "tests/test_cart.py",
+        "def test_total():\n    assert total([1]) == 1\n\n\ndef test_empty():\n    pass\n",
+    ),
+    (
+        "slug.ts",
+        "export function slugify(s: string) {\n  return s;\n}\n",
+    ),
+    (
+        "slug.test.ts",
+        "describe('slugify', () => {\n  it('lowercases', () => {\n    slugify('A');\n  });\n});\n",
+    ),
+];
+
+#[test]
+fn test_tests_for_symbol_ranks_references_first() {
+    let (_dir, search) = setup_indexed(TEST_FIXTURES);
+
+    let result = execute_tests_for(
+        &search,
+        TestsForInput {
+            target: "charge".to_string(),
+            limit: 20,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(result.target.as_deref(), Some("billing.rs:1 charge"));
+    let found: Vec<(&str, &str)> = result
+        .tests
+        .iter()
+        .map(|t| (t.path.as_str(), t.name.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("billing.rs", "test_charge"),
+            ("tests/billing.rs", "charges_customer"),
+            ("tests/billing.rs", "uses_setup"),
+            ("billing.rs", "test_other"),
+        ]
+    );
+    assert_eq!(
+        result.tests[0].reasons,
+        vec!["references charge", "same file"]
+    );
+    assert_eq!(
+        result.tests[2].reasons,
+        vec!["via helper setup", "naming convention"]
+    );
+}
+
+#[test]
+fn test_tests_for_file_uses_naming_conventions() {
+    let (_dir, search) = setup_indexed(TEST_FIXTURES);
+
+    let result = execute_tests_for(
+        &search,
+        TestsForInput {
+            target: "cart.py".to_string(),
+            limit: 20,
+        },
+    )
+    .unwrap();
+
+    let found: Vec<(&str, &str)> = result
+        .tests
+        .iter()
+        .map(|t| (t.path.as_str(), t.name.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("tests/test_cart.py", "test_total"),
+            ("tests/test_cart.py", "test_empty"),
+        ]
+    );
+
+    let js = execute_tests_for(
+        &search,
+        TestsForInput {
+            target: "slug.ts".to_string(),
+            limit: 20,
+        },
+    )
+    .unwrap();
+    assert_eq!(js.tests.len(), 1);
+    assert_eq!(js.tests[0].name, "lowercases");
+}
+
+#[test]
+fn test_tests_for_unknown_target_hints() {
+    let (_dir, search) = setup_indexed(&[]);
+
+    let result = execute_tests_for(
+        &search,
+        TestsForInput {
+            target: "no_such_function".to_string(),
+            limit: 20,
+        },
+    )
+    .unwrap();
+
+    assert!(result.target.is_none() && result.tests.is_empty());
+    assert!(result.hint.unwrap().contains("No symbol named"));
 }
 
 // ============================================================================
@@ -748,6 +2672,9 @@ fn test_search_results_exclude_sensitive_files() {
         query: "secret".to_string(),
         limit: 20,
         mode: SearchMode::Combined,
+        aggregate: None,
+        max_per_directory: None,
+        filter: RegionFilter::All,
     };
 
     let result = execute_search(&search, input).unwrap();